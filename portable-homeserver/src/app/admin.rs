@@ -1,5 +1,5 @@
-use anyhow::{Context, Result};
-use reqwest::Url;
+use anyhow::{Context, Result, bail};
+use reqwest::{StatusCode, Url};
 use serde::Deserialize;
 
 #[derive(Clone, Debug, Deserialize)]
@@ -9,9 +9,39 @@ pub(crate) struct AdminInfo {
     pub(crate) total_disk_used_mb: f64,
     pub(crate) num_signup_codes: u64,
     pub(crate) num_unused_signup_codes: u64,
+    /// Open client connections. Only reported by homeservers that track them.
+    #[serde(default)]
+    pub(crate) active_connections: Option<u64>,
+    /// Highest connection count the homeserver has recorded recently, if any.
+    #[serde(default)]
+    pub(crate) peak_connections: Option<u64>,
 }
 
-pub(crate) async fn fetch_info(base_url: &str, password: &str) -> Result<AdminInfo> {
+impl AdminInfo {
+    /// Fold this poll into the peak seen so far, or `None` when the homeserver
+    /// does not report connections at all.
+    pub(crate) fn connection_peak(&self, previous: Option<u64>) -> Option<u64> {
+        let active = self.active_connections?;
+        [previous, Some(active), self.peak_connections]
+            .into_iter()
+            .flatten()
+            .max()
+    }
+}
+
+/// Homeserver release series whose admin API this client was written against.
+pub(crate) const SUPPORTED_HOMESERVER_SERIES: &str = "0.6";
+
+/// Outcome of polling `/info`, which doubles as the admin API handshake.
+#[derive(Clone, Debug)]
+pub(crate) enum InfoResponse {
+    Info(AdminInfo),
+    /// The server answered in a way this client does not understand, which
+    /// usually means a different homeserver version.
+    Incompatible(String),
+}
+
+pub(crate) async fn fetch_info(base_url: &str, password: &str) -> Result<InfoResponse> {
     let client = reqwest::Client::new();
     let url = endpoint(base_url, "/info")?;
     let response = client
@@ -19,14 +49,29 @@ pub(crate) async fn fetch_info(base_url: &str, password: &str) -> Result<AdminIn
         .header("X-Admin-Password", password)
         .send()
         .await
-        .context("Failed to reach the admin info endpoint")?
-        .error_for_status()
-        .context("Admin server rejected the info request")?;
-
-    response
-        .json::<AdminInfo>()
+        .context("Failed to reach the admin info endpoint")?;
+    let status = response.status();
+    let body = response
+        .text()
         .await
-        .context("Failed to parse info response")
+        .context("Failed to read info response")?;
+
+    classify_info_response(status, &body)
+}
+
+fn classify_info_response(status: StatusCode, body: &str) -> Result<InfoResponse> {
+    if is_unsupported_status(status) {
+        return Ok(InfoResponse::Incompatible(format!(
+            "the admin API has no /info endpoint (HTTP {status})"
+        )));
+    }
+    if !status.is_success() {
+        bail!("Admin server rejected the info request (HTTP {status})");
+    }
+    Ok(match serde_json::from_str::<AdminInfo>(body) {
+        Ok(info) => InfoResponse::Info(info),
+        Err(err) => InfoResponse::Incompatible(format!("unexpected /info response: {err}")),
+    })
 }
 
 pub(crate) async fn generate_signup_token(base_url: &str, password: &str) -> Result<String> {
@@ -47,6 +92,19 @@ pub(crate) async fn generate_signup_token(base_url: &str, password: &str) -> Res
         .context("Failed to read signup token response body")
 }
 
+/// Prefix of the one-line invites the Swiss Knife Sessions tab understands.
+pub(crate) const INVITE_SCHEME: &str = "pubky-invite:";
+
+/// Bundle the homeserver key and a signup token into a single invite string,
+/// e.g. `pubky-invite:<homeserver key>?code=<token>`.
+pub(crate) fn format_invite(homeserver_public_key: &str, signup_token: &str) -> String {
+    format!(
+        "{INVITE_SCHEME}{}?code={}",
+        homeserver_public_key.trim(),
+        signup_token.trim()
+    )
+}
+
 pub(crate) async fn delete_entry(base_url: &str, password: &str, entry_path: &str) -> Result<()> {
     let client = reqwest::Client::new();
     let url = endpoint(base_url, &format!("/webdav/{}", entry_path))?;
@@ -83,7 +141,90 @@ pub(crate) async fn toggle_user_disabled(
     Ok(())
 }
 
+/// Older homeservers answer unknown admin routes with these statuses.
+fn is_unsupported_status(status: StatusCode) -> bool {
+    matches!(
+        status,
+        StatusCode::NOT_FOUND | StatusCode::METHOD_NOT_ALLOWED | StatusCode::NOT_IMPLEMENTED
+    )
+}
+
 fn endpoint(base_url: &str, path: &str) -> Result<Url> {
     let url = Url::parse(base_url).context("Invalid admin base URL")?;
     url.join(path).context("Invalid admin endpoint path")
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn info_with(active: Option<u64>, peak: Option<u64>) -> AdminInfo {
+        AdminInfo {
+            num_users: 1,
+            num_disabled_users: 0,
+            total_disk_used_mb: 0.0,
+            num_signup_codes: 0,
+            num_unused_signup_codes: 0,
+            active_connections: active,
+            peak_connections: peak,
+        }
+    }
+
+    #[test]
+    fn info_without_connection_fields_still_parses() {
+        let info: AdminInfo = serde_json::from_str(
+            r#"{"num_users":2,"num_disabled_users":0,"total_disk_used_mb":1.5,"num_signup_codes":3,"num_unused_signup_codes":1}"#,
+        )
+        .expect("older info payload should parse");
+        assert_eq!(info.active_connections, None);
+        assert_eq!(info.connection_peak(Some(9)), None);
+    }
+
+    #[test]
+    fn connection_peak_tracks_the_highest_value() {
+        assert_eq!(info_with(Some(3), None).connection_peak(None), Some(3));
+        assert_eq!(info_with(Some(3), None).connection_peak(Some(7)), Some(7));
+        assert_eq!(
+            info_with(Some(3), Some(12)).connection_peak(Some(7)),
+            Some(12)
+        );
+    }
+
+    #[test]
+    fn info_handshake_flags_incompatible_servers() {
+        let missing = classify_info_response(StatusCode::NOT_FOUND, "").unwrap();
+        assert!(matches!(missing, InfoResponse::Incompatible(_)));
+
+        let reshaped = classify_info_response(StatusCode::OK, r#"{"users":3}"#).unwrap();
+        assert!(matches!(reshaped, InfoResponse::Incompatible(_)));
+
+        assert!(classify_info_response(StatusCode::UNAUTHORIZED, "").is_err());
+
+        let ok = classify_info_response(
+            StatusCode::OK,
+            r#"{"num_users":2,"num_disabled_users":0,"total_disk_used_mb":1.5,"num_signup_codes":3,"num_unused_signup_codes":1}"#,
+        )
+        .unwrap();
+        assert!(matches!(ok, InfoResponse::Info(info) if info.num_users == 2));
+    }
+
+    #[test]
+    fn invites_bundle_key_and_token() {
+        assert_eq!(
+            format_invite(
+                " 8pinxxgqs41n4aididenw5apqp1urfmzdztr8jt4abrkdn435ewo ",
+                "AB12-CD34\n"
+            ),
+            "pubky-invite:8pinxxgqs41n4aididenw5apqp1urfmzdztr8jt4abrkdn435ewo?code=AB12-CD34"
+        );
+    }
+
+    #[test]
+    fn missing_routes_mean_unsupported() {
+        assert!(is_unsupported_status(StatusCode::NOT_FOUND));
+        assert!(is_unsupported_status(StatusCode::METHOD_NOT_ALLOWED));
+        assert!(is_unsupported_status(StatusCode::NOT_IMPLEMENTED));
+        assert!(!is_unsupported_status(StatusCode::UNAUTHORIZED));
+        assert!(!is_unsupported_status(StatusCode::OK));
+    }
+}
@@ -0,0 +1,248 @@
+use std::{
+    fs::{self, OpenOptions},
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, Result};
+use time::OffsetDateTime;
+use time::format_description::well_known::Rfc3339;
+
+use super::state::NetworkProfile;
+
+/// File inside the data directory that admin actions are appended to.
+const AUDIT_LOG_FILE_NAME: &str = "admin-audit.log";
+
+/// How many of the most recent entries the admin panel shows.
+pub(crate) const AUDIT_PANEL_LIMIT: usize = 50;
+
+const FIELD_SEPARATOR: char = '\t';
+const SUCCESS_MARKER: &str = "ok";
+const FAILURE_PREFIX: &str = "failed: ";
+
+/// Admin operations that are recorded in the audit log.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum AuditAction {
+    GenerateSignupToken,
+    DeleteEntry,
+    DisableUser,
+    EnableUser,
+    ChangeAdminPassword,
+}
+
+impl AuditAction {
+    const ALL: [Self; 5] = [
+        Self::GenerateSignupToken,
+        Self::DeleteEntry,
+        Self::DisableUser,
+        Self::EnableUser,
+        Self::ChangeAdminPassword,
+    ];
+
+    fn key(self) -> &'static str {
+        match self {
+            Self::GenerateSignupToken => "generate_signup_token",
+            Self::DeleteEntry => "delete_entry",
+            Self::DisableUser => "disable_user",
+            Self::EnableUser => "enable_user",
+            Self::ChangeAdminPassword => "change_admin_password",
+        }
+    }
+
+    pub(crate) fn label(self) -> &'static str {
+        match self {
+            Self::GenerateSignupToken => "Signup token generated",
+            Self::DeleteEntry => "Entry deleted",
+            Self::DisableUser => "User disabled",
+            Self::EnableUser => "User enabled",
+            Self::ChangeAdminPassword => "Admin password changed",
+        }
+    }
+
+    fn from_key(key: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|action| action.key() == key)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) enum AuditOutcome {
+    Success,
+    Failure(String),
+}
+
+/// One admin action taken from the app.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct AuditEntry {
+    pub(crate) timestamp: String,
+    pub(crate) network: String,
+    pub(crate) action: AuditAction,
+    pub(crate) target: String,
+    pub(crate) outcome: AuditOutcome,
+}
+
+impl AuditEntry {
+    pub(crate) fn now(
+        network: NetworkProfile,
+        action: AuditAction,
+        target: impl Into<String>,
+        outcome: AuditOutcome,
+    ) -> Self {
+        let timestamp = OffsetDateTime::now_utc()
+            .format(&Rfc3339)
+            .unwrap_or_else(|_| String::from("unknown"));
+        Self {
+            timestamp,
+            network: network.label().to_string(),
+            action,
+            target: target.into(),
+            outcome,
+        }
+    }
+
+    fn to_line(&self) -> String {
+        let outcome = match &self.outcome {
+            AuditOutcome::Success => SUCCESS_MARKER.to_string(),
+            AuditOutcome::Failure(reason) => format!("{FAILURE_PREFIX}{}", single_line(reason)),
+        };
+        [
+            single_line(&self.timestamp),
+            single_line(&self.network),
+            self.action.key().to_string(),
+            single_line(&self.target),
+            outcome,
+        ]
+        .join(&FIELD_SEPARATOR.to_string())
+    }
+
+    fn from_line(line: &str) -> Option<Self> {
+        let mut fields = line.splitn(5, FIELD_SEPARATOR);
+        let timestamp = fields.next()?.to_string();
+        let network = fields.next()?.to_string();
+        let action = AuditAction::from_key(fields.next()?)?;
+        let target = fields.next()?.to_string();
+        let outcome = match fields.next()? {
+            SUCCESS_MARKER => AuditOutcome::Success,
+            other => AuditOutcome::Failure(
+                other
+                    .strip_prefix(FAILURE_PREFIX)
+                    .unwrap_or(other)
+                    .to_string(),
+            ),
+        };
+        Some(Self {
+            timestamp,
+            network,
+            action,
+            target,
+            outcome,
+        })
+    }
+}
+
+pub(crate) fn audit_log_path(data_dir: &str) -> PathBuf {
+    Path::new(data_dir.trim()).join(AUDIT_LOG_FILE_NAME)
+}
+
+/// Append `entry` to the audit log in `data_dir`, creating the file if needed.
+pub(crate) fn append_audit_entry(data_dir: &str, entry: &AuditEntry) -> Result<()> {
+    let path = audit_log_path(data_dir);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create data directory at {}", parent.display()))?;
+    }
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .with_context(|| format!("Failed to open {}", path.display()))?;
+    writeln!(file, "{}", entry.to_line())
+        .with_context(|| format!("Failed to write {}", path.display()))
+}
+
+/// Read up to `limit` entries from the audit log, newest first. Malformed lines
+/// are skipped so a hand-edited file never hides the rest of the trail.
+pub(crate) fn read_recent_audit_entries(data_dir: &str, limit: usize) -> Result<Vec<AuditEntry>> {
+    let path = audit_log_path(data_dir);
+    if !path.is_file() {
+        return Ok(Vec::new());
+    }
+    let contents =
+        fs::read_to_string(&path).with_context(|| format!("Failed to read {}", path.display()))?;
+    Ok(contents
+        .lines()
+        .rev()
+        .filter_map(AuditEntry::from_line)
+        .take(limit)
+        .collect())
+}
+
+fn single_line(value: &str) -> String {
+    value
+        .chars()
+        .map(|ch| if ch.is_control() { ' ' } else { ch })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn entry_roundtrips_through_a_line() {
+        let entry = AuditEntry::now(
+            NetworkProfile::Mainnet,
+            AuditAction::DeleteEntry,
+            "pk/pub/a\tb.txt",
+            AuditOutcome::Failure("404\nnot found".into()),
+        );
+        let parsed = AuditEntry::from_line(&entry.to_line()).expect("line should parse");
+
+        assert_eq!(parsed.action, AuditAction::DeleteEntry);
+        assert_eq!(parsed.target, "pk/pub/a b.txt");
+        assert_eq!(
+            parsed.outcome,
+            AuditOutcome::Failure("404 not found".into())
+        );
+        assert_eq!(parsed.network, NetworkProfile::Mainnet.label());
+    }
+
+    #[test]
+    fn appends_and_reads_newest_first() {
+        let temp_dir = tempfile::tempdir().expect("temp dir");
+        let dir = temp_dir.path().to_str().unwrap();
+
+        for (action, target) in [
+            (AuditAction::GenerateSignupToken, ""),
+            (AuditAction::DisableUser, "pk1"),
+            (AuditAction::EnableUser, "pk1"),
+        ] {
+            let entry = AuditEntry::now(
+                NetworkProfile::Mainnet,
+                action,
+                target,
+                AuditOutcome::Success,
+            );
+            append_audit_entry(dir, &entry).expect("entry should be appended");
+        }
+        fs::write(
+            audit_log_path(dir),
+            fs::read_to_string(audit_log_path(dir)).unwrap() + "garbage line\n",
+        )
+        .unwrap();
+
+        let entries = read_recent_audit_entries(dir, 2).expect("entries should load");
+        let actions: Vec<_> = entries.iter().map(|entry| entry.action).collect();
+        assert_eq!(
+            actions,
+            vec![AuditAction::EnableUser, AuditAction::DisableUser]
+        );
+    }
+
+    #[test]
+    fn missing_log_reads_as_empty() {
+        let temp_dir = tempfile::tempdir().expect("temp dir");
+        let entries = read_recent_audit_entries(temp_dir.path().to_str().unwrap(), 10)
+            .expect("read should succeed");
+        assert!(entries.is_empty());
+    }
+}
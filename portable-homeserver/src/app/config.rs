@@ -2,7 +2,7 @@
 use std::mem::ManuallyDrop;
 use std::{
     env, fs,
-    net::{IpAddr, SocketAddr},
+    net::{IpAddr, Ipv4Addr, SocketAddr},
     path::{Path, PathBuf},
     str::FromStr,
 };
@@ -22,6 +22,8 @@ use jni::{
 #[cfg(target_os = "android")]
 use ndk_context::android_context;
 
+use super::wizard::MIN_ADMIN_PASSWORD_LEN;
+
 /// Shape of the editable configuration exposed in the UI form.
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub(crate) struct ConfigForm {
@@ -88,6 +90,8 @@ pub(crate) struct ConfigState {
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub(crate) enum ConfigFeedback {
     Saved,
+    /// The new signup mode was saved but only takes effect after a restart.
+    SignupModeNeedsRestart(String),
     ValidationError(String),
     PersistenceError(String),
 }
@@ -100,6 +104,60 @@ pub(crate) enum ConfigPersistOutcome {
     Unchanged,
 }
 
+/// Which interfaces the drive listen sockets bind to. The admin socket is
+/// left alone so the admin API is never exposed by this toggle.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum BindScope {
+    LocalOnly,
+    AllInterfaces,
+    /// Any other combination, including sockets that do not parse.
+    Custom,
+}
+
+impl BindScope {
+    fn ip(self) -> Option<IpAddr> {
+        match self {
+            BindScope::LocalOnly => Some(IpAddr::V4(Ipv4Addr::LOCALHOST)),
+            BindScope::AllInterfaces => Some(IpAddr::V4(Ipv4Addr::UNSPECIFIED)),
+            BindScope::Custom => None,
+        }
+    }
+
+    /// The scope the form's drive sockets currently describe.
+    pub(crate) fn of(form: &ConfigForm) -> Self {
+        let ips = [
+            &form.drive_pubky_listen_socket,
+            &form.drive_icann_listen_socket,
+        ]
+        .map(|raw| raw.trim().parse::<SocketAddr>().ok().map(|addr| addr.ip()));
+        [BindScope::LocalOnly, BindScope::AllInterfaces]
+            .into_iter()
+            .find(|scope| ips.iter().all(|ip| *ip == scope.ip()))
+            .unwrap_or(BindScope::Custom)
+    }
+}
+
+/// Rewrite the drive listen sockets to bind to `scope`, keeping their ports.
+pub(crate) fn apply_bind_scope(form: &mut ConfigForm, scope: BindScope) -> Result<()> {
+    let Some(ip) = scope.ip() else {
+        return Ok(());
+    };
+    let mut pubky = parse_socket("Pubky TLS listen socket", &form.drive_pubky_listen_socket)?;
+    let mut icann = parse_socket("ICANN HTTP listen socket", &form.drive_icann_listen_socket)?;
+    pubky.set_ip(ip);
+    icann.set_ip(ip);
+    form.drive_pubky_listen_socket = pubky.to_string();
+    form.drive_icann_listen_socket = icann.to_string();
+    Ok(())
+}
+
+/// Whether `config.toml` already exists in `data_dir`. A missing file means the
+/// operator has never launched a node from this folder.
+pub(crate) fn config_exists(data_dir: &str) -> bool {
+    let trimmed = data_dir.trim();
+    !trimmed.is_empty() && Path::new(trimmed).join("config.toml").is_file()
+}
+
 pub(crate) fn load_config_form_from_dir(data_dir: &str) -> Result<ConfigForm> {
     if data_dir.trim().is_empty() {
         return Ok(ConfigForm::default());
@@ -177,6 +235,44 @@ pub(crate) fn persist_config_form(
     Ok(ConfigPersistOutcome::Updated)
 }
 
+/// Write only the signup mode to `config.toml`, leaving any other unsaved form
+/// edits alone. Returns the form as now stored on disk.
+pub(crate) fn persist_signup_mode(data_dir: &str, mode: &SignupMode) -> Result<ConfigForm> {
+    let mut stored = load_config_form_from_dir(data_dir)?;
+    stored.signup_mode = mode.clone();
+    persist_config_form(data_dir, &stored)?;
+    Ok(stored)
+}
+
+/// Write only the admin password to `config.toml`, like
+/// [`persist_signup_mode`]. Returns the form as now stored on disk.
+pub(crate) fn persist_admin_password(data_dir: &str, password: &str) -> Result<ConfigForm> {
+    let mut stored = load_config_form_from_dir(data_dir)?;
+    stored.admin_password = password.to_string();
+    persist_config_form(data_dir, &stored)?;
+    Ok(stored)
+}
+
+/// Check a replacement admin password typed twice in the admin panel.
+pub(crate) fn validate_new_admin_password(
+    current: &str,
+    new_password: &str,
+    confirmation: &str,
+) -> Result<(), String> {
+    if new_password.trim().len() < MIN_ADMIN_PASSWORD_LEN {
+        return Err(format!(
+            "Use an admin password with at least {MIN_ADMIN_PASSWORD_LEN} characters."
+        ));
+    }
+    if new_password != confirmation {
+        return Err("The two passwords do not match.".into());
+    }
+    if new_password == current {
+        return Err("The new password is the same as the current one.".into());
+    }
+    Ok(())
+}
+
 pub(crate) fn apply_config_form(form: &ConfigForm, config: &mut ConfigToml) -> Result<()> {
     config.general.signup_mode = form.signup_mode.clone();
 
@@ -358,7 +454,104 @@ fn parse_optional_domain(raw: &str) -> Result<Option<Domain>> {
         .map_err(|err| anyhow!("Invalid domain '{}': {}", trimmed, err))
 }
 
+/// Levels the homeserver understands for `logging.level`.
+const LOGGING_LEVELS: [&str; 6] = ["trace", "debug", "info", "warn", "error", "off"];
+
+/// Named choices for the logging level dropdown, so the common cases need no
+/// typing.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum LoggingPreset {
+    Default,
+    Quiet,
+    Normal,
+    Verbose,
+    Trace,
+    Custom,
+}
+
+impl LoggingPreset {
+    pub(crate) const ALL: [LoggingPreset; 6] = [
+        LoggingPreset::Default,
+        LoggingPreset::Quiet,
+        LoggingPreset::Normal,
+        LoggingPreset::Verbose,
+        LoggingPreset::Trace,
+        LoggingPreset::Custom,
+    ];
+
+    pub(crate) fn key(self) -> &'static str {
+        match self {
+            LoggingPreset::Default => "default",
+            LoggingPreset::Quiet => "quiet",
+            LoggingPreset::Normal => "normal",
+            LoggingPreset::Verbose => "verbose",
+            LoggingPreset::Trace => "trace",
+            LoggingPreset::Custom => "custom",
+        }
+    }
+
+    pub(crate) fn label(self) -> &'static str {
+        match self {
+            LoggingPreset::Default => "Homeserver default",
+            LoggingPreset::Quiet => "Quiet (warn)",
+            LoggingPreset::Normal => "Normal (info)",
+            LoggingPreset::Verbose => "Verbose (debug)",
+            LoggingPreset::Trace => "Trace",
+            LoggingPreset::Custom => "Advanced: custom level",
+        }
+    }
+
+    /// Value written to `logging_level`; `None` for the custom entry, which
+    /// keeps whatever was typed.
+    pub(crate) fn level(self) -> Option<&'static str> {
+        match self {
+            LoggingPreset::Default => Some(""),
+            LoggingPreset::Quiet => Some("warn"),
+            LoggingPreset::Normal => Some("info"),
+            LoggingPreset::Verbose => Some("debug"),
+            LoggingPreset::Trace => Some("trace"),
+            LoggingPreset::Custom => None,
+        }
+    }
+
+    pub(crate) fn from_key(key: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|preset| preset.key() == key)
+    }
+
+    /// The preset matching a stored level, or `Custom` for anything else.
+    pub(crate) fn for_level(raw: &str) -> Self {
+        let normalized = raw.trim().to_ascii_lowercase();
+        Self::ALL
+            .into_iter()
+            .find(|preset| preset.level() == Some(normalized.as_str()))
+            .unwrap_or(LoggingPreset::Custom)
+    }
+}
+
+/// Check a custom logging level before it is saved, with a hint that names
+/// the accepted values.
+pub(crate) fn validate_logging_level(raw: &str) -> Result<(), String> {
+    let trimmed = raw.trim();
+    if trimmed.is_empty() {
+        return Ok(());
+    }
+    if trimmed.contains(['=', ',']) {
+        return Err(format!(
+            "'{trimmed}' looks like a per-module directive. Only a single level is supported here; set module filters in config.toml under [logging]."
+        ));
+    }
+    if LOGGING_LEVELS.contains(&trimmed.to_ascii_lowercase().as_str()) {
+        Ok(())
+    } else {
+        Err(format!(
+            "'{trimmed}' is not a logging level. Use {}.",
+            LOGGING_LEVELS.join(", ")
+        ))
+    }
+}
+
 fn parse_logging_level(raw: &str, existing: Option<LoggingToml>) -> Result<Option<LoggingToml>> {
+    validate_logging_level(raw).map_err(|message| anyhow!(message))?;
     let trimmed = raw.trim();
     if trimmed.is_empty() {
         return Ok(existing.map(|mut logging| {
@@ -369,7 +562,7 @@ fn parse_logging_level(raw: &str, existing: Option<LoggingToml>) -> Result<Optio
 
     let parsed = trimmed.parse().map_err(|err| {
         anyhow!(
-            "Invalid logging level '{}': {}. Use trace, debug, info, warn, or error.",
+            "Invalid logging level '{}': {}. Use trace, debug, info, warn, error, or off.",
             trimmed,
             err
         )
@@ -444,4 +637,130 @@ mod tests {
             .expect("second write should short circuit");
         assert_eq!(second, ConfigPersistOutcome::Unchanged);
     }
+
+    #[test]
+    fn persist_signup_mode_keeps_other_settings() {
+        let temp_dir = tempfile::tempdir().expect("temp dir");
+        let dir = temp_dir.path().to_str().unwrap();
+        let mut form = ConfigForm::default();
+        form.admin_password = "on-disk".into();
+        form.signup_mode = SignupMode::TokenRequired;
+        persist_config_form(dir, &form).expect("config should persist");
+
+        let stored = persist_signup_mode(dir, &SignupMode::Open).expect("mode should persist");
+        assert_eq!(stored.admin_password, "on-disk");
+
+        let saved = ConfigToml::from_file(temp_dir.path().join("config.toml"))
+            .expect("config should parse");
+        assert_eq!(saved.general.signup_mode, SignupMode::Open);
+        assert_eq!(saved.admin.admin_password, "on-disk");
+    }
+
+    #[test]
+    fn persist_admin_password_keeps_other_settings() {
+        let temp_dir = tempfile::tempdir().expect("temp dir");
+        let dir = temp_dir.path().to_str().unwrap();
+        let mut form = ConfigForm::default();
+        form.admin_password = "old-password".into();
+        form.signup_mode = SignupMode::TokenRequired;
+        persist_config_form(dir, &form).expect("config should persist");
+
+        let stored = persist_admin_password(dir, "new-password").expect("password should persist");
+        assert_eq!(stored.signup_mode, SignupMode::TokenRequired);
+
+        let saved = ConfigToml::from_file(temp_dir.path().join("config.toml"))
+            .expect("config should parse");
+        assert_eq!(saved.admin.admin_password, "new-password");
+        assert_eq!(saved.general.signup_mode, SignupMode::TokenRequired);
+    }
+
+    #[test]
+    fn validates_new_admin_passwords() {
+        assert!(validate_new_admin_password("old-password", "short", "short").is_err());
+        assert!(validate_new_admin_password("old-password", "new-password", "typo").is_err());
+        assert!(
+            validate_new_admin_password("old-password", "old-password", "old-password").is_err()
+        );
+        assert_eq!(
+            validate_new_admin_password("old-password", "new-password", "new-password"),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn bind_scope_rewrites_drive_sockets_only() {
+        let mut form = ConfigForm {
+            drive_pubky_listen_socket: String::from("127.0.0.1:6287"),
+            drive_icann_listen_socket: String::from(" 127.0.0.1:6286 "),
+            admin_listen_socket: String::from("127.0.0.1:6288"),
+            ..ConfigForm::default()
+        };
+        assert_eq!(BindScope::of(&form), BindScope::LocalOnly);
+
+        apply_bind_scope(&mut form, BindScope::AllInterfaces).expect("sockets should parse");
+        assert_eq!(form.drive_pubky_listen_socket, "0.0.0.0:6287");
+        assert_eq!(form.drive_icann_listen_socket, "0.0.0.0:6286");
+        assert_eq!(form.admin_listen_socket, "127.0.0.1:6288");
+        assert_eq!(BindScope::of(&form), BindScope::AllInterfaces);
+
+        form.drive_icann_listen_socket = String::from("192.168.1.5:6286");
+        assert_eq!(BindScope::of(&form), BindScope::Custom);
+
+        form.drive_pubky_listen_socket = String::from("not-a-socket");
+        assert!(apply_bind_scope(&mut form, BindScope::LocalOnly).is_err());
+        assert_eq!(form.drive_icann_listen_socket, "192.168.1.5:6286");
+    }
+
+    #[test]
+    fn logging_presets_map_to_levels() {
+        assert_eq!(LoggingPreset::for_level(""), LoggingPreset::Default);
+        assert_eq!(LoggingPreset::for_level(" WARN "), LoggingPreset::Quiet);
+        assert_eq!(LoggingPreset::for_level("debug"), LoggingPreset::Verbose);
+        assert_eq!(LoggingPreset::for_level("error"), LoggingPreset::Custom);
+        for preset in LoggingPreset::ALL {
+            assert_eq!(LoggingPreset::from_key(preset.key()), Some(preset));
+        }
+    }
+
+    #[test]
+    fn invalid_logging_levels_are_rejected_before_save() {
+        assert_eq!(validate_logging_level("Error"), Ok(()));
+        assert!(validate_logging_level("verbose").is_err());
+        assert!(validate_logging_level("pubky_homeserver=debug").is_err());
+
+        let form = ConfigForm {
+            logging_level: String::from("verbose"),
+            ..ConfigForm::default()
+        };
+        let mut config = ConfigToml::default();
+        assert!(apply_config_form(&form, &mut config).is_err());
+    }
+
+    #[test]
+    fn off_disables_logging() {
+        assert_eq!(validate_logging_level(" OFF "), Ok(()));
+
+        let form = ConfigForm {
+            logging_level: String::from("off"),
+            ..ConfigForm::default()
+        };
+        let mut config = ConfigToml::default();
+        apply_config_form(&form, &mut config).expect("off is a valid level");
+        assert!(
+            ConfigForm::from_config(&config)
+                .logging_level
+                .eq_ignore_ascii_case("off")
+        );
+    }
+
+    #[test]
+    fn config_exists_tracks_config_file() {
+        let temp_dir = tempfile::tempdir().expect("temp dir");
+        let dir = temp_dir.path().to_str().unwrap();
+        assert!(!config_exists(dir));
+        assert!(!config_exists("   "));
+
+        persist_config_form(dir, &ConfigForm::default()).expect("config should persist");
+        assert!(config_exists(dir));
+    }
 }
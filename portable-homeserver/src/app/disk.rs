@@ -0,0 +1,147 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+/// File inside the data directory that stores the low-disk threshold.
+const DISK_ALERT_FILE_NAME: &str = "disk-alert.toml";
+
+/// Warn once the volume holding the data directory has less than this free.
+pub(crate) const DEFAULT_LOW_DISK_THRESHOLD_MB: u64 = 1024;
+
+const BYTES_PER_MB: u64 = 1024 * 1024;
+
+/// Operator-chosen threshold for the low-disk warning. Zero turns it off.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub(crate) struct DiskAlertSettings {
+    pub(crate) threshold_mb: u64,
+}
+
+impl Default for DiskAlertSettings {
+    fn default() -> Self {
+        Self {
+            threshold_mb: DEFAULT_LOW_DISK_THRESHOLD_MB,
+        }
+    }
+}
+
+fn disk_alert_path(data_dir: &str) -> PathBuf {
+    Path::new(data_dir.trim()).join(DISK_ALERT_FILE_NAME)
+}
+
+/// Load the threshold saved for `data_dir`, falling back to the default.
+pub(crate) fn load_disk_alert_settings(data_dir: &str) -> DiskAlertSettings {
+    if data_dir.trim().is_empty() {
+        return DiskAlertSettings::default();
+    }
+    fs::read_to_string(disk_alert_path(data_dir))
+        .ok()
+        .and_then(|raw| toml::from_str(&raw).ok())
+        .unwrap_or_default()
+}
+
+pub(crate) fn save_disk_alert_settings(data_dir: &str, settings: DiskAlertSettings) -> Result<()> {
+    let path = disk_alert_path(data_dir);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
+    let rendered =
+        toml::to_string(&settings).context("Failed to encode the disk alert settings")?;
+    fs::write(&path, rendered).with_context(|| format!("Failed to write {}", path.display()))
+}
+
+/// Parse the threshold field from the admin panel.
+pub(crate) fn parse_threshold_mb(raw: &str) -> Result<u64, String> {
+    let trimmed = raw.trim();
+    if trimmed.is_empty() {
+        return Ok(0);
+    }
+    trimmed
+        .parse()
+        .map_err(|_| format!("{trimmed:?} is not a whole number of megabytes."))
+}
+
+/// Free space, in MB, on the volume that holds `path`. Walks up to the
+/// closest existing ancestor so a not-yet-created directory still resolves.
+pub(crate) fn free_space_mb(path: &Path) -> Result<u64> {
+    let existing = path
+        .ancestors()
+        .find(|candidate| candidate.exists())
+        .unwrap_or(path);
+    let bytes = fs2::available_space(existing)
+        .with_context(|| format!("Failed to read free space for {}", existing.display()))?;
+    Ok(bytes / BYTES_PER_MB)
+}
+
+/// Raised while the volume holding the homeserver data is nearly full.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct LowDiskWarning {
+    pub(crate) free_mb: u64,
+    pub(crate) threshold_mb: u64,
+    /// `total_disk_used_mb` from the admin API, when it could be fetched.
+    pub(crate) homeserver_used_mb: Option<f64>,
+}
+
+impl LowDiskWarning {
+    pub(crate) fn message(&self) -> String {
+        let mut message = format!(
+            "Only {} MB free on the disk holding the homeserver data (warning below {} MB).",
+            self.free_mb, self.threshold_mb
+        );
+        if let Some(used) = self.homeserver_used_mb {
+            message.push_str(&format!(" The homeserver currently stores {used:.1} MB."));
+        }
+        message.push_str(" Free up space before writes start failing.");
+        message
+    }
+}
+
+/// `Some` when `free_mb` is under a non-zero threshold.
+pub(crate) fn low_disk_warning(free_mb: u64, threshold_mb: u64) -> Option<LowDiskWarning> {
+    (threshold_mb > 0 && free_mb < threshold_mb).then_some(LowDiskWarning {
+        free_mb,
+        threshold_mb,
+        homeserver_used_mb: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn warns_only_below_a_non_zero_threshold() {
+        assert_eq!(low_disk_warning(2048, 1024), None);
+        assert_eq!(low_disk_warning(1024, 1024), None);
+        assert_eq!(low_disk_warning(5, 0), None);
+
+        let mut warning = low_disk_warning(512, 1024).expect("below threshold");
+        assert_eq!(warning.free_mb, 512);
+        assert!(!warning.message().contains("currently stores"));
+        warning.homeserver_used_mb = Some(40.0);
+        assert!(warning.message().contains("currently stores 40.0 MB"));
+    }
+
+    #[test]
+    fn parses_threshold_input() {
+        assert_eq!(parse_threshold_mb(" 2048 "), Ok(2048));
+        assert_eq!(parse_threshold_mb(""), Ok(0));
+        assert!(parse_threshold_mb("1.5GB").is_err());
+    }
+
+    #[test]
+    fn settings_roundtrip_through_data_dir() {
+        let temp_dir = tempfile::tempdir().expect("temp dir");
+        let dir = temp_dir.path().display().to_string();
+        assert_eq!(load_disk_alert_settings(&dir), DiskAlertSettings::default());
+
+        save_disk_alert_settings(&dir, DiskAlertSettings { threshold_mb: 300 }).expect("save");
+        assert_eq!(load_disk_alert_settings(&dir).threshold_mb, 300);
+        assert!(free_space_mb(&temp_dir.path().join("not-created-yet")).is_ok());
+    }
+}
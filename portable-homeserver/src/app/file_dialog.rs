@@ -0,0 +1,23 @@
+use std::path::PathBuf;
+
+#[derive(Debug, Clone)]
+pub(crate) enum FileDialogResult {
+    Selected(PathBuf),
+    Cancelled,
+    Unavailable,
+}
+
+#[cfg(target_os = "android")]
+pub(crate) fn save_file(_file_name: &str) -> FileDialogResult {
+    FileDialogResult::Unavailable
+}
+
+/// Open a save dialog suggesting `file_name`.
+#[cfg(not(target_os = "android"))]
+pub(crate) fn save_file(file_name: &str) -> FileDialogResult {
+    rfd::FileDialog::new()
+        .set_file_name(file_name)
+        .save_file()
+        .map(FileDialogResult::Selected)
+        .unwrap_or(FileDialogResult::Cancelled)
+}
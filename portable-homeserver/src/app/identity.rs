@@ -0,0 +1,224 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, Result, anyhow};
+use pubky_testnet::pubky::{Keypair, recovery_file};
+
+/// File the homeserver keeps its hex-encoded secret key in.
+const SECRET_FILE_NAME: &str = "secret";
+
+/// Backup written next to the secret before an imported key replaces it.
+const SECRET_BACKUP_FILE_NAME: &str = "secret.bak";
+
+pub(crate) fn secret_file_path(data_dir: &str) -> PathBuf {
+    Path::new(data_dir.trim()).join(SECRET_FILE_NAME)
+}
+
+/// Read the node keypair from the data directory, if one has been generated.
+pub(crate) fn read_node_keypair(data_dir: &str) -> Result<Option<Keypair>> {
+    let path = secret_file_path(data_dir);
+    if !path.is_file() {
+        return Ok(None);
+    }
+
+    let raw =
+        fs::read_to_string(&path).with_context(|| format!("Failed to read {}", path.display()))?;
+    let secret = decode_hex_secret(raw.trim())
+        .with_context(|| format!("{} does not contain a valid secret key", path.display()))?;
+    Ok(Some(Keypair::from_secret_key(&secret)))
+}
+
+/// Replace the node keypair, keeping a backup of the previous secret.
+pub(crate) fn write_node_keypair(data_dir: &str, keypair: &Keypair) -> Result<PathBuf> {
+    let path = secret_file_path(data_dir);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create data directory at {}", parent.display()))?;
+    }
+
+    if path.is_file() {
+        let backup = path.with_file_name(SECRET_BACKUP_FILE_NAME);
+        fs::copy(&path, &backup)
+            .with_context(|| format!("Failed to back up the node key to {}", backup.display()))?;
+    }
+
+    fs::write(&path, encode_hex(&keypair.secret_key()))
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+    Ok(path)
+}
+
+/// Encrypt the node key into a recovery file at `target`.
+pub(crate) fn export_node_recovery_file(
+    data_dir: &str,
+    target: &str,
+    passphrase: &str,
+) -> Result<PathBuf> {
+    if passphrase.is_empty() {
+        return Err(anyhow!("Enter a passphrase to encrypt the recovery file."));
+    }
+    let keypair = read_node_keypair(data_dir)?
+        .ok_or_else(|| anyhow!("No node key found yet. Start the server once to generate it."))?;
+
+    let target = recovery_target(target)?;
+    if let Some(parent) = target.parent()
+        && !parent.as_os_str().is_empty()
+    {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create directory {}", parent.display()))?;
+    }
+    let bytes = recovery_file::create_recovery_file(&keypair, passphrase);
+    fs::write(&target, bytes).with_context(|| format!("Failed to write {}", target.display()))?;
+    Ok(target)
+}
+
+/// Decrypt a recovery file and install its key as the node identity.
+pub(crate) fn import_node_recovery_file(
+    data_dir: &str,
+    source: &str,
+    passphrase: &str,
+) -> Result<Keypair> {
+    let source = recovery_target(source)?;
+    let bytes =
+        fs::read(&source).with_context(|| format!("Failed to read {}", source.display()))?;
+    let keypair = recovery_file::decrypt_recovery_file(&bytes, passphrase)
+        .map_err(|err| anyhow!("Failed to decrypt {}: {err}", source.display()))?;
+    write_node_keypair(data_dir, &keypair)?;
+    Ok(keypair)
+}
+
+fn recovery_target(raw: &str) -> Result<PathBuf> {
+    let trimmed = raw.trim();
+    if trimmed.is_empty() {
+        return Err(anyhow!("Enter the path of the recovery file."));
+    }
+
+    let mut path = expand_home(trimmed, home_dir().as_deref())?;
+    if path.extension().is_none() {
+        path.set_extension("pkarr");
+    }
+    Ok(path)
+}
+
+/// Resolve a leading `~` against `home`, as a shell would.
+fn expand_home(path: &str, home: Option<&Path>) -> Result<PathBuf> {
+    let Some(rest) = path.strip_prefix('~') else {
+        return Ok(PathBuf::from(path));
+    };
+    if !(rest.is_empty() || rest.starts_with('/') || rest.starts_with('\\')) {
+        // `~user/...` is not supported; treat it as a literal path.
+        return Ok(PathBuf::from(path));
+    }
+    let home = home.ok_or_else(|| anyhow!("Could not resolve the home directory for {path}."))?;
+    Ok(home.join(rest.trim_start_matches(['/', '\\'])))
+}
+
+fn home_dir() -> Option<PathBuf> {
+    std::env::var_os("HOME")
+        .or_else(|| std::env::var_os("USERPROFILE"))
+        .map(PathBuf::from)
+}
+
+fn encode_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+fn decode_hex_secret(raw: &str) -> Result<[u8; 32]> {
+    if raw.len() != 64 || !raw.is_ascii() {
+        return Err(anyhow!("expected 64 hex characters"));
+    }
+
+    let mut secret = [0u8; 32];
+    for (index, byte) in secret.iter_mut().enumerate() {
+        let pair = &raw[index * 2..index * 2 + 2];
+        *byte = u8::from_str_radix(pair, 16).map_err(|err| anyhow!("invalid hex: {err}"))?;
+    }
+    Ok(secret)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hex_secret_roundtrip() {
+        let secret = [0xabu8; 32];
+        let encoded = encode_hex(&secret);
+        assert_eq!(encoded.len(), 64);
+        assert_eq!(decode_hex_secret(&encoded).expect("valid hex"), secret);
+        assert!(decode_hex_secret("zz").is_err());
+    }
+
+    #[test]
+    fn recovery_paths_expand_a_leading_tilde() {
+        let home = Path::new("/home/operator");
+        assert_eq!(
+            expand_home("~/Backups/homeserver.pkarr", Some(home)).expect("home is known"),
+            home.join("Backups/homeserver.pkarr")
+        );
+        assert_eq!(expand_home("~", Some(home)).expect("home is known"), home);
+        assert_eq!(
+            expand_home("backups/~node", Some(home)).expect("relative path"),
+            PathBuf::from("backups/~node")
+        );
+        assert!(expand_home("~/node.pkarr", None).is_err());
+    }
+
+    #[test]
+    fn missing_secret_reads_as_none() {
+        let temp_dir = tempfile::tempdir().expect("temp dir");
+        let dir = temp_dir.path().to_str().unwrap();
+        assert!(
+            read_node_keypair(dir)
+                .expect("read should succeed")
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn export_and_import_node_identity() {
+        let source_dir = tempfile::tempdir().expect("temp dir");
+        let target_dir = tempfile::tempdir().expect("temp dir");
+        let source = source_dir.path().to_str().unwrap();
+        let target = target_dir.path().to_str().unwrap();
+
+        let keypair = Keypair::from_secret_key(&[3u8; 32]);
+        write_node_keypair(source, &keypair).expect("key should be written");
+
+        let bundle = source_dir.path().join("backup/node");
+        let exported = export_node_recovery_file(source, bundle.to_str().unwrap(), "passphrase")
+            .expect("export should succeed");
+        assert_eq!(
+            exported.extension().and_then(|ext| ext.to_str()),
+            Some("pkarr")
+        );
+
+        let previous = Keypair::from_secret_key(&[9u8; 32]);
+        write_node_keypair(target, &previous).expect("previous key should be written");
+        let imported = import_node_recovery_file(target, exported.to_str().unwrap(), "passphrase")
+            .expect("import should succeed");
+
+        assert_eq!(imported.public_key(), keypair.public_key());
+        let stored = read_node_keypair(target)
+            .expect("read should succeed")
+            .expect("key should exist");
+        assert_eq!(stored.public_key(), keypair.public_key());
+        assert!(target_dir.path().join(SECRET_BACKUP_FILE_NAME).is_file());
+    }
+
+    #[test]
+    fn import_rejects_wrong_passphrase() {
+        let temp_dir = tempfile::tempdir().expect("temp dir");
+        let dir = temp_dir.path().to_str().unwrap();
+        let keypair = Keypair::from_secret_key(&[5u8; 32]);
+        write_node_keypair(dir, &keypair).expect("key should be written");
+        let bundle = temp_dir.path().join("node.pkarr");
+        export_node_recovery_file(dir, bundle.to_str().unwrap(), "right")
+            .expect("export should succeed");
+
+        let err = import_node_recovery_file(dir, bundle.to_str().unwrap(), "wrong")
+            .expect_err("wrong passphrase must fail");
+        assert!(err.to_string().contains("Failed to decrypt"));
+    }
+}
@@ -0,0 +1,209 @@
+use std::{collections::VecDeque, fs, path::Path};
+
+use anyhow::{Context, Result};
+use time::{OffsetDateTime, format_description::well_known::Rfc3339};
+
+use super::admin::AdminInfo;
+
+/// Samples kept in memory: six hours of the 30 second stats poll.
+pub(crate) const METRICS_HISTORY_LIMIT: usize = 720;
+
+/// Suggested name for the exported CSV, also used in the data directory when
+/// no save dialog is available.
+pub(crate) const METRICS_CSV_FILE_NAME: &str = "homeserver-metrics.csv";
+
+/// Width and height of the sparkline `viewBox`.
+pub(crate) const SPARKLINE_WIDTH: f64 = 100.0;
+pub(crate) const SPARKLINE_HEIGHT: f64 = 24.0;
+
+const CSV_HEADER: &str = "timestamp,users,disabled_users,disk_used_mb,signup_codes,unused_signup_codes,active_connections";
+
+/// One successful `/info` poll.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct MetricsSample {
+    pub(crate) timestamp: String,
+    pub(crate) num_users: u64,
+    pub(crate) num_disabled_users: u64,
+    pub(crate) total_disk_used_mb: f64,
+    pub(crate) num_signup_codes: u64,
+    pub(crate) num_unused_signup_codes: u64,
+    pub(crate) active_connections: Option<u64>,
+}
+
+impl MetricsSample {
+    pub(crate) fn now(info: &AdminInfo) -> Self {
+        let timestamp = OffsetDateTime::now_utc()
+            .format(&Rfc3339)
+            .unwrap_or_else(|_| String::from("unknown"));
+        Self::at(timestamp, info)
+    }
+
+    fn at(timestamp: String, info: &AdminInfo) -> Self {
+        Self {
+            timestamp,
+            num_users: info.num_users,
+            num_disabled_users: info.num_disabled_users,
+            total_disk_used_mb: info.total_disk_used_mb,
+            num_signup_codes: info.num_signup_codes,
+            num_unused_signup_codes: info.num_unused_signup_codes,
+            active_connections: info.active_connections,
+        }
+    }
+
+    fn to_csv_row(&self) -> String {
+        format!(
+            "{},{},{},{:.2},{},{},{}",
+            self.timestamp,
+            self.num_users,
+            self.num_disabled_users,
+            self.total_disk_used_mb,
+            self.num_signup_codes,
+            self.num_unused_signup_codes,
+            self.active_connections
+                .map(|count| count.to_string())
+                .unwrap_or_default()
+        )
+    }
+}
+
+/// Stats polled while the current server runs, oldest first, capped at
+/// [`METRICS_HISTORY_LIMIT`].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub(crate) struct MetricsHistory {
+    samples: VecDeque<MetricsSample>,
+}
+
+impl MetricsHistory {
+    pub(crate) fn record(&mut self, sample: MetricsSample) {
+        if self.samples.len() == METRICS_HISTORY_LIMIT {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(sample);
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.samples.clear();
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.samples.len()
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.samples.is_empty()
+    }
+
+    pub(crate) fn to_csv(&self) -> String {
+        let mut csv = String::from(CSV_HEADER);
+        csv.push('\n');
+        for sample in &self.samples {
+            csv.push_str(&sample.to_csv_row());
+            csv.push('\n');
+        }
+        csv
+    }
+
+    /// SVG polyline points tracing `metric` over time, or `None` until there
+    /// are two samples to connect.
+    pub(crate) fn sparkline(&self, metric: impl Fn(&MetricsSample) -> f64) -> Option<String> {
+        if self.samples.len() < 2 {
+            return None;
+        }
+        let values: Vec<f64> = self.samples.iter().map(metric).collect();
+        let min = values.iter().copied().fold(f64::INFINITY, f64::min);
+        let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        let range = max - min;
+        let step = SPARKLINE_WIDTH / (values.len() - 1) as f64;
+
+        let points = values
+            .iter()
+            .enumerate()
+            .map(|(index, value)| {
+                let y = if range > 0.0 {
+                    SPARKLINE_HEIGHT - (value - min) / range * SPARKLINE_HEIGHT
+                } else {
+                    SPARKLINE_HEIGHT / 2.0
+                };
+                format!("{:.1},{:.1}", index as f64 * step, y)
+            })
+            .collect::<Vec<_>>()
+            .join(" ");
+        Some(points)
+    }
+}
+
+pub(crate) fn save_metrics_csv(path: &Path, history: &MetricsHistory) -> Result<()> {
+    if let Some(parent) = path.parent()
+        && !parent.as_os_str().is_empty()
+    {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
+    fs::write(path, history.to_csv()).with_context(|| format!("Failed to write {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn info(users: u64, disk_mb: f64) -> AdminInfo {
+        AdminInfo {
+            num_users: users,
+            num_disabled_users: 0,
+            total_disk_used_mb: disk_mb,
+            num_signup_codes: 3,
+            num_unused_signup_codes: 1,
+            active_connections: None,
+            peak_connections: None,
+        }
+    }
+
+    #[test]
+    fn history_is_bounded_and_exports_csv() {
+        let mut history = MetricsHistory::default();
+        for index in 0..(METRICS_HISTORY_LIMIT + 5) {
+            history.record(MetricsSample::at(
+                format!("t{index}"),
+                &info(index as u64, 1.5),
+            ));
+        }
+        assert_eq!(history.len(), METRICS_HISTORY_LIMIT);
+
+        let csv = history.to_csv();
+        let mut lines = csv.lines();
+        assert_eq!(lines.next(), Some(CSV_HEADER));
+        assert_eq!(lines.next(), Some("t5,5,0,1.50,3,1,"));
+        assert_eq!(csv.lines().count(), METRICS_HISTORY_LIMIT + 1);
+    }
+
+    #[test]
+    fn sparkline_spans_the_view_box() {
+        let mut history = MetricsHistory::default();
+        assert_eq!(history.sparkline(|sample| sample.num_users as f64), None);
+
+        for users in [1, 3, 2] {
+            history.record(MetricsSample::at(String::from("t"), &info(users, 0.0)));
+        }
+        assert_eq!(
+            history.sparkline(|sample| sample.num_users as f64),
+            Some(String::from("0.0,24.0 50.0,0.0 100.0,12.0"))
+        );
+        assert_eq!(
+            history.sparkline(|sample| sample.total_disk_used_mb),
+            Some(String::from("0.0,12.0 50.0,12.0 100.0,12.0"))
+        );
+    }
+
+    #[test]
+    fn csv_is_written_to_disk() {
+        let temp_dir = tempfile::tempdir().expect("temp dir");
+        let path = temp_dir.path().join("nested").join("metrics.csv");
+        let mut history = MetricsHistory::default();
+        history.record(MetricsSample::now(&info(2, 4.0)));
+
+        save_metrics_csv(&path, &history).expect("csv should save");
+        let written = fs::read_to_string(&path).expect("csv should exist");
+        assert!(written.starts_with(CSV_HEADER));
+        assert_eq!(written.lines().count(), 2);
+    }
+}
@@ -1,13 +1,21 @@
 mod admin;
+mod audit;
 mod bootstrap;
 mod config;
+mod disk;
+mod file_dialog;
+mod identity;
 pub(crate) mod logs;
+mod metrics;
 mod mobile;
+mod ports;
+mod reachability;
 mod state;
 mod status;
 mod style;
 mod tasks;
 mod ui;
+mod wizard;
 
 #[cfg(not(target_os = "android"))]
 pub use bootstrap::launch_desktop;
@@ -0,0 +1,316 @@
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use anyhow::{Context, Result, bail};
+
+/// A process found listening on one of the ports we need.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct PortHolder {
+    pub(crate) port: u16,
+    pub(crate) pid: u32,
+    /// Executable name, when the platform tool reports it.
+    pub(crate) command: Option<String>,
+}
+
+impl PortHolder {
+    /// Whether the holder looks like an earlier run of this app (same
+    /// executable, different PID), e.g. one left behind by a crash.
+    pub(crate) fn is_previous_instance(&self) -> bool {
+        if self.pid == std::process::id() {
+            return false;
+        }
+        let Ok(own_exe) = std::env::current_exe() else {
+            return false;
+        };
+        // Prefer the holder's full executable path; the reported command name
+        // may be truncated, so it is only compared when the path is unknown.
+        if let Some(exe) = process_executable(self.pid) {
+            return exe == own_exe;
+        }
+        self.command
+            .as_deref()
+            .is_some_and(|command| same_executable_name(command, &own_exe))
+    }
+
+    pub(crate) fn describe(&self) -> String {
+        let name = self.command.as_deref().unwrap_or("unknown process");
+        if self.is_previous_instance() {
+            format!(
+                "Port {} is held by PID {} ({name}), a previous run of this app.",
+                self.port, self.pid
+            )
+        } else {
+            format!("Port {} is held by PID {} ({name}).", self.port, self.pid)
+        }
+    }
+}
+
+/// Ports that were still bound when a testnet start was requested, plus any
+/// owning processes the platform tools could identify.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct PortConflict {
+    pub(crate) ports: Vec<u16>,
+    pub(crate) holders: Vec<PortHolder>,
+}
+
+impl PortConflict {
+    /// PIDs the app offers to terminate: only earlier runs of this app. Other
+    /// holders are reported but left for the user to stop.
+    pub(crate) fn terminable_pids(&self) -> Vec<u32> {
+        let mut pids: Vec<u32> = self
+            .holders
+            .iter()
+            .filter(|holder| holder.is_previous_instance())
+            .map(|holder| holder.pid)
+            .collect();
+        pids.sort_unstable();
+        pids.dedup();
+        pids
+    }
+}
+
+/// Look up which processes listen on `ports`. Best effort: returns an empty
+/// list when the lookup tool is missing or fails.
+#[cfg(all(unix, not(target_os = "android")))]
+pub(crate) fn find_port_holders(ports: &[u16]) -> Vec<PortHolder> {
+    let port_list = ports
+        .iter()
+        .map(|port| port.to_string())
+        .collect::<Vec<_>>()
+        .join(",");
+    Command::new("lsof")
+        .args(["-nP", "+c0", "-sTCP:LISTEN", "-Fpcn"])
+        .arg(format!("-iTCP:{port_list}"))
+        .output()
+        .map(|output| parse_lsof_output(&String::from_utf8_lossy(&output.stdout), ports))
+        .unwrap_or_default()
+}
+
+#[cfg(windows)]
+pub(crate) fn find_port_holders(ports: &[u16]) -> Vec<PortHolder> {
+    Command::new("netstat")
+        .args(["-ano", "-p", "TCP"])
+        .output()
+        .map(|output| parse_netstat_output(&String::from_utf8_lossy(&output.stdout), ports))
+        .unwrap_or_default()
+}
+
+#[cfg(not(any(all(unix, not(target_os = "android")), windows)))]
+pub(crate) fn find_port_holders(_ports: &[u16]) -> Vec<PortHolder> {
+    Vec::new()
+}
+
+/// Path of the executable `pid` is running, where the platform exposes it.
+#[cfg(target_os = "linux")]
+fn process_executable(pid: u32) -> Option<PathBuf> {
+    let path = std::fs::read_link(format!("/proc/{pid}/exe")).ok()?;
+    // A binary replaced since the process started is reported as "<path> (deleted)".
+    let original = path
+        .to_string_lossy()
+        .strip_suffix(" (deleted)")
+        .map(PathBuf::from);
+    Some(original.unwrap_or(path))
+}
+
+#[cfg(not(target_os = "linux"))]
+fn process_executable(_pid: u32) -> Option<PathBuf> {
+    None
+}
+
+/// Whether `command` is exactly the file name of `exe`, ignoring a `.exe`
+/// suffix.
+fn same_executable_name(command: &str, exe: &Path) -> bool {
+    let command = command.trim_end_matches(".exe");
+    !command.is_empty()
+        && exe
+            .file_stem()
+            .is_some_and(|stem| stem.to_string_lossy() == command)
+}
+
+/// Ask `pid` to exit.
+pub(crate) fn terminate_process(pid: u32) -> Result<()> {
+    if pid == std::process::id() {
+        bail!("Refusing to terminate this app's own process");
+    }
+
+    #[cfg(windows)]
+    let status = Command::new("taskkill")
+        .args(["/PID", &pid.to_string(), "/F"])
+        .status();
+    #[cfg(not(windows))]
+    let status = Command::new("kill")
+        .args(["-TERM", &pid.to_string()])
+        .status();
+
+    let status = status.with_context(|| format!("Failed to signal PID {pid}"))?;
+    if !status.success() {
+        bail!("Could not terminate PID {pid} ({status})");
+    }
+    Ok(())
+}
+
+/// Parse `lsof -F pcn` output: `p<pid>` starts a process, `c<command>` names
+/// it, and each `n<addr>:<port>` is one listening socket.
+#[cfg_attr(any(windows, target_os = "android"), allow(dead_code))]
+fn parse_lsof_output(output: &str, ports: &[u16]) -> Vec<PortHolder> {
+    let mut holders: Vec<PortHolder> = Vec::new();
+    let mut pid = None;
+    let mut command = None;
+
+    for line in output.lines() {
+        let mut chars = line.chars();
+        let tag = chars.next();
+        let value = chars.as_str();
+        match tag {
+            Some('p') => {
+                pid = value.parse().ok();
+                command = None;
+            }
+            Some('c') => command = Some(value.to_string()),
+            Some('n') => {
+                let Some(pid) = pid else { continue };
+                let Some(port) = value
+                    .rsplit_once(':')
+                    .and_then(|(_, port)| port.parse::<u16>().ok())
+                else {
+                    continue;
+                };
+                if ports.contains(&port)
+                    && !holders
+                        .iter()
+                        .any(|holder| holder.pid == pid && holder.port == port)
+                {
+                    holders.push(PortHolder {
+                        port,
+                        pid,
+                        command: command.clone(),
+                    });
+                }
+            }
+            _ => {}
+        }
+    }
+
+    holders
+}
+
+/// Parse `netstat -ano` rows such as
+/// `TCP    0.0.0.0:6286    0.0.0.0:0    LISTENING    1234`.
+#[cfg_attr(not(windows), allow(dead_code))]
+fn parse_netstat_output(output: &str, ports: &[u16]) -> Vec<PortHolder> {
+    let mut holders: Vec<PortHolder> = Vec::new();
+
+    for line in output.lines() {
+        let columns: Vec<&str> = line.split_whitespace().collect();
+        let [proto, local, _remote, state, pid] = columns.as_slice() else {
+            continue;
+        };
+        if !proto.eq_ignore_ascii_case("tcp") || !state.eq_ignore_ascii_case("listening") {
+            continue;
+        }
+        let Some(port) = local
+            .rsplit_once(':')
+            .and_then(|(_, port)| port.parse::<u16>().ok())
+        else {
+            continue;
+        };
+        let Ok(pid) = pid.parse::<u32>() else {
+            continue;
+        };
+        if ports.contains(&port)
+            && !holders
+                .iter()
+                .any(|holder| holder.pid == pid && holder.port == port)
+        {
+            holders.push(PortHolder {
+                port,
+                pid,
+                command: None,
+            });
+        }
+    }
+
+    holders
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_lsof_listeners() {
+        let output = "p4242\ncportable-homeserver\nf12\nn*:6286\nf13\nn[::]:6286\nf14\nn127.0.0.1:6287\np77\ncnginx\nf3\nn*:8080\n";
+        let holders = parse_lsof_output(output, &[6286, 6287, 15411]);
+
+        assert_eq!(
+            holders,
+            vec![
+                PortHolder {
+                    port: 6286,
+                    pid: 4242,
+                    command: Some("portable-homeserver".into()),
+                },
+                PortHolder {
+                    port: 6287,
+                    pid: 4242,
+                    command: Some("portable-homeserver".into()),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn parses_netstat_listeners() {
+        let output = "\nActive Connections\n\n  Proto  Local Address          Foreign Address        State           PID\n  TCP    0.0.0.0:6286           0.0.0.0:0              LISTENING       1234\n  TCP    [::]:15411             [::]:0                 LISTENING       1234\n  TCP    127.0.0.1:6286         127.0.0.1:50000        ESTABLISHED     999\n  TCP    0.0.0.0:445            0.0.0.0:0              LISTENING       4\n";
+        let holders = parse_netstat_output(output, &[6286, 15411]);
+
+        assert_eq!(
+            holders
+                .iter()
+                .map(|holder| (holder.port, holder.pid))
+                .collect::<Vec<_>>(),
+            vec![(6286, 1234), (15411, 1234)]
+        );
+    }
+
+    #[test]
+    fn executable_names_must_match_exactly() {
+        let exe = Path::new("/opt/bin/portable-homeserver");
+        assert!(same_executable_name("portable-homeserver", exe));
+        assert!(same_executable_name("portable-homeserver.exe", exe));
+        assert!(!same_executable_name("portable", exe));
+        assert!(!same_executable_name("p", exe));
+        assert!(!same_executable_name("", exe));
+    }
+
+    #[test]
+    fn unrelated_holders_cannot_be_terminated() {
+        let conflict = PortConflict {
+            ports: vec![6286, 15411],
+            holders: vec![
+                PortHolder {
+                    port: 6286,
+                    pid: u32::MAX,
+                    command: Some("postgres".into()),
+                },
+                PortHolder {
+                    port: 15411,
+                    pid: u32::MAX - 1,
+                    command: None,
+                },
+            ],
+        };
+        assert!(conflict.terminable_pids().is_empty());
+    }
+
+    #[test]
+    fn own_process_is_never_a_previous_instance() {
+        let holder = PortHolder {
+            port: 6286,
+            pid: std::process::id(),
+            command: Some("portable-homeserver".into()),
+        };
+        assert!(!holder.is_previous_instance());
+        assert!(terminate_process(std::process::id()).is_err());
+    }
+}
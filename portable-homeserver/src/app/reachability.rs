@@ -0,0 +1,94 @@
+use std::net::IpAddr;
+use std::time::Duration;
+
+use anyhow::{Context, Result, anyhow};
+
+/// Service that echoes the caller's public address as plain text. Only
+/// contacted when the operator presses the detect button.
+pub(crate) const PUBLIC_IP_SERVICE: &str = "https://api.ipify.org";
+
+const DETECT_TIMEOUT: Duration = Duration::from_secs(10);
+
+pub(crate) async fn detect_public_ip() -> Result<IpAddr> {
+    let client = reqwest::Client::builder()
+        .timeout(DETECT_TIMEOUT)
+        .build()
+        .context("Failed to build HTTP client")?;
+    let body = client
+        .get(PUBLIC_IP_SERVICE)
+        .send()
+        .await
+        .with_context(|| format!("Failed to reach {PUBLIC_IP_SERVICE}"))?
+        .error_for_status()
+        .with_context(|| format!("{PUBLIC_IP_SERVICE} rejected the request"))?
+        .text()
+        .await
+        .context("Failed to read the public IP response")?;
+
+    parse_ip_response(&body)
+}
+
+fn parse_ip_response(body: &str) -> Result<IpAddr> {
+    let trimmed = body.trim();
+    trimmed
+        .parse()
+        .map_err(|_| anyhow!("Unexpected public IP response: {trimmed:?}"))
+}
+
+/// Explain who can reach the node when it advertises `public_ip`, or `None`
+/// when the address looks publicly routable.
+pub(crate) fn reachability_note(public_ip: &str) -> Option<&'static str> {
+    let Ok(ip) = public_ip.trim().parse::<IpAddr>() else {
+        return None;
+    };
+    if ip.is_loopback() {
+        return Some(
+            "127.0.0.1 means local-only: other machines cannot reach this homeserver. Use your public IP to let others connect.",
+        );
+    }
+    if ip.is_unspecified() {
+        return Some(
+            "An unspecified address is not reachable. Advertise the IP other machines should connect to.",
+        );
+    }
+    let private = match ip {
+        IpAddr::V4(v4) => v4.is_private() || v4.is_link_local(),
+        IpAddr::V6(v6) => v6.is_unicast_link_local() || (v6.segments()[0] & 0xfe00) == 0xfc00,
+    };
+    private.then_some(
+        "This is a private network address: only devices on the same network can reach the homeserver.",
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_plain_text_addresses() {
+        assert_eq!(
+            parse_ip_response("203.0.113.7\n").unwrap(),
+            "203.0.113.7".parse::<IpAddr>().unwrap()
+        );
+        assert!(parse_ip_response("2001:db8::1").is_ok());
+        assert!(parse_ip_response("<html>rate limited</html>").is_err());
+    }
+
+    #[test]
+    fn notes_local_and_private_addresses() {
+        assert!(
+            reachability_note("127.0.0.1")
+                .unwrap()
+                .contains("local-only")
+        );
+        assert!(reachability_note("0.0.0.0").is_some());
+        assert!(
+            reachability_note("192.168.1.20")
+                .unwrap()
+                .contains("private")
+        );
+        assert!(reachability_note("fd00::1").is_some());
+        assert!(reachability_note("203.0.113.7").is_none());
+        assert!(reachability_note("not an ip").is_none());
+    }
+}
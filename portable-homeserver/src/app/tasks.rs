@@ -14,10 +14,48 @@ use pubky_testnet::StaticTestnet;
 use tokio::time::{Duration, sleep};
 use tracing::{error, warn};
 
+use super::ports::{PortConflict, find_port_holders};
 use super::state::{NetworkProfile, RunningServer, ServerInfo, ServerStatus, StartSpec};
 
 const STATIC_TESTNET_MAX_ADDR_IN_USE_RETRIES: usize = 5;
 
+/// Components bundled by [`StaticTestnet`], in dependency order: the
+/// homeserver publishes through the relays, which talk to the DHT.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TestnetComponent {
+    Homeserver,
+    HttpRelay,
+    PkarrRelay,
+    Dht,
+}
+
+impl TestnetComponent {
+    const TEARDOWN_ORDER: [TestnetComponent; 4] = [
+        TestnetComponent::Homeserver,
+        TestnetComponent::HttpRelay,
+        TestnetComponent::PkarrRelay,
+        TestnetComponent::Dht,
+    ];
+
+    fn label(self) -> &'static str {
+        match self {
+            TestnetComponent::Homeserver => "homeserver",
+            TestnetComponent::HttpRelay => "HTTP relay",
+            TestnetComponent::PkarrRelay => "pkarr relay",
+            TestnetComponent::Dht => "DHT bootstrap node",
+        }
+    }
+
+    fn ports(self) -> &'static [u16] {
+        match self {
+            TestnetComponent::Homeserver => &[6286, 6287, 6288],
+            TestnetComponent::HttpRelay => &[15412],
+            TestnetComponent::PkarrRelay => &[15411],
+            TestnetComponent::Dht => &[6881],
+        }
+    }
+}
+
 const STATIC_TESTNET_PORTS: [u16; 6] = [15411, 15412, 6286, 6287, 6288, 6881];
 
 #[cfg(test)]
@@ -84,54 +122,101 @@ fn is_addr_in_use_error(err: &anyhow::Error) -> bool {
 }
 
 async fn wait_for_static_testnet_ports_to_release() -> Result<()> {
-    wait_for_ports_to_release(
+    let blocked = wait_for_ports_to_release(
         &STATIC_TESTNET_PORTS,
         Duration::from_millis(STATIC_TESTNET_PORT_RELEASE_TIMEOUT_MS),
         Duration::from_millis(STATIC_TESTNET_PORT_POLL_INTERVAL_MS),
     )
-    .await
+    .await?;
+
+    if blocked.is_empty() {
+        return Ok(());
+    }
+
+    Err(anyhow!(
+        "Static testnet components failed to release their ports: {}",
+        describe_blocked_components(&blocked)
+    ))
+}
+
+/// Map still-bound ports back to the testnet component that owns them.
+fn blocked_components(blocked_ports: &[u16]) -> Vec<TestnetComponent> {
+    TestnetComponent::TEARDOWN_ORDER
+        .into_iter()
+        .filter(|component| {
+            component
+                .ports()
+                .iter()
+                .any(|port| blocked_ports.contains(port))
+        })
+        .collect()
+}
+
+fn describe_blocked_components(blocked_ports: &[u16]) -> String {
+    blocked_components(blocked_ports)
+        .into_iter()
+        .map(|component| {
+            let ports = component
+                .ports()
+                .iter()
+                .filter(|port| blocked_ports.contains(port))
+                .map(|port| port.to_string())
+                .collect::<Vec<_>>()
+                .join(", ");
+            format!("{} (port {ports})", component.label())
+        })
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+fn probe_bound_ports(ports: &[u16]) -> Result<Vec<u16>> {
+    let mut bound = Vec::new();
+
+    for &port in ports {
+        match TcpListener::bind(SocketAddr::from((Ipv4Addr::UNSPECIFIED, port))) {
+            Ok(listener) => drop(listener),
+            Err(err) if err.kind() == io::ErrorKind::AddrInUse => bound.push(port),
+            Err(err) => {
+                return Err(err)
+                    .with_context(|| format!("Failed to probe port {port} availability"));
+            }
+        }
+    }
+
+    Ok(bound)
+}
+
+/// Pre-start check for the static testnet: report which of its fixed ports are
+/// already bound, and by whom, so a leftover process can be dealt with before
+/// the retries in [`retry_addr_in_use`] run out.
+pub(crate) fn testnet_port_conflict() -> Result<Option<PortConflict>> {
+    let ports = probe_bound_ports(&STATIC_TESTNET_PORTS)?;
+    if ports.is_empty() {
+        return Ok(None);
+    }
+    let holders = find_port_holders(&ports);
+    Ok(Some(PortConflict { ports, holders }))
 }
 
+/// Human-readable list of the testnet components behind `ports`.
+pub(crate) fn describe_testnet_ports(ports: &[u16]) -> String {
+    describe_blocked_components(ports)
+}
+
+/// Poll until every port is free or the timeout elapses. Returns the ports that
+/// were still bound when giving up (empty when everything was released).
 async fn wait_for_ports_to_release(
     ports: &[u16],
     timeout: Duration,
     poll_interval: Duration,
-) -> Result<()> {
+) -> Result<Vec<u16>> {
     let deadline = Instant::now() + timeout;
-    let mut last_blocked_port = None;
 
     loop {
-        let mut all_ports_free = true;
-
-        for &port in ports {
-            match TcpListener::bind(SocketAddr::from((Ipv4Addr::UNSPECIFIED, port))) {
-                Ok(listener) => drop(listener),
-                Err(err) => {
-                    if err.kind() == io::ErrorKind::AddrInUse {
-                        last_blocked_port = Some(port);
-                        all_ports_free = false;
-                        break;
-                    }
-
-                    return Err(err)
-                        .with_context(|| format!("Failed to probe port {port} availability"));
-                }
-            }
-        }
-
-        if all_ports_free {
-            return Ok(());
-        }
+        let bound = probe_bound_ports(ports)?;
 
-        if Instant::now() >= deadline {
-            let port = last_blocked_port
-                .map(|value| value.to_string())
-                .unwrap_or_else(|| "unknown".to_string());
-            return Err(anyhow!(
-                "Ports {:?} remained bound after shutdown; last blocked port: {}",
-                ports,
-                port
-            ));
+        if bound.is_empty() || Instant::now() >= deadline {
+            return Ok(bound);
         }
 
         sleep(poll_interval).await;
@@ -255,6 +340,11 @@ async fn shutdown_running_server(server: RunningServer) -> Result<()> {
             drop(handle);
         }
         RunningServer::Testnet(testnet) => {
+            // `StaticTestnet` only exposes shutdown hooks for the homeserver
+            // and the pkarr relay. The HTTP relay and the DHT bootstrap node
+            // cannot be stopped on their own; they release their sockets when
+            // the testnet is dropped below, and the port wait that follows
+            // names whichever of them is still bound.
             testnet.homeserver().core().shutdown();
             testnet.pkarr_relay().shutdown();
 
@@ -473,6 +563,18 @@ mod tests {
             .await
             .expect("static testnet shutdown should succeed");
 
+        let still_bound = probe_bound_ports(&STATIC_TESTNET_PORTS).expect("ports should be probed");
+        for component in TestnetComponent::TEARDOWN_ORDER {
+            assert!(
+                component
+                    .ports()
+                    .iter()
+                    .all(|port| !still_bound.contains(port)),
+                "{} should release its ports after shutdown",
+                component.label()
+            );
+        }
+
         let restarted = StaticTestnet::start()
             .await
             .expect("static testnet should restart cleanly");
@@ -480,5 +582,38 @@ mod tests {
         shutdown_running_server(RunningServer::Testnet(Arc::new(restarted)))
             .await
             .expect("shutdown after restart should succeed");
+
+        assert!(
+            probe_bound_ports(&STATIC_TESTNET_PORTS)
+                .expect("ports should be probed")
+                .is_empty(),
+            "every static testnet component should be torn down after restart"
+        );
+    }
+
+    #[test]
+    fn testnet_components_cover_every_static_port() {
+        let mut component_ports: Vec<u16> = TestnetComponent::TEARDOWN_ORDER
+            .iter()
+            .flat_map(|component| component.ports().iter().copied())
+            .collect();
+        let mut static_ports = STATIC_TESTNET_PORTS.to_vec();
+        component_ports.sort_unstable();
+        static_ports.sort_unstable();
+
+        assert_eq!(component_ports, static_ports);
+    }
+
+    #[test]
+    fn describes_components_that_failed_to_release() {
+        assert_eq!(
+            blocked_components(&[15412, 6881]),
+            vec![TestnetComponent::HttpRelay, TestnetComponent::Dht]
+        );
+        assert_eq!(
+            describe_blocked_components(&[6287, 15411]),
+            "homeserver (port 6287); pkarr relay (port 15411)"
+        );
+        assert!(blocked_components(&[]).is_empty());
     }
 }
@@ -1,3 +1,4 @@
+use std::path::{Path, PathBuf};
 use std::time::Instant;
 
 use dioxus::events::{FormEvent, MouseEvent};
@@ -7,17 +8,40 @@ use pubky_homeserver::SignupMode;
 use tokio::time::{Duration, sleep};
 use tracing::Level;
 
-use super::admin::{self, AdminInfo};
+use super::admin::{self, AdminInfo, InfoResponse, SUPPORTED_HOMESERVER_SERIES};
+use super::audit::{
+    AUDIT_PANEL_LIMIT, AuditAction, AuditEntry, AuditOutcome, append_audit_entry, audit_log_path,
+    read_recent_audit_entries,
+};
 use super::config::{
-    ConfigFeedback, ConfigForm, ConfigState, config_state_from_dir, default_data_dir,
-    load_config_form_from_dir, modify_config_form, persist_config_form,
+    BindScope, ConfigFeedback, ConfigForm, ConfigState, LoggingPreset, apply_bind_scope,
+    config_exists, config_state_from_dir, default_data_dir, load_config_form_from_dir,
+    modify_config_form, persist_admin_password, persist_config_form, persist_signup_mode,
+    validate_logging_level, validate_new_admin_password,
+};
+use super::disk::{
+    DiskAlertSettings, LowDiskWarning, free_space_mb, load_disk_alert_settings, low_disk_warning,
+    parse_threshold_mb, save_disk_alert_settings,
+};
+use super::file_dialog::{self, FileDialogResult};
+use super::identity::{
+    export_node_recovery_file, import_node_recovery_file, read_node_keypair, secret_file_path,
 };
 use super::logs;
+use super::metrics::{
+    METRICS_CSV_FILE_NAME, METRICS_HISTORY_LIMIT, MetricsHistory, MetricsSample, SPARKLINE_HEIGHT,
+    SPARKLINE_WIDTH, save_metrics_csv,
+};
 use super::mobile::{MobileEnhancementsScript, is_android_touch, touch_copy};
-use super::state::{NetworkProfile, RunningServer, ServerStatus, resolve_start_spec};
+use super::ports::{PortConflict, terminate_process};
+use super::reachability::{PUBLIC_IP_SERVICE, detect_public_ip, reachability_note};
+use super::state::{NetworkProfile, RunningServer, ServerStatus, StartSpec, resolve_start_spec};
 use super::status::{StatusCopy, StatusDetails, status_copy, status_details};
 use super::style::STYLE;
-use super::tasks::{spawn_start_task, stop_current_server};
+use super::tasks::{
+    describe_testnet_ports, spawn_start_task, stop_current_server, testnet_port_conflict,
+};
+use super::wizard::{MIN_ADMIN_PASSWORD_LEN, WizardState, WizardStep};
 
 #[derive(Clone, Debug)]
 enum FetchState<T> {
@@ -73,6 +97,15 @@ struct DisableUserFormState {
     in_flight: bool,
 }
 
+#[derive(Clone, Debug, Default)]
+struct PasswordChangeFormState {
+    new_password: String,
+    confirmation: String,
+    /// Set after the first click; the second click saves and restarts.
+    armed: bool,
+    feedback: Option<ActionFeedback>,
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 enum AppTab {
     Overview,
@@ -131,11 +164,28 @@ struct AdminPanelState {
     password_initialized: bool,
     info: FetchState<AdminInfo>,
     info_refresh_nonce: u64,
+    /// Highest connection count seen while the current server has been running.
+    connection_peak: Option<u64>,
+    /// Set when `/info` suggests a homeserver whose admin API this UI does
+    /// not speak; admin actions stay disabled until it clears.
+    api_mismatch: Option<String>,
     signup_token: Option<String>,
+    /// Server key plus signup token, ready to paste into the Swiss Knife.
+    invite: Option<String>,
     signup_feedback: Option<ActionFeedback>,
     signup_in_flight: bool,
     delete_form: DeleteEntryFormState,
     disable_form: DisableUserFormState,
+    password_form: PasswordChangeFormState,
+    audit_entries: Vec<AuditEntry>,
+    audit_error: Option<String>,
+    audit_loaded_for: Option<String>,
+    /// Unsaved edit of the low-disk threshold field.
+    disk_threshold_draft: Option<String>,
+    disk_threshold_feedback: Option<ActionFeedback>,
+    /// Stats from every successful poll since the server started.
+    metrics: MetricsHistory,
+    metrics_feedback: Option<ActionFeedback>,
 }
 
 impl Default for AdminPanelState {
@@ -145,11 +195,22 @@ impl Default for AdminPanelState {
             password_initialized: false,
             info: FetchState::Idle,
             info_refresh_nonce: 1,
+            connection_peak: None,
+            api_mismatch: None,
             signup_token: None,
+            invite: None,
             signup_feedback: None,
             signup_in_flight: false,
             delete_form: DeleteEntryFormState::default(),
             disable_form: DisableUserFormState::default(),
+            password_form: PasswordChangeFormState::default(),
+            audit_entries: Vec::new(),
+            audit_error: None,
+            audit_loaded_for: None,
+            disk_threshold_draft: None,
+            disk_threshold_feedback: None,
+            metrics: MetricsHistory::default(),
+            metrics_feedback: None,
         }
     }
 }
@@ -165,6 +226,42 @@ impl AdminPanelState {
     fn bump_info_refresh(&mut self) {
         self.info_refresh_nonce = self.info_refresh_nonce.wrapping_add(1);
     }
+
+    /// Adopt a newly saved admin password and refetch stats with it.
+    fn resync_password(&mut self, password: String) {
+        self.password_initialized = false;
+        self.ensure_password(password);
+        self.bump_info_refresh();
+    }
+
+    fn reload_audit(&mut self, data_dir: &str) {
+        self.audit_loaded_for = Some(data_dir.to_string());
+        match read_recent_audit_entries(data_dir, AUDIT_PANEL_LIMIT) {
+            Ok(entries) => {
+                self.audit_entries = entries;
+                self.audit_error = None;
+            }
+            Err(err) => self.audit_error = Some(format!("Failed to load the audit log: {err:#}")),
+        }
+    }
+
+    fn record_audit(&mut self, data_dir: &str, entry: AuditEntry) {
+        match append_audit_entry(data_dir, &entry) {
+            Ok(()) => self.reload_audit(data_dir),
+            Err(err) => {
+                self.audit_entries.insert(0, entry);
+                self.audit_entries.truncate(AUDIT_PANEL_LIMIT);
+                self.audit_error = Some(format!("Failed to record admin action: {err:#}"));
+            }
+        }
+    }
+}
+
+fn audit_outcome<T>(result: &anyhow::Result<T>) -> AuditOutcome {
+    match result {
+        Ok(_) => AuditOutcome::Success,
+        Err(err) => AuditOutcome::Failure(err.to_string()),
+    }
 }
 
 async fn poll_admin_info(
@@ -192,6 +289,11 @@ async fn poll_admin_info(
                 if last_admin_url.as_deref() != Some(admin_url.as_str()) {
                     should_fetch = true;
                     last_admin_url = Some(admin_url.clone());
+                    let mut state = admin_state.write();
+                    state.connection_peak = None;
+                    state.api_mismatch = None;
+                    state.metrics.clear();
+                    state.metrics_feedback = None;
                 }
 
                 if nonce != last_nonce {
@@ -220,10 +322,20 @@ async fn poll_admin_info(
 
                         let result = admin::fetch_info(&admin_url, &password).await;
                         match result {
-                            Ok(info) => {
+                            Ok(InfoResponse::Info(info)) => {
                                 let mut state = admin_state.write();
+                                state.connection_peak = info.connection_peak(state.connection_peak);
+                                state.api_mismatch = None;
+                                state.metrics.record(MetricsSample::now(&info));
                                 state.info = FetchState::Loaded(info);
                             }
+                            Ok(InfoResponse::Incompatible(reason)) => {
+                                let mut state = admin_state.write();
+                                state.info = FetchState::Error(format!(
+                                    "Server stats are unavailable: {reason}."
+                                ));
+                                state.api_mismatch = Some(reason);
+                            }
                             Err(err) => {
                                 let mut state = admin_state.write();
                                 state.info = FetchState::Error(format!(
@@ -241,6 +353,8 @@ async fn poll_admin_info(
                 if last_admin_url.take().is_some() {
                     let mut state = admin_state.write();
                     state.info = FetchState::Idle;
+                    state.connection_peak = None;
+                    state.api_mismatch = None;
                 }
                 last_fetch = Instant::now()
                     .checked_sub(Duration::from_secs(60))
@@ -253,6 +367,83 @@ async fn poll_admin_info(
     }
 }
 
+/// How often free space is re-read while the homeserver runs.
+const DISK_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+async fn monitor_disk_space(
+    status: Signal<ServerStatus, SyncStorage>,
+    data_dir: Signal<String, SyncStorage>,
+    config_state: Signal<ConfigState, SyncStorage>,
+    mut threshold: Signal<u64, SyncStorage>,
+    mut warning: Signal<Option<LowDiskWarning>, SyncStorage>,
+) {
+    let mut threshold_loaded_for = data_dir.peek().clone();
+    let mut last_check: Option<(Instant, u64)> = None;
+
+    loop {
+        let current_dir = data_dir.read().clone();
+        if current_dir != threshold_loaded_for {
+            threshold.set(load_disk_alert_settings(&current_dir).threshold_mb);
+            threshold_loaded_for = current_dir.clone();
+        }
+
+        let status_snapshot = status.read().clone();
+        match status_snapshot {
+            ServerStatus::Running(info) => {
+                let threshold_mb = *threshold.read();
+                let due = match last_check {
+                    Some((checked_at, checked_threshold)) => {
+                        checked_at.elapsed() >= DISK_CHECK_INTERVAL
+                            || checked_threshold != threshold_mb
+                    }
+                    None => true,
+                };
+
+                if due {
+                    last_check = Some((Instant::now(), threshold_mb));
+                    // The testnet keeps its state in a temporary directory.
+                    let volume = match info.network {
+                        NetworkProfile::Mainnet => PathBuf::from(current_dir.trim()),
+                        NetworkProfile::Testnet => std::env::temp_dir(),
+                    };
+                    match free_space_mb(&volume) {
+                        Ok(free_mb) => {
+                            let mut next = low_disk_warning(free_mb, threshold_mb);
+                            if let Some(next_warning) = next.as_mut() {
+                                let password = config_state.read().form.admin_password.clone();
+                                if !password.trim().is_empty()
+                                    && let Ok(InfoResponse::Info(admin_info)) =
+                                        admin::fetch_info(&info.admin_url, &password).await
+                                {
+                                    next_warning.homeserver_used_mb =
+                                        Some(admin_info.total_disk_used_mb);
+                                }
+                            }
+                            match (warning.peek().is_some(), next.is_some()) {
+                                (false, true) => {
+                                    tracing::warn!(free_mb, threshold_mb, "low disk space")
+                                }
+                                (true, false) => tracing::info!(free_mb, "disk space recovered"),
+                                _ => {}
+                            }
+                            warning.set(next);
+                        }
+                        Err(err) => tracing::warn!(?err, "disk space check failed"),
+                    }
+                }
+            }
+            _ => {
+                last_check = None;
+                if warning.peek().is_some() {
+                    warning.set(None);
+                }
+            }
+        }
+
+        sleep(Duration::from_secs(1)).await;
+    }
+}
+
 fn sanitize_entry_target(pubkey: &str, entry_path: &str) -> Result<String, String> {
     let trimmed_pubkey = pubkey.trim();
     if trimmed_pubkey.is_empty() {
@@ -272,9 +463,94 @@ fn sanitize_entry_target(pubkey: &str, entry_path: &str) -> Result<String, Strin
     Ok(format!("{}/{}", trimmed_pubkey, sanitized))
 }
 
+/// Ask the admin API for a signup token. With `as_invite`, the token is also
+/// bundled with the server's public key into a one-line invite.
+fn request_signup_token(
+    status: Signal<ServerStatus, SyncStorage>,
+    mut admin_state: Signal<AdminPanelState, SyncStorage>,
+    data_dir: String,
+    as_invite: bool,
+) {
+    let status_snapshot = status.read().clone();
+    let ServerStatus::Running(info) = status_snapshot else {
+        let mut state = admin_state.write();
+        state.signup_feedback = Some(ActionFeedback::Error(if as_invite {
+            "Start the homeserver to create invites.".into()
+        } else {
+            "Start the homeserver to create signup tokens.".into()
+        }));
+        return;
+    };
+
+    let admin_url = info.admin_url.clone();
+    let password = {
+        let state = admin_state.read();
+        state.password.clone()
+    };
+
+    if password.trim().is_empty() {
+        let mut state = admin_state.write();
+        state.signup_feedback = Some(ActionFeedback::Error(
+            "Provide the admin password to generate a signup token.".into(),
+        ));
+        return;
+    }
+
+    {
+        let mut state = admin_state.write();
+        state.signup_in_flight = true;
+        state.signup_feedback = Some(ActionFeedback::Info(if as_invite {
+            "Creating an invite…".into()
+        } else {
+            "Requesting a new signup token…".into()
+        }));
+        state.signup_token = None;
+        state.invite = None;
+    }
+
+    let network = info.network;
+    let public_key = info.public_key.clone();
+    let mut admin_state_task = admin_state;
+    spawn(async move {
+        let result = admin::generate_signup_token(&admin_url, &password).await;
+        let mut state = admin_state_task.write();
+        let entry = AuditEntry::now(
+            network,
+            AuditAction::GenerateSignupToken,
+            "",
+            audit_outcome(&result),
+        );
+        state.record_audit(&data_dir, entry);
+        state.signup_in_flight = false;
+        match result {
+            Ok(token) => {
+                let token = token.trim().to_string();
+                if as_invite {
+                    state.invite = Some(admin::format_invite(&public_key, &token));
+                    state.signup_feedback = Some(ActionFeedback::Success(
+                        "Created an invite. Share it with the new user.".into(),
+                    ));
+                } else {
+                    state.signup_feedback =
+                        Some(ActionFeedback::Success("Generated a signup token.".into()));
+                }
+                state.signup_token = Some(token);
+                state.bump_info_refresh();
+            }
+            Err(err) => {
+                state.signup_feedback = Some(ActionFeedback::Error(format!(
+                    "Failed to generate token: {}",
+                    err
+                )));
+            }
+        }
+    });
+}
+
 fn toggle_user_access(
     status: Signal<ServerStatus, SyncStorage>,
     mut admin_state: Signal<AdminPanelState, SyncStorage>,
+    data_dir: String,
     disable: bool,
 ) {
     let status_snapshot = status.read().clone();
@@ -311,11 +587,19 @@ fn toggle_user_access(
             state.disable_form.feedback = Some(ActionFeedback::Info(action_copy.into()));
         }
 
+        let network = info.network;
         let mut admin_state_task = admin_state;
         spawn(async move {
             let result = admin::toggle_user_disabled(&admin_url, &password, &pubkey, disable).await;
             let mut state = admin_state_task.write();
             state.disable_form.in_flight = false;
+            let action = if disable {
+                AuditAction::DisableUser
+            } else {
+                AuditAction::EnableUser
+            };
+            let entry = AuditEntry::now(network, action, pubkey.trim(), audit_outcome(&result));
+            state.record_audit(&data_dir, entry);
             match result {
                 Ok(()) => {
                     let success_copy = if disable {
@@ -354,6 +638,41 @@ pub fn App() -> Element {
     let config_state = use_signal_sync(|| initial_config_state.clone());
 
     let active_tab = use_signal_sync(|| AppTab::Overview);
+    let show_wizard = use_signal_sync(|| !config_exists(&initial_data_dir));
+    let disk_threshold =
+        use_signal_sync(|| load_disk_alert_settings(&initial_data_dir).threshold_mb);
+    let disk_warning = use_signal_sync(|| Option::<LowDiskWarning>::None);
+
+    let mut disk_monitor_started = use_signal_sync(|| false);
+    if !*disk_monitor_started.peek() {
+        disk_monitor_started.set(true);
+        spawn(monitor_disk_space(
+            status,
+            data_dir,
+            config_state,
+            disk_threshold,
+            disk_warning,
+        ));
+    }
+
+    if *show_wizard.read() {
+        return rsx! {
+            MobileEnhancementsScript {}
+            style { "{STYLE}" }
+            main { class: "app",
+                div { class: "app-shell",
+                    SetupWizard {
+                        data_dir,
+                        network,
+                        config_state,
+                        status,
+                        running_server,
+                        show_wizard,
+                    }
+                }
+            }
+        };
+    }
 
     let selected_tab = *active_tab.read();
 
@@ -361,13 +680,16 @@ pub fn App() -> Element {
     let mut network_for_toggle = network;
     let network_for_overview = network;
     let network_for_config = network;
+    let network_for_admin = network;
     let data_dir_for_overview = data_dir;
     let data_dir_for_config = data_dir;
+    let data_dir_for_admin = data_dir;
     let status_for_overview = status;
     let status_for_config = status;
     let status_for_admin = status;
     let running_for_overview = running_server;
     let running_for_config = running_server;
+    let running_for_admin = running_server;
     let config_for_config = config_state;
     let config_for_admin = config_state;
 
@@ -391,6 +713,7 @@ pub fn App() -> Element {
                                 data_dir: data_dir_for_overview,
                                 status: status_for_overview,
                                 running_server: running_for_overview,
+                                disk_warning,
                             }
                         },
                         AppTab::Configuration => rsx! {
@@ -404,8 +727,13 @@ pub fn App() -> Element {
                         },
                         AppTab::Admin => rsx! {
                             AdminTab {
+                                network: network_for_admin,
                                 status: status_for_admin,
+                                running_server: running_for_admin,
                                 config_state: config_for_admin,
+                                data_dir: data_dir_for_admin,
+                                disk_threshold,
+                                disk_warning,
                             }
                         },
                         AppTab::Logs => rsx! {
@@ -482,8 +810,10 @@ fn OverviewTab(
     data_dir: Signal<String, SyncStorage>,
     status: Signal<ServerStatus, SyncStorage>,
     running_server: Signal<Option<RunningServer>, SyncStorage>,
+    disk_warning: Signal<Option<LowDiskWarning>, SyncStorage>,
 ) -> Element {
     let status_snapshot = status.read().clone();
+    let disk_warning_snapshot = disk_warning.read().clone();
     let start_disabled = matches!(
         status_snapshot,
         ServerStatus::Starting | ServerStatus::Running(_) | ServerStatus::Stopping
@@ -496,6 +826,10 @@ fn OverviewTab(
     let selected_network = *network.read();
     let current_dir = { data_dir.read().clone() };
 
+    let mut port_conflict = use_signal_sync(|| Option::<PortConflict>::None);
+    let mut port_feedback = use_signal_sync(|| Option::<ActionFeedback>::None);
+    let mut armed_pid = use_signal_sync(|| Option::<u32>::None);
+
     let network_for_start = network;
     let data_dir_for_start = data_dir;
     let mut status_for_start = status;
@@ -503,6 +837,25 @@ fn OverviewTab(
     let status_for_stop = status;
     let running_for_stop = running_server;
 
+    let conflict_view = port_conflict.read().clone().map(|conflict| {
+        let components = describe_testnet_ports(&conflict.ports);
+        let descriptions: Vec<String> = conflict.holders.iter().map(|h| h.describe()).collect();
+        let armed = *armed_pid.read();
+        let pid_buttons: Vec<(u32, String, &'static str)> = conflict
+            .terminable_pids()
+            .into_iter()
+            .map(|pid| {
+                if armed == Some(pid) {
+                    (pid, format!("Confirm: terminate PID {pid}"), "action")
+                } else {
+                    (pid, format!("Terminate PID {pid}"), "secondary")
+                }
+            })
+            .collect();
+        (components, descriptions, pid_buttons)
+    });
+    let port_feedback_snapshot = port_feedback.read().clone();
+
     rsx! {
         section { class: "tab-section overview",
             div { class: "overview-layout",
@@ -534,6 +887,20 @@ fn OverviewTab(
                                 }
                             };
 
+                            if matches!(start_spec, StartSpec::Testnet) {
+                                armed_pid.set(None);
+                                match testnet_port_conflict() {
+                                    Ok(Some(conflict)) => {
+                                        port_conflict.set(Some(conflict));
+                                        port_feedback.set(None);
+                                        return;
+                                    }
+                                    Ok(None) => port_conflict.set(None),
+                                    // The start retries still cover this; don't block on the probe.
+                                    Err(err) => tracing::warn!(?err, "testnet port probe failed"),
+                                }
+                            }
+
                             running_for_start.write().take();
                             let _ = spawn_start_task(start_spec, status_for_start, running_for_start);
                         },
@@ -541,6 +908,67 @@ fn OverviewTab(
                             stop_current_server(status_for_stop, running_for_stop, None::<fn()>);
                         }
                     }
+                    if let Some((components, descriptions, pid_buttons)) = conflict_view {
+                        div { class: "port-conflict",
+                            strong { "Testnet ports are already in use: {components}." }
+                            if descriptions.is_empty() {
+                                p { "The process holding them could not be identified. Close it, or wait for it to exit, then press Start again." }
+                            }
+                            for description in descriptions.iter() {
+                                p { "{description}" }
+                            }
+                            if !descriptions.is_empty() && pid_buttons.is_empty() {
+                                p { "None of them is an earlier run of this app. Stop them yourself, then press Start again." }
+                            }
+                            div { class: "button-row",
+                                for (pid, label, class) in pid_buttons {
+                                    button {
+                                        class,
+                                        onclick: move |_| {
+                                            if *armed_pid.peek() != Some(pid) {
+                                                armed_pid.set(Some(pid));
+                                                return;
+                                            }
+                                            armed_pid.set(None);
+                                            match terminate_process(pid) {
+                                                Ok(()) => {
+                                                    port_feedback.set(Some(ActionFeedback::Info(format!(
+                                                        "Asked PID {pid} to exit. Checking the ports again…"
+                                                    ))));
+                                                    spawn(async move {
+                                                        sleep(Duration::from_secs(1)).await;
+                                                        recheck_testnet_ports(port_conflict, port_feedback);
+                                                    });
+                                                }
+                                                Err(err) => port_feedback.set(Some(ActionFeedback::Error(format!("{err:#}")))),
+                                            }
+                                        },
+                                        "{label}"
+                                    }
+                                }
+                                button {
+                                    class: "secondary",
+                                    onclick: move |_| {
+                                        armed_pid.set(None);
+                                        recheck_testnet_ports(port_conflict, port_feedback);
+                                    },
+                                    "Check again"
+                                }
+                                button {
+                                    class: "secondary",
+                                    onclick: move |_| {
+                                        armed_pid.set(None);
+                                        port_conflict.set(None);
+                                        port_feedback.set(None);
+                                    },
+                                    "Dismiss"
+                                }
+                            }
+                        }
+                    }
+                    if let Some(feedback) = port_feedback_snapshot {
+                        div { class: "admin-feedback {feedback.class()}", "{feedback.message()}" }
+                    }
                     if matches!(selected_network, NetworkProfile::Mainnet) {
                         div { class: "data-dir-summary",
                             span { class: "summary-label", "Data directory" }
@@ -551,12 +979,36 @@ fn OverviewTab(
                         "Testnet runs a local DHT, relays, and homeserver with fixed ports using pubky-testnet."
                     }
                 }
-                StatusPanel { status: status_snapshot }
+                StatusPanel { status: status_snapshot, disk_warning: disk_warning_snapshot }
             }
         }
     }
 }
 
+/// Re-probe the testnet ports after the user acted on a conflict.
+fn recheck_testnet_ports(
+    mut conflict: Signal<Option<PortConflict>, SyncStorage>,
+    mut feedback: Signal<Option<ActionFeedback>, SyncStorage>,
+) {
+    match testnet_port_conflict() {
+        Ok(None) => {
+            conflict.set(None);
+            feedback.set(Some(ActionFeedback::Success(
+                "The testnet ports are free. Press Start to launch the testnet.".into(),
+            )));
+        }
+        Ok(Some(still_bound)) => {
+            conflict.set(Some(still_bound));
+            feedback.set(Some(ActionFeedback::Error(
+                "Some testnet ports are still in use.".into(),
+            )));
+        }
+        Err(err) => feedback.set(Some(ActionFeedback::Error(format!(
+            "Failed to check the testnet ports: {err:#}"
+        )))),
+    }
+}
+
 #[component]
 fn ConfigurationTab(
     network: Signal<NetworkProfile, SyncStorage>,
@@ -596,6 +1048,9 @@ fn ConfigurationTab(
     let status_for_save = status;
     let running_for_save = running_server;
     let network_for_save = network;
+    let mut config_state_for_apply = config_state;
+    let data_dir_for_apply = data_dir;
+    let status_for_apply = status;
     let config_state_for_editor = config_state;
 
     rsx! {
@@ -623,6 +1078,32 @@ fn ConfigurationTab(
                             }
                         }
                     },
+                    on_apply_signup_mode: move |_| {
+                        let (mode, form) = {
+                            let state = config_state_for_apply.read();
+                            (state.form.signup_mode.clone(), state.form.clone())
+                        };
+                        let dir = data_dir_for_apply.read().to_string();
+
+                        let stored = match persist_signup_mode(&dir, &mode) {
+                            Ok(stored) => stored,
+                            Err(err) => {
+                                let mut state = config_state_for_apply.write();
+                                state.feedback = Some(ConfigFeedback::PersistenceError(err.to_string()));
+                                return;
+                            }
+                        };
+
+                        let running = matches!(*status_for_apply.read(), ServerStatus::Running(_));
+                        let message = if running {
+                            "Signup mode saved. Use Save & Restart to apply it to the running homeserver."
+                        } else {
+                            "Signup mode saved. It takes effect the next time the homeserver starts."
+                        };
+                        let mut state = config_state_for_apply.write();
+                        state.dirty = form != stored;
+                        state.feedback = Some(ConfigFeedback::SignupModeNeedsRestart(message.into()));
+                    },
                     on_save_and_restart: move |_| {
                         let form_snapshot = {
                             let state = config_state_for_save.read();
@@ -667,6 +1148,12 @@ fn ConfigurationTab(
                         }
                     }
                 }
+                NodeIdentityCard {
+                    key: "{current_dir}",
+                    data_dir: current_dir.clone(),
+                    status,
+                    running_server,
+                }
                 FooterNotes { data_dir: current_dir }
             }
         }
@@ -675,12 +1162,25 @@ fn ConfigurationTab(
 
 #[component]
 fn AdminTab(
+    network: Signal<NetworkProfile, SyncStorage>,
     status: Signal<ServerStatus, SyncStorage>,
+    running_server: Signal<Option<RunningServer>, SyncStorage>,
     config_state: Signal<ConfigState, SyncStorage>,
+    data_dir: Signal<String, SyncStorage>,
+    disk_threshold: Signal<u64, SyncStorage>,
+    disk_warning: Signal<Option<LowDiskWarning>, SyncStorage>,
 ) -> Element {
     rsx! {
         section { class: "tab-section admin",
-            AdminPanel { status, config_state }
+            AdminPanel {
+                network,
+                status,
+                running_server,
+                config_state,
+                data_dir,
+                disk_threshold,
+                disk_warning,
+            }
         }
     }
 }
@@ -774,10 +1274,16 @@ fn LogsTab() -> Element {
 
 #[component]
 fn AdminPanel(
+    network: Signal<NetworkProfile, SyncStorage>,
     status: Signal<ServerStatus, SyncStorage>,
+    running_server: Signal<Option<RunningServer>, SyncStorage>,
     config_state: Signal<ConfigState, SyncStorage>,
+    data_dir: Signal<String, SyncStorage>,
+    disk_threshold: Signal<u64, SyncStorage>,
+    disk_warning: Signal<Option<LowDiskWarning>, SyncStorage>,
 ) -> Element {
     let mut admin_state = use_signal_sync(AdminPanelState::default);
+    let current_data_dir = data_dir.read().clone();
 
     let config_password = {
         let guard = config_state.read();
@@ -787,6 +1293,9 @@ fn AdminPanel(
         let mut state = admin_state.write();
         state.ensure_password(config_password.clone());
     }
+    if admin_state.peek().audit_loaded_for.as_deref() != Some(current_data_dir.as_str()) {
+        admin_state.write().reload_audit(&current_data_dir);
+    }
 
     let mut poller_started = use_signal_sync(|| false);
     if !*poller_started.read() {
@@ -800,16 +1309,39 @@ fn AdminPanel(
 
     let status_snapshot = status.read().clone();
     let admin_snapshot = admin_state.read().clone();
+    let actions_blocked = admin_snapshot.api_mismatch.is_some();
+    let disk_warning_message = disk_warning.read().clone().map(|mut warning| {
+        if warning.homeserver_used_mb.is_none()
+            && let FetchState::Loaded(info) = &admin_snapshot.info
+        {
+            warning.homeserver_used_mb = Some(info.total_disk_used_mb);
+        }
+        warning.message()
+    });
+    let threshold_mb = *disk_threshold.read();
+    let threshold_value = admin_snapshot
+        .disk_threshold_draft
+        .clone()
+        .unwrap_or_else(|| threshold_mb.to_string());
+
+    let (touch_feedback_mode, touch_copy_hint, signup_token_copy_success, invite_copy_success) =
+        if is_android_touch() {
+            (
+                Some(String::from("tooltip")),
+                Some(String::from("Tap to copy")),
+                Some(String::from("Copied signup token")),
+                Some(String::from("Copied invite")),
+            )
+        } else {
+            (None, None, None, None)
+        };
 
-    let (touch_feedback_mode, touch_copy_hint, signup_token_copy_success) = if is_android_touch() {
-        (
-            Some(String::from("tooltip")),
-            Some(String::from("Tap to copy")),
-            Some(String::from("Copied signup token")),
-        )
-    } else {
-        (None, None, None)
-    };
+    let metrics_history = &admin_snapshot.metrics;
+    let users_trend = metrics_history.sparkline(|sample| sample.num_users as f64);
+    let disk_trend = metrics_history.sparkline(|sample| sample.total_disk_used_mb);
+    let signup_codes_trend = metrics_history.sparkline(|sample| sample.num_signup_codes as f64);
+    let metrics_count = metrics_history.len();
+    let metrics_empty = metrics_history.is_empty();
 
     let info_section = match &admin_snapshot.info {
         FetchState::Idle => match status_snapshot {
@@ -835,6 +1367,22 @@ fn AdminPanel(
                 "None unused".to_string()
             };
             let disk_used = format!("{:.1} MB", info.total_disk_used_mb);
+            let connections_metric = match info.active_connections {
+                Some(active) => {
+                    let peak_hint = match admin_snapshot.connection_peak {
+                        Some(peak) => format!("Peak {peak} since start"),
+                        None => "No peak recorded yet".to_string(),
+                    };
+                    rsx! {
+                        div { class: "admin-metric",
+                            span { class: "metric-label", "Connections" }
+                            span { class: "metric-value", "{active}" }
+                            span { class: "metric-hint", "{peak_hint}" }
+                        }
+                    }
+                }
+                None => rsx! {},
+            };
 
             rsx! {
                 div { class: "admin-metrics-grid",
@@ -842,17 +1390,21 @@ fn AdminPanel(
                         span { class: "metric-label", "Users" }
                         span { class: "metric-value", "{info.num_users}" }
                         span { class: "metric-hint", "{disabled_hint}" }
+                        Sparkline { points: users_trend }
                     }
                     div { class: "admin-metric",
                         span { class: "metric-label", "Disk used" }
                         span { class: "metric-value", "{disk_used}" }
                         span { class: "metric-hint", "Includes all tenants" }
+                        Sparkline { points: disk_trend }
                     }
                     div { class: "admin-metric",
                         span { class: "metric-label", "Signup codes" }
                         span { class: "metric-value", "{info.num_signup_codes}" }
                         span { class: "metric-hint", "{unused_hint}" }
+                        Sparkline { points: signup_codes_trend }
                     }
+                    {connections_metric}
                 }
             }
         }
@@ -885,61 +1437,15 @@ fn AdminPanel(
         state.bump_info_refresh();
     };
 
-    let status_for_token = status;
-    let mut admin_state_for_token = admin_state;
-    let on_generate_token = move |_| {
-        let status_snapshot = status_for_token.read().clone();
-        if let ServerStatus::Running(info) = status_snapshot {
-            let admin_url = info.admin_url.clone();
-            let password = {
-                let state = admin_state_for_token.read();
-                state.password.clone()
-            };
-
-            if password.trim().is_empty() {
-                let mut state = admin_state_for_token.write();
-                state.signup_feedback = Some(ActionFeedback::Error(
-                    "Provide the admin password to generate a signup token.".into(),
-                ));
-                return;
-            }
-
-            {
-                let mut state = admin_state_for_token.write();
-                state.signup_in_flight = true;
-                state.signup_feedback = Some(ActionFeedback::Info(
-                    "Requesting a new signup token…".into(),
-                ));
-                state.signup_token = None;
-            }
-
-            let mut admin_state_task = admin_state_for_token;
-            spawn(async move {
-                let result = admin::generate_signup_token(&admin_url, &password).await;
-                let mut state = admin_state_task.write();
-                match result {
-                    Ok(token) => {
-                        state.signup_in_flight = false;
-                        state.signup_token = Some(token);
-                        state.signup_feedback =
-                            Some(ActionFeedback::Success("Generated a signup token.".into()));
-                        state.bump_info_refresh();
-                    }
-                    Err(err) => {
-                        state.signup_in_flight = false;
-                        state.signup_feedback = Some(ActionFeedback::Error(format!(
-                            "Failed to generate token: {}",
-                            err
-                        )));
-                    }
-                }
-            });
-        } else {
-            let mut state = admin_state_for_token.write();
-            state.signup_feedback = Some(ActionFeedback::Error(
-                "Start the homeserver to create signup tokens.".into(),
-            ));
-        }
+    let on_generate_token = {
+        let status = status;
+        let admin_state = admin_state;
+        move |_| request_signup_token(status, admin_state, data_dir.read().clone(), false)
+    };
+    let on_create_invite = {
+        let status = status;
+        let admin_state = admin_state;
+        move |_| request_signup_token(status, admin_state, data_dir.read().clone(), true)
     };
 
     let status_for_delete = status;
@@ -980,11 +1486,20 @@ fn AdminPanel(
                 state.delete_form.feedback = Some(ActionFeedback::Info("Deleting entry…".into()));
             }
 
+            let network = info.network;
+            let audit_dir = data_dir.read().clone();
             let mut admin_state_task = admin_state_for_delete;
             spawn(async move {
                 let result = admin::delete_entry(&admin_url, &password, &target).await;
                 let mut state = admin_state_task.write();
                 state.delete_form.in_flight = false;
+                let entry = AuditEntry::now(
+                    network,
+                    AuditAction::DeleteEntry,
+                    target.as_str(),
+                    audit_outcome(&result),
+                );
+                state.record_audit(&audit_dir, entry);
                 match result {
                     Ok(()) => {
                         state.delete_form.feedback =
@@ -1010,12 +1525,222 @@ fn AdminPanel(
     let on_disable_user = {
         let status = status;
         let admin_state = admin_state;
-        move |_| toggle_user_access(status, admin_state, true)
+        move |_| toggle_user_access(status, admin_state, data_dir.read().clone(), true)
     };
     let on_enable_user = {
         let status = status;
         let admin_state = admin_state;
-        move |_| toggle_user_access(status, admin_state, false)
+        move |_| toggle_user_access(status, admin_state, data_dir.read().clone(), false)
+    };
+
+    let audit_path = audit_log_path(&current_data_dir).display().to_string();
+    let audit_rows: Vec<_> = admin_snapshot
+        .audit_entries
+        .iter()
+        .enumerate()
+        .map(|(index, entry)| {
+            let (class_name, outcome) = match &entry.outcome {
+                AuditOutcome::Success => ("audit-entry success", format!("{} · ok", entry.network)),
+                AuditOutcome::Failure(reason) => (
+                    "audit-entry error",
+                    format!("{} · failed: {reason}", entry.network),
+                ),
+            };
+            let target = if entry.target.is_empty() {
+                String::from("—")
+            } else {
+                entry.target.clone()
+            };
+            (
+                format!("{index}-{}", entry.timestamp),
+                class_name,
+                entry.timestamp.clone(),
+                entry.action.label(),
+                target,
+                outcome,
+            )
+        })
+        .collect();
+    let mut admin_state_for_audit = admin_state;
+    let data_dir_for_audit = data_dir;
+    let on_reload_audit = move |_| {
+        let dir = data_dir_for_audit.read().clone();
+        admin_state_for_audit.write().reload_audit(&dir);
+    };
+
+    let mut admin_state_for_metrics = admin_state;
+    let data_dir_for_metrics = data_dir;
+    let on_export_metrics = move |_| {
+        let history = admin_state_for_metrics.read().metrics.clone();
+        let path = match file_dialog::save_file(METRICS_CSV_FILE_NAME) {
+            FileDialogResult::Selected(path) => path,
+            FileDialogResult::Cancelled => return,
+            FileDialogResult::Unavailable => {
+                Path::new(data_dir_for_metrics.read().trim()).join(METRICS_CSV_FILE_NAME)
+            }
+        };
+        let feedback = match save_metrics_csv(&path, &history) {
+            Ok(()) => ActionFeedback::Success(format!(
+                "Exported {} samples to {}.",
+                history.len(),
+                path.display()
+            )),
+            Err(err) => ActionFeedback::Error(format!("Failed to export metrics: {err:#}")),
+        };
+        admin_state_for_metrics.write().metrics_feedback = Some(feedback);
+    };
+
+    let mut admin_state_for_threshold_input = admin_state;
+    let on_threshold_input = move |evt: FormEvent| {
+        admin_state_for_threshold_input.write().disk_threshold_draft = Some(evt.value());
+    };
+
+    let mut admin_state_for_threshold = admin_state;
+    let data_dir_for_threshold = data_dir;
+    let mut threshold_for_save = disk_threshold;
+    let on_save_threshold = move |_| {
+        let raw = admin_state_for_threshold
+            .read()
+            .disk_threshold_draft
+            .clone()
+            .unwrap_or_else(|| threshold_for_save.read().to_string());
+        let mut state = admin_state_for_threshold.write();
+        let threshold_mb = match parse_threshold_mb(&raw) {
+            Ok(value) => value,
+            Err(message) => {
+                state.disk_threshold_feedback = Some(ActionFeedback::Error(message));
+                return;
+            }
+        };
+        let dir = data_dir_for_threshold.read().clone();
+        match save_disk_alert_settings(&dir, DiskAlertSettings { threshold_mb }) {
+            Ok(()) => {
+                threshold_for_save.set(threshold_mb);
+                state.disk_threshold_draft = None;
+                state.disk_threshold_feedback =
+                    Some(ActionFeedback::Success(if threshold_mb == 0 {
+                        "Low-disk warnings are off.".into()
+                    } else {
+                        format!("Warning when less than {threshold_mb} MB is free.")
+                    }));
+            }
+            Err(err) => {
+                state.disk_threshold_feedback = Some(ActionFeedback::Error(format!(
+                    "Failed to save the threshold: {err:#}"
+                )));
+            }
+        }
+    };
+
+    let password_change_available = matches!(*network.read(), NetworkProfile::Mainnet);
+    let restart_pending = matches!(
+        status_snapshot,
+        ServerStatus::Starting | ServerStatus::Stopping
+    );
+    let change_password_label = if admin_snapshot.password_form.armed {
+        "Confirm change"
+    } else {
+        "Change admin password"
+    };
+
+    let mut admin_state_for_new_password = admin_state;
+    let mut admin_state_for_confirmation = admin_state;
+    let mut admin_state_for_cancel_change = admin_state;
+    let on_cancel_password_change = move |_| {
+        let mut state = admin_state_for_cancel_change.write();
+        state.password_form.armed = false;
+        state.password_form.feedback = None;
+    };
+
+    let mut admin_state_for_change = admin_state;
+    let mut config_state_for_change = config_state;
+    let data_dir_for_change = data_dir;
+    let network_for_change = network;
+    let status_for_change = status;
+    let running_for_change = running_server;
+    let on_change_password = move |_| {
+        let (new_password, confirmation, armed) = {
+            let state = admin_state_for_change.read();
+            (
+                state.password_form.new_password.clone(),
+                state.password_form.confirmation.clone(),
+                state.password_form.armed,
+            )
+        };
+        let current = config_state_for_change.read().form.admin_password.clone();
+        if let Err(message) = validate_new_admin_password(&current, &new_password, &confirmation) {
+            let mut state = admin_state_for_change.write();
+            state.password_form.armed = false;
+            state.password_form.feedback = Some(ActionFeedback::Error(message));
+            return;
+        }
+
+        let restart = matches!(*status_for_change.read(), ServerStatus::Running(_));
+        if !armed {
+            let mut state = admin_state_for_change.write();
+            state.password_form.armed = true;
+            state.password_form.feedback = Some(ActionFeedback::Info(if restart {
+                "Click \"Confirm change\" to save the new password to config.toml and restart the homeserver.".into()
+            } else {
+                "Click \"Confirm change\" to save the new password to config.toml.".into()
+            }));
+            return;
+        }
+
+        let dir = data_dir_for_change.read().clone();
+        let selection = *network_for_change.read();
+        let result = persist_admin_password(&dir, &new_password);
+        {
+            let mut state = admin_state_for_change.write();
+            let entry = AuditEntry::now(
+                selection,
+                AuditAction::ChangeAdminPassword,
+                "",
+                audit_outcome(&result),
+            );
+            state.record_audit(&dir, entry);
+            state.password_form.armed = false;
+            if let Err(err) = result {
+                state.password_form.feedback = Some(ActionFeedback::Error(format!(
+                    "Failed to save the new password: {err}"
+                )));
+                return;
+            }
+            state.resync_password(new_password.clone());
+            state.password_form.new_password.clear();
+            state.password_form.confirmation.clear();
+        }
+        config_state_for_change.write().form.admin_password = new_password;
+
+        if !restart {
+            admin_state_for_change.write().password_form.feedback = Some(ActionFeedback::Success(
+                "Admin password saved. It takes effect the next time the homeserver starts.".into(),
+            ));
+            return;
+        }
+
+        match resolve_start_spec(selection, &dir) {
+            Ok(start_spec) => {
+                admin_state_for_change.write().password_form.feedback =
+                    Some(ActionFeedback::Success(
+                        "Admin password saved. Restarting the homeserver…".into(),
+                    ));
+                stop_current_server(
+                    status_for_change,
+                    running_for_change,
+                    Some(move || {
+                        let _ = spawn_start_task(start_spec, status_for_change, running_for_change);
+                    }),
+                );
+            }
+            Err(err) => {
+                admin_state_for_change.write().password_form.feedback = Some(
+                    ActionFeedback::Error(format!(
+                        "Admin password saved, but the homeserver could not be restarted ({err}). Restart it from the Overview tab."
+                    )),
+                );
+            }
+        }
     };
 
     let mut admin_state_for_delete_pubkey = admin_state;
@@ -1033,24 +1758,71 @@ fn AdminPanel(
                     button { class: "secondary", onclick: on_refresh_info, "Refresh stats" }
                 }
             }
+            if let Some(reason) = admin_snapshot.api_mismatch.clone() {
+                div { class: "admin-compat-banner",
+                    strong { "This admin UI expects homeserver {SUPPORTED_HOMESERVER_SERIES}.x." }
+                    " The running server's admin API looks different ({reason}), so maintenance actions are disabled until the versions match."
+                }
+            }
+            if let Some(message) = disk_warning_message {
+                div { class: "disk-warning",
+                    strong { "Low disk space. " }
+                    "{message}"
+                }
+            }
             div { class: "admin-card admin-stats-card",
                 h3 { "Homeserver stats" }
                 {info_section}
-            }
-            div { class: "admin-actions-grid",
-                div { class: "admin-card",
-                    h3 { "Credentials & tokens" }
-                    p { "Use your admin password to authenticate API requests." }
-                    label { "Admin password" }
-                    input {
-                        r#type: "password",
+                div { class: "metrics-export-row",
+                    span { class: "metric-hint",
+                        "{metrics_count} samples recorded since the server started (newest {METRICS_HISTORY_LIMIT} kept)."
+                    }
+                    button {
+                        class: "secondary",
+                        onclick: on_export_metrics,
+                        disabled: metrics_empty,
+                        "Export CSV"
+                    }
+                }
+                if let Some(feedback) = admin_snapshot.metrics_feedback.clone() {
+                    div { class: "admin-feedback {feedback.class()}", "{feedback.message()}" }
+                }
+                div { class: "disk-threshold-row",
+                    label { "Warn when free disk space drops below (MB, 0 turns it off)" }
+                    div { class: "button-row",
+                        input {
+                            r#type: "number",
+                            min: "0",
+                            value: "{threshold_value}",
+                            oninput: on_threshold_input,
+                        }
+                        button { class: "secondary", onclick: on_save_threshold, "Save threshold" }
+                    }
+                    if let Some(feedback) = admin_snapshot.disk_threshold_feedback.clone() {
+                        div { class: "admin-feedback {feedback.class()}", "{feedback.message()}" }
+                    }
+                }
+            }
+            div { class: "admin-actions-grid",
+                div { class: "admin-card",
+                    h3 { "Credentials & tokens" }
+                    p { "Use your admin password to authenticate API requests." }
+                    label { "Admin password" }
+                    input {
+                        r#type: "password",
                         value: "{admin_snapshot.password}",
                         oninput: on_password_change,
                         placeholder: "Configured in config.toml",
                     }
                     div { class: "button-row",
                         button { class: "secondary", onclick: on_use_config_password, "Use config value" }
-                        button { class: "action", onclick: on_generate_token, disabled: admin_snapshot.signup_in_flight, "Gen signup token" }
+                        button { class: "action", onclick: on_generate_token, disabled: admin_snapshot.signup_in_flight || actions_blocked, "Gen signup token" }
+                        button {
+                            class: "secondary",
+                            onclick: on_create_invite,
+                            disabled: admin_snapshot.signup_in_flight || actions_blocked,
+                            "Create invite"
+                        }
                     }
                     if let Some(feedback) = admin_snapshot.signup_feedback.clone() {
                         div { class: "admin-feedback {feedback.class()}", "{feedback.message()}" }
@@ -1065,6 +1837,66 @@ fn AdminPanel(
                             "{token}"
                         }
                     }
+                    if let Some(invite) = admin_snapshot.invite.clone() {
+                        p { class: "invite-hint",
+                            "Invite: paste it into the Swiss Knife Sessions tab (Use invite) to fill in this homeserver and the signup code."
+                        }
+                        pre {
+                            class: "token-display invite-display",
+                            "data-touch-copy": touch_copy(invite.clone()),
+                            "data-touch-tooltip": touch_copy_hint.clone(),
+                            "data-touch-feedback": touch_feedback_mode.clone(),
+                            "data-copy-success": invite_copy_success.clone(),
+                            "{invite}"
+                        }
+                    }
+                }
+                div { class: "admin-card",
+                    h3 { "Change admin password" }
+                    if password_change_available {
+                        p {
+                            "Saves a new password to config.toml, restarts the homeserver if it is running, and switches this panel to the new password."
+                        }
+                        label { "New password" }
+                        input {
+                            r#type: "password",
+                            value: "{admin_snapshot.password_form.new_password}",
+                            oninput: move |evt: FormEvent| {
+                                let mut state = admin_state_for_new_password.write();
+                                state.password_form.new_password = evt.value();
+                                state.password_form.armed = false;
+                            },
+                            placeholder: "At least {MIN_ADMIN_PASSWORD_LEN} characters",
+                        }
+                        label { "Repeat new password" }
+                        input {
+                            r#type: "password",
+                            value: "{admin_snapshot.password_form.confirmation}",
+                            oninput: move |evt: FormEvent| {
+                                let mut state = admin_state_for_confirmation.write();
+                                state.password_form.confirmation = evt.value();
+                                state.password_form.armed = false;
+                            },
+                        }
+                        div { class: "button-row",
+                            button {
+                                class: "action",
+                                onclick: on_change_password,
+                                disabled: restart_pending,
+                                "{change_password_label}"
+                            }
+                            if admin_snapshot.password_form.armed {
+                                button { class: "secondary", onclick: on_cancel_password_change, "Cancel" }
+                            }
+                        }
+                        if let Some(feedback) = admin_snapshot.password_form.feedback.clone() {
+                            div { class: "admin-feedback {feedback.class()}", "{feedback.message()}" }
+                        }
+                    } else {
+                        p { class: "admin-info-message",
+                            "The bundled testnet uses a fixed admin password. Switch to Mainnet to change the password in config.toml."
+                        }
+                    }
                 }
                 div { class: "admin-card",
                     h3 { "Delete entry" }
@@ -1093,7 +1925,7 @@ fn AdminPanel(
                         button {
                             class: "action",
                             onclick: on_delete_entry,
-                            disabled: admin_snapshot.delete_form.in_flight,
+                            disabled: admin_snapshot.delete_form.in_flight || actions_blocked,
                             "Delete entry"
                         }
                     }
@@ -1118,13 +1950,13 @@ fn AdminPanel(
                         button {
                             class: "secondary",
                             onclick: on_disable_user,
-                            disabled: admin_snapshot.disable_form.in_flight,
+                            disabled: admin_snapshot.disable_form.in_flight || actions_blocked,
                             "Disable user"
                         }
                         button {
                             class: "secondary",
                             onclick: on_enable_user,
-                            disabled: admin_snapshot.disable_form.in_flight,
+                            disabled: admin_snapshot.disable_form.in_flight || actions_blocked,
                             "Enable user"
                         }
                     }
@@ -1133,6 +1965,278 @@ fn AdminPanel(
                     }
                 }
             }
+            div { class: "admin-card audit-card",
+                div { class: "audit-header",
+                    h3 { "Audit log" }
+                    button { class: "secondary", onclick: on_reload_audit, "Reload" }
+                }
+                p {
+                    "Admin actions taken from this app are appended to "
+                    code { "{audit_path}" }
+                    "."
+                }
+                if let Some(message) = admin_snapshot.audit_error.clone() {
+                    div { class: "admin-feedback error", "{message}" }
+                }
+                if admin_snapshot.audit_entries.is_empty() {
+                    div { class: "admin-info-message", "No admin actions recorded yet." }
+                } else {
+                    ul { class: "audit-list",
+                        for (key, class_name, timestamp, action, target, outcome) in audit_rows {
+                            li { key: "{key}", class: "{class_name}",
+                                span { class: "audit-time", "{timestamp}" }
+                                span { class: "audit-action", "{action}" }
+                                span { class: "audit-target", "{target}" }
+                                span { class: "audit-outcome", "{outcome}" }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Trend line drawn under a stats card once two polls have been recorded.
+#[component]
+fn Sparkline(points: Option<String>) -> Element {
+    let Some(points) = points else {
+        return rsx! {};
+    };
+    rsx! {
+        svg {
+            class: "sparkline",
+            view_box: "0 0 {SPARKLINE_WIDTH} {SPARKLINE_HEIGHT}",
+            preserve_aspect_ratio: "none",
+            aria_hidden: "true",
+            polyline {
+                points,
+                fill: "none",
+                stroke: "currentColor",
+                stroke_width: "1.5",
+                stroke_linejoin: "round",
+            }
+        }
+    }
+}
+
+#[component]
+fn SetupWizard(
+    data_dir: Signal<String, SyncStorage>,
+    network: Signal<NetworkProfile, SyncStorage>,
+    config_state: Signal<ConfigState, SyncStorage>,
+    status: Signal<ServerStatus, SyncStorage>,
+    running_server: Signal<Option<RunningServer>, SyncStorage>,
+    show_wizard: Signal<bool, SyncStorage>,
+) -> Element {
+    let wizard = use_signal_sync(|| {
+        let dir = data_dir.read().clone();
+        let form = config_state.read().form.clone();
+        WizardState::new(dir, &form)
+    });
+    let snapshot = wizard.read().clone();
+    let total_steps = WizardStep::ALL.len();
+    let step_label = format!("Step {} of {total_steps}", snapshot.position());
+
+    let mut wizard_for_dir = wizard;
+    let mut wizard_for_mainnet = wizard;
+    let mut wizard_for_testnet = wizard;
+    let mut wizard_for_token = wizard;
+    let mut wizard_for_open = wizard;
+    let mut wizard_for_password = wizard;
+
+    let step_body = match snapshot.step {
+        WizardStep::DataDir => rsx! {
+            p { "Your homeserver keeps its keys, configuration, and user data in this folder." }
+            DataDirInput {
+                value: snapshot.data_dir.clone(),
+                on_change: move |value| wizard_for_dir.write().data_dir = value,
+            }
+        },
+        WizardStep::Network => rsx! {
+            p { "Mainnet serves real users. The Static Testnet runs everything locally for demos and ignores the data directory." }
+            div { class: "signup-mode-options",
+                label { class: "signup-mode-option",
+                    input {
+                        r#type: "radio",
+                        name: "wizard-network",
+                        checked: matches!(snapshot.network, NetworkProfile::Mainnet),
+                        onchange: move |_| wizard_for_mainnet.write().network = NetworkProfile::Mainnet,
+                    }
+                    span { "{NetworkProfile::Mainnet.label()}" }
+                }
+                label { class: "signup-mode-option",
+                    input {
+                        r#type: "radio",
+                        name: "wizard-network",
+                        checked: matches!(snapshot.network, NetworkProfile::Testnet),
+                        onchange: move |_| wizard_for_testnet.write().network = NetworkProfile::Testnet,
+                    }
+                    span { "{NetworkProfile::Testnet.label()}" }
+                }
+            }
+        },
+        WizardStep::SignupMode => rsx! {
+            p { "Require a signup token to control who can create accounts, or let anyone sign up." }
+            div { class: "signup-mode-options",
+                label { class: "signup-mode-option",
+                    input {
+                        r#type: "radio",
+                        name: "wizard-signup-mode",
+                        checked: matches!(snapshot.signup_mode, SignupMode::TokenRequired),
+                        onchange: move |_| wizard_for_token.write().signup_mode = SignupMode::TokenRequired,
+                    }
+                    span { "Token required" }
+                }
+                label { class: "signup-mode-option",
+                    input {
+                        r#type: "radio",
+                        name: "wizard-signup-mode",
+                        checked: matches!(snapshot.signup_mode, SignupMode::Open),
+                        onchange: move |_| wizard_for_open.write().signup_mode = SignupMode::Open,
+                    }
+                    span { "Open signup" }
+                }
+            }
+        },
+        WizardStep::AdminPassword => rsx! {
+            p { "The admin password protects maintenance endpoints such as signup tokens and user management." }
+            div { class: "config-field",
+                label { "Admin password" }
+                input {
+                    r#type: "password",
+                    value: "{snapshot.admin_password}",
+                    placeholder: "At least {MIN_ADMIN_PASSWORD_LEN} characters",
+                    oninput: move |evt: FormEvent| wizard_for_password.write().admin_password = evt.value(),
+                }
+            }
+        },
+        WizardStep::Review => {
+            let signup_label = match snapshot.signup_mode {
+                SignupMode::Open => "Open signup",
+                SignupMode::TokenRequired => "Token required",
+            };
+            rsx! {
+                p { "Everything is ready. We'll write config.toml and start your homeserver." }
+                div { class: "data-dir-summary",
+                    span { class: "summary-label", "Data directory" }
+                    span { class: "summary-value", "{snapshot.data_dir}" }
+                    span { class: "summary-label", "Network" }
+                    span { class: "summary-value", "{snapshot.network.label()}" }
+                    span { class: "summary-label", "Signup mode" }
+                    span { class: "summary-value", "{signup_label}" }
+                }
+            }
+        }
+    };
+
+    let mut wizard_for_back = wizard;
+    let mut wizard_for_next = wizard;
+    let mut wizard_for_finish = wizard;
+    let mut show_wizard_for_skip = show_wizard;
+    let mut wizard_for_skip = wizard;
+    let mut show_wizard_for_finish = show_wizard;
+    let mut data_dir_for_finish = data_dir;
+    let mut network_for_finish = network;
+    let mut config_state_for_finish = config_state;
+    let mut status_for_finish = status;
+    let mut running_for_finish = running_server;
+
+    let on_finish = move |_: MouseEvent| {
+        let answers = {
+            let mut state = wizard_for_finish.write();
+            if !state.validate_all() {
+                return;
+            }
+            state.clone()
+        };
+
+        let dir = answers.data_dir.trim().to_string();
+        // Start from what the chosen folder already holds, so an existing
+        // config.toml keeps its sockets, domains, and limits.
+        let mut form = match load_config_form_from_dir(&dir) {
+            Ok(form) => form,
+            Err(err) => {
+                wizard_for_finish.write().error = Some(err.to_string());
+                return;
+            }
+        };
+        answers.apply_to_form(&mut form);
+
+        if let Err(err) = persist_config_form(&dir, &form) {
+            wizard_for_finish.write().error = Some(err.to_string());
+            return;
+        }
+
+        *data_dir_for_finish.write() = dir.clone();
+        *network_for_finish.write() = answers.network;
+        {
+            let mut state = config_state_for_finish.write();
+            state.form = form;
+            state.dirty = false;
+            state.feedback = None;
+        }
+
+        match resolve_start_spec(answers.network, &dir) {
+            Ok(start_spec) => {
+                running_for_finish.write().take();
+                let _ = spawn_start_task(start_spec, status_for_finish, running_for_finish);
+            }
+            Err(err) => *status_for_finish.write() = ServerStatus::Error(err.to_string()),
+        }
+
+        *show_wizard_for_finish.write() = false;
+    };
+
+    // Write the current settings so the wizard is not offered again on the
+    // next launch.
+    let on_skip = move |_: MouseEvent| {
+        let dir = data_dir.read().clone();
+        let form = config_state.read().form.clone();
+        if let Err(err) = persist_config_form(&dir, &form) {
+            wizard_for_skip.write().error = Some(err.to_string());
+            return;
+        }
+        *show_wizard_for_skip.write() = false;
+    };
+
+    rsx! {
+        section { class: "tab-section wizard",
+            div { class: "empty-state-card wizard-card",
+                span { class: "summary-label", "{step_label}" }
+                h2 { "{snapshot.step.title()}" }
+                {step_body}
+                if let Some(message) = snapshot.error.clone() {
+                    div { class: "config-feedback error", "{message}" }
+                }
+                div { class: "button-row",
+                    button {
+                        class: "secondary",
+                        disabled: snapshot.is_first(),
+                        onclick: move |_: MouseEvent| wizard_for_back.write().back(),
+                        "Back"
+                    }
+                    if snapshot.is_last() {
+                        button { class: "action", onclick: on_finish, "Save & start server" }
+                    } else {
+                        button {
+                            class: "action",
+                            onclick: move |_: MouseEvent| {
+                                wizard_for_next.write().advance();
+                            },
+                            "Next"
+                        }
+                    }
+                }
+                p { class: "footnote",
+                    "Already know your way around? "
+                    button {
+                        class: "link-button",
+                        onclick: on_skip,
+                        "Skip setup"
+                    }
+                }
+            }
         }
     }
 }
@@ -1162,6 +2266,7 @@ fn ConfigEditor(
     config_state: Signal<ConfigState, SyncStorage>,
     restart_blocked: bool,
     on_reload: EventHandler<()>,
+    on_apply_signup_mode: EventHandler<()>,
     on_save_and_restart: EventHandler<()>,
 ) -> Element {
     let snapshot = config_state.read().clone();
@@ -1178,7 +2283,37 @@ fn ConfigEditor(
         logging_level,
     } = snapshot.form.clone();
 
+    let bind_scope = BindScope::of(&snapshot.form);
     let save_disabled = restart_blocked || !snapshot.dirty;
+    let apply_disabled = restart_blocked;
+
+    let mut detect_feedback = use_signal_sync(|| None::<ActionFeedback>);
+    let detect_snapshot = detect_feedback.read().clone();
+    let detecting = matches!(detect_snapshot, Some(ActionFeedback::Info(_)));
+    let reachability = reachability_note(&pkdns_public_ip);
+    let config_state_detect = config_state;
+    let on_detect_public_ip = move |_: MouseEvent| {
+        detect_feedback.set(Some(ActionFeedback::Info(format!(
+            "Asking {PUBLIC_IP_SERVICE} for this machine's public IP…"
+        ))));
+        spawn(async move {
+            match detect_public_ip().await {
+                Ok(ip) => {
+                    modify_config_form(config_state_detect, |form| {
+                        form.pkdns_public_ip = ip.to_string();
+                    });
+                    detect_feedback.set(Some(ActionFeedback::Success(format!(
+                        "Filled in {ip}. Review it, then Save & Restart to advertise it."
+                    ))));
+                }
+                Err(err) => {
+                    detect_feedback.set(Some(ActionFeedback::Error(format!(
+                        "Could not detect the public IP: {err}"
+                    ))));
+                }
+            }
+        });
+    };
 
     let feedback = snapshot.feedback.clone();
     let config_state_pubky = config_state;
@@ -1198,7 +2333,18 @@ fn ConfigEditor(
                 button { class: "secondary", onclick: move |_: MouseEvent| on_reload.call(()), "Reload from disk" }
             }
 
-            SignupModePicker { selection: signup_mode, config_state }
+            div { class: "signup-mode-row",
+                SignupModePicker { selection: signup_mode, config_state }
+                button {
+                    class: "secondary",
+                    disabled: apply_disabled,
+                    title: "Save only the signup mode; it takes effect when the homeserver restarts",
+                    onclick: move |_: MouseEvent| on_apply_signup_mode.call(()),
+                    "Save signup mode"
+                }
+            }
+
+            BindScopePicker { config_state, scope: bind_scope, public_ip: pkdns_public_ip.clone() }
 
             div { class: "config-grid",
                 ConfigField {
@@ -1281,17 +2427,24 @@ fn ConfigEditor(
                         });
                     },
                 }
-                ConfigField {
-                    label: "Logging level override",
-                    value: logging_level,
-                    placeholder: "info",
-                    on_change: move |value| {
-                        modify_config_form(config_state_logging, |form| {
-                            form.logging_level = value;
-                        });
-                    },
+                LoggingLevelField { value: logging_level, config_state: config_state_logging }
+            }
+
+            div { class: "reachability-row",
+                button {
+                    class: "secondary",
+                    disabled: detecting,
+                    title: "Look up this machine's public IP and fill the Public IP address field",
+                    onclick: on_detect_public_ip,
+                    "Detect public IP"
+                }
+                if let Some(note) = reachability {
+                    p { class: "reachability-note", "{note}" }
                 }
             }
+            if let Some(detect) = detect_snapshot {
+                div { class: "config-feedback {detect.class()}", "{detect.message()}" }
+            }
 
             if let Some(feedback) = feedback {
                 match feedback {
@@ -1300,6 +2453,9 @@ fn ConfigEditor(
                             p { "Configuration saved. Restarting homeserver..." }
                         }
                     },
+                    ConfigFeedback::SignupModeNeedsRestart(message) => rsx! {
+                        div { class: "config-feedback info", "{message}" }
+                    },
                     ConfigFeedback::ValidationError(message) => rsx! {
                         div { class: "config-feedback error", "{message}" }
                     },
@@ -1363,6 +2519,146 @@ fn SignupModePicker(
     }
 }
 
+/// Local-only vs all-interfaces toggle for the drive listen sockets.
+#[component]
+fn BindScopePicker(
+    config_state: Signal<ConfigState, SyncStorage>,
+    scope: BindScope,
+    public_ip: String,
+) -> Element {
+    let mut error = use_signal_sync(|| None::<String>);
+    let mut select_scope = move |selected: BindScope| {
+        let mut form = config_state.read().form.clone();
+        match apply_bind_scope(&mut form, selected) {
+            Ok(()) => {
+                error.set(None);
+                modify_config_form(config_state, |current| {
+                    current.drive_pubky_listen_socket = form.drive_pubky_listen_socket;
+                    current.drive_icann_listen_socket = form.drive_icann_listen_socket;
+                });
+            }
+            Err(err) => error.set(Some(format!("Fix the listen sockets first: {err}"))),
+        }
+    };
+    let advertises_remote_ip = public_ip
+        .trim()
+        .parse::<std::net::IpAddr>()
+        .is_ok_and(|ip| !ip.is_loopback() && !ip.is_unspecified());
+    let note = match scope {
+        BindScope::AllInterfaces => Some((
+            "bind-note warning",
+            "The homeserver accepts connections from any machine that can reach this one, including the internet if your router forwards the ports. The admin API stays on its own socket; keep it on 127.0.0.1 and use a strong admin password.",
+        )),
+        BindScope::LocalOnly if advertises_remote_ip => Some((
+            "bind-note warning",
+            "The public IP is set, but the homeserver only listens on 127.0.0.1, so other machines cannot connect. Choose All interfaces to accept them.",
+        )),
+        BindScope::LocalOnly => None,
+        BindScope::Custom => Some((
+            "bind-note",
+            "The listen sockets below use custom addresses. Pick an option to rewrite both drive sockets.",
+        )),
+    };
+
+    rsx! {
+        div { class: "signup-mode-group",
+            span { "Listen on" }
+            div { class: "signup-mode-options",
+                label { class: "signup-mode-option",
+                    input {
+                        r#type: "radio",
+                        name: "bind-scope",
+                        value: "local",
+                        checked: scope == BindScope::LocalOnly,
+                        onchange: move |_| select_scope(BindScope::LocalOnly),
+                    }
+                    span { "Local only (127.0.0.1)" }
+                }
+                label { class: "signup-mode-option",
+                    input {
+                        r#type: "radio",
+                        name: "bind-scope",
+                        value: "all",
+                        checked: scope == BindScope::AllInterfaces,
+                        onchange: move |_| select_scope(BindScope::AllInterfaces),
+                    }
+                    span { "All interfaces (0.0.0.0)" }
+                }
+            }
+            if let Some((class, text)) = note {
+                p { class, "{text}" }
+            }
+            if let Some(message) = error.read().clone() {
+                div { class: "config-feedback error", "{message}" }
+            }
+        }
+    }
+}
+
+/// Logging presets, plus a free-text level behind the "Advanced" entry that is
+/// checked as it is typed.
+#[component]
+fn LoggingLevelField(config_state: Signal<ConfigState, SyncStorage>, value: String) -> Element {
+    let mut custom_selected =
+        use_signal_sync(|| LoggingPreset::for_level(&value) == LoggingPreset::Custom);
+    let preset = if *custom_selected.read() {
+        LoggingPreset::Custom
+    } else {
+        LoggingPreset::for_level(&value)
+    };
+    let custom_error = if preset == LoggingPreset::Custom {
+        validate_logging_level(&value).err()
+    } else {
+        None
+    };
+
+    rsx! {
+        div { class: "config-field",
+            label { "Logging level" }
+            select {
+                value: preset.key(),
+                onchange: move |evt: FormEvent| {
+                    let Some(selected) = LoggingPreset::from_key(&evt.value()) else {
+                        return;
+                    };
+                    match selected.level() {
+                        Some(level) => {
+                            custom_selected.set(false);
+                            modify_config_form(config_state, |form| {
+                                form.logging_level = level.to_string();
+                            });
+                        }
+                        None => custom_selected.set(true),
+                    }
+                },
+                for option in LoggingPreset::ALL {
+                    option {
+                        value: option.key(),
+                        selected: option == preset,
+                        "{option.label()}"
+                    }
+                }
+            }
+            if preset == LoggingPreset::Custom {
+                input {
+                    r#type: "text",
+                    value: "{value}",
+                    placeholder: "warn",
+                    oninput: move |evt: FormEvent| {
+                        let level = evt.value();
+                        modify_config_form(config_state, |form| {
+                            form.logging_level = level;
+                        });
+                    },
+                }
+                if let Some(error) = custom_error {
+                    p { class: "config-field-error", "{error}" }
+                }
+            }
+        }
+    }
+}
+
 #[component]
 fn ConfigField(
     label: &'static str,
@@ -1408,6 +2704,195 @@ fn ActionButtons(
     }
 }
 
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+struct IdentityFormState {
+    export_path: String,
+    import_path: String,
+    passphrase: String,
+    feedback: Option<ActionFeedback>,
+}
+
+#[component]
+fn NodeIdentityCard(
+    data_dir: String,
+    status: Signal<ServerStatus, SyncStorage>,
+    running_server: Signal<Option<RunningServer>, SyncStorage>,
+) -> Element {
+    let form = use_signal_sync(IdentityFormState::default);
+    let snapshot = form.read().clone();
+    let dir_for_key = data_dir.clone();
+    let mut node_key = use_signal_sync(move || node_public_key(&dir_for_key));
+    let dir_for_refresh = data_dir.clone();
+    use_effect(move || {
+        // The first start generates the key, so re-read it when the status moves.
+        let _ = status.read();
+        node_key.set(node_public_key(&dir_for_refresh));
+    });
+    let public_key = node_key.read().clone();
+    let restart_blocked = matches!(
+        *status.read(),
+        ServerStatus::Starting | ServerStatus::Stopping
+    );
+    let (touch_hint, touch_feedback, copy_success) = if is_android_touch() {
+        (
+            Some(String::from("Tap to copy")),
+            Some(String::from("tooltip")),
+            Some(String::from("Copied node public key")),
+        )
+    } else {
+        (None, None, None)
+    };
+
+    let mut form_for_export_path = form;
+    let mut form_for_import_path = form;
+    let mut form_for_passphrase = form;
+    let mut form_for_copy = form;
+    let mut form_for_export = form;
+    let mut form_for_import = form;
+    let dir_for_export = data_dir.clone();
+    let dir_for_import = data_dir.clone();
+
+    let on_export = move |_| {
+        let (target, passphrase) = {
+            let state = form_for_export.read();
+            (state.export_path.clone(), state.passphrase.clone())
+        };
+        let feedback = match export_node_recovery_file(&dir_for_export, &target, &passphrase) {
+            Ok(path) => ActionFeedback::Success(format!(
+                "Saved the node recovery file to {}.",
+                path.display()
+            )),
+            Err(err) => ActionFeedback::Error(format!("{err:#}")),
+        };
+        node_key.set(node_public_key(&dir_for_export));
+        form_for_export.write().feedback = Some(feedback);
+    };
+
+    let on_import = move |_| {
+        let (source, passphrase) = {
+            let state = form_for_import.read();
+            (state.import_path.clone(), state.passphrase.clone())
+        };
+        if source.trim().is_empty() {
+            form_for_import.write().feedback = Some(ActionFeedback::Error(
+                "Enter the path of the recovery file to import.".into(),
+            ));
+            return;
+        }
+
+        form_for_import.write().feedback = Some(ActionFeedback::Info(
+            "Stopping the homeserver before replacing its key...".into(),
+        ));
+        let dir = dir_for_import.clone();
+        let mut form_after_stop = form_for_import;
+        stop_current_server(
+            status,
+            running_server,
+            Some(move || {
+                let feedback = match import_node_recovery_file(&dir, &source, &passphrase) {
+                    Ok(keypair) => ActionFeedback::Success(format!(
+                        "Imported node key {}. Start the server to publish it; the previous key was kept as secret.bak.",
+                        keypair.public_key()
+                    )),
+                    Err(err) => ActionFeedback::Error(format!("{err:#}")),
+                };
+                node_key.set(node_public_key(&dir));
+                form_after_stop.write().feedback = Some(feedback);
+            }),
+        );
+    };
+
+    let secret_path = secret_file_path(&data_dir).display().to_string();
+    let key_section = match public_key {
+        Ok(Some(key)) => {
+            let key_for_copy = key.clone();
+            rsx! {
+                label { "Public key" }
+                pre {
+                    class: "public-key",
+                    "data-touch-copy": touch_copy(key.clone()),
+                    "data-touch-tooltip": touch_hint,
+                    "data-touch-feedback": touch_feedback,
+                    "data-copy-success": copy_success,
+                    "{key}"
+                }
+                div { class: "button-row",
+                    button {
+                        class: "secondary",
+                        onclick: move |_| {
+                            let script = format!("navigator.clipboard.writeText({key_for_copy:?});");
+                            let _ = document::eval(&script);
+                            form_for_copy.write().feedback =
+                                Some(ActionFeedback::Info("Copied the node public key.".into()));
+                        },
+                        "Copy public key"
+                    }
+                }
+            }
+        }
+        Ok(None) => rsx! {
+            p { class: "footnote", "No key yet. The homeserver generates one the first time it starts." }
+        },
+        Err(message) => rsx! {
+            div { class: "admin-feedback error", "{message}" }
+        },
+    };
+
+    rsx! {
+        div { class: "admin-card identity-card",
+            h3 { "Node identity" }
+            p {
+                "The homeserver signs its PKARR records with the key stored in "
+                code { "{secret_path}" }
+                "."
+            }
+            {key_section}
+            label { "Recovery passphrase" }
+            input {
+                r#type: "password",
+                value: "{snapshot.passphrase}",
+                placeholder: "Used to encrypt or decrypt the recovery file",
+                oninput: move |evt: FormEvent| form_for_passphrase.write().passphrase = evt.value(),
+            }
+            label { "Export to" }
+            input {
+                r#type: "text",
+                value: "{snapshot.export_path}",
+                placeholder: "~/Backups/homeserver.pkarr",
+                oninput: move |evt: FormEvent| form_for_export_path.write().export_path = evt.value(),
+            }
+            label { "Import from" }
+            input {
+                r#type: "text",
+                value: "{snapshot.import_path}",
+                placeholder: "~/Backups/homeserver.pkarr",
+                oninput: move |evt: FormEvent| form_for_import_path.write().import_path = evt.value(),
+            }
+            div { class: "button-row",
+                button { class: "secondary", onclick: on_export, "Export recovery file" }
+                button {
+                    class: "action",
+                    disabled: restart_blocked,
+                    title: "Stops the homeserver, then replaces its key",
+                    onclick: on_import,
+                    "Import key"
+                }
+            }
+            if let Some(feedback) = snapshot.feedback.clone() {
+                div { class: "admin-feedback {feedback.class()}", "{feedback.message()}" }
+            }
+        }
+    }
+}
+
+/// Public key of the node identity stored in `data_dir`, if any.
+fn node_public_key(data_dir: &str) -> Result<Option<String>, String> {
+    match read_node_keypair(data_dir) {
+        Ok(keypair) => Ok(keypair.map(|keypair| keypair.public_key().to_string())),
+        Err(err) => Err(err.to_string()),
+    }
+}
+
 #[component]
 fn FooterNotes(data_dir: String) -> Element {
     rsx! {
@@ -1423,7 +2908,7 @@ fn FooterNotes(data_dir: String) -> Element {
 }
 
 #[component]
-fn StatusPanel(status: ServerStatus) -> Element {
+fn StatusPanel(status: ServerStatus, disk_warning: Option<LowDiskWarning>) -> Element {
     let StatusCopy {
         class_name,
         heading,
@@ -1509,6 +2994,12 @@ fn StatusPanel(status: ServerStatus) -> Element {
         div { class: "status-card {class_name}",
             h2 { "{heading}" }
             p { "{summary}" }
+            if let Some(warning) = disk_warning {
+                div { class: "disk-warning",
+                    strong { "Low disk space. " }
+                    "{warning.message()}"
+                }
+            }
             {details_section}
         }
     }
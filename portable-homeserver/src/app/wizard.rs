@@ -0,0 +1,223 @@
+use pubky_homeserver::SignupMode;
+
+use super::config::ConfigForm;
+use super::state::NetworkProfile;
+
+/// Minimum admin password length accepted by the first-run wizard.
+pub(crate) const MIN_ADMIN_PASSWORD_LEN: usize = 8;
+
+/// Ordered steps of the first-run setup wizard.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum WizardStep {
+    DataDir,
+    Network,
+    SignupMode,
+    AdminPassword,
+    Review,
+}
+
+impl WizardStep {
+    pub(crate) const ALL: [Self; 5] = [
+        Self::DataDir,
+        Self::Network,
+        Self::SignupMode,
+        Self::AdminPassword,
+        Self::Review,
+    ];
+
+    pub(crate) fn title(self) -> &'static str {
+        match self {
+            Self::DataDir => "Choose a data directory",
+            Self::Network => "Pick a network",
+            Self::SignupMode => "Decide who can sign up",
+            Self::AdminPassword => "Protect the admin API",
+            Self::Review => "Review and launch",
+        }
+    }
+
+    fn index(self) -> usize {
+        Self::ALL
+            .iter()
+            .position(|step| *step == self)
+            .unwrap_or_default()
+    }
+
+    fn next(self) -> Option<Self> {
+        Self::ALL.get(self.index() + 1).copied()
+    }
+
+    fn previous(self) -> Option<Self> {
+        self.index().checked_sub(1).map(|index| Self::ALL[index])
+    }
+}
+
+/// Answers collected by the first-run wizard before they are written to
+/// `config.toml` and the homeserver is started.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct WizardState {
+    pub(crate) step: WizardStep,
+    pub(crate) data_dir: String,
+    pub(crate) network: NetworkProfile,
+    pub(crate) signup_mode: SignupMode,
+    pub(crate) admin_password: String,
+    pub(crate) error: Option<String>,
+}
+
+impl WizardState {
+    pub(crate) fn new(data_dir: String, form: &ConfigForm) -> Self {
+        Self {
+            step: WizardStep::DataDir,
+            data_dir,
+            network: NetworkProfile::Mainnet,
+            signup_mode: form.signup_mode.clone(),
+            admin_password: String::new(),
+            error: None,
+        }
+    }
+
+    /// 1-based position of the current step, used for the progress label.
+    pub(crate) fn position(&self) -> usize {
+        self.step.index() + 1
+    }
+
+    pub(crate) fn is_first(&self) -> bool {
+        self.step.previous().is_none()
+    }
+
+    pub(crate) fn is_last(&self) -> bool {
+        self.step.next().is_none()
+    }
+
+    /// Validate the answers gathered on the current step.
+    pub(crate) fn validate_step(&self) -> Result<(), String> {
+        match self.step {
+            WizardStep::DataDir => {
+                if self.data_dir.trim().is_empty() {
+                    return Err("Choose a folder where the homeserver can keep its data.".into());
+                }
+            }
+            WizardStep::AdminPassword => {
+                if self.admin_password.trim().len() < MIN_ADMIN_PASSWORD_LEN {
+                    return Err(format!(
+                        "Use an admin password with at least {MIN_ADMIN_PASSWORD_LEN} characters."
+                    ));
+                }
+            }
+            WizardStep::Network | WizardStep::SignupMode | WizardStep::Review => {}
+        }
+
+        Ok(())
+    }
+
+    /// Move to the next step when the current one validates. Returns `true` when
+    /// the wizard advanced.
+    pub(crate) fn advance(&mut self) -> bool {
+        if let Err(message) = self.validate_step() {
+            self.error = Some(message);
+            return false;
+        }
+
+        match self.step.next() {
+            Some(next) => {
+                self.step = next;
+                self.error = None;
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub(crate) fn back(&mut self) {
+        if let Some(previous) = self.step.previous() {
+            self.step = previous;
+            self.error = None;
+        }
+    }
+
+    /// Validate every step, jumping back to the first one that needs attention.
+    pub(crate) fn validate_all(&mut self) -> bool {
+        let current = self.step;
+        for step in WizardStep::ALL {
+            self.step = step;
+            if let Err(message) = self.validate_step() {
+                self.error = Some(message);
+                return false;
+            }
+        }
+        self.step = current;
+        self.error = None;
+        true
+    }
+
+    /// Copy the wizard answers into the configuration form.
+    pub(crate) fn apply_to_form(&self, form: &mut ConfigForm) {
+        form.signup_mode = self.signup_mode.clone();
+        form.admin_password = self.admin_password.trim().to_string();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn wizard() -> WizardState {
+        WizardState::new("/tmp/pubky".into(), &ConfigForm::default())
+    }
+
+    #[test]
+    fn walks_through_every_step_in_order() {
+        let mut state = wizard();
+        state.admin_password = "correct horse".into();
+
+        let mut visited = vec![state.step];
+        while state.advance() {
+            visited.push(state.step);
+        }
+
+        assert_eq!(visited, WizardStep::ALL.to_vec());
+        assert!(state.is_last());
+        assert_eq!(state.position(), WizardStep::ALL.len());
+
+        state.back();
+        assert_eq!(state.step, WizardStep::AdminPassword);
+    }
+
+    #[test]
+    fn blocks_on_missing_data_dir() {
+        let mut state = wizard();
+        state.data_dir = "   ".into();
+
+        assert!(!state.advance());
+        assert_eq!(state.step, WizardStep::DataDir);
+        assert!(state.error.is_some());
+    }
+
+    #[test]
+    fn rejects_short_admin_passwords() {
+        let mut state = wizard();
+        state.admin_password = "short".into();
+
+        assert!(!state.validate_all());
+        assert_eq!(state.step, WizardStep::AdminPassword);
+        assert!(
+            state
+                .error
+                .as_deref()
+                .unwrap_or_default()
+                .contains("at least")
+        );
+    }
+
+    #[test]
+    fn applies_answers_to_config_form() {
+        let mut state = wizard();
+        state.signup_mode = SignupMode::Open;
+        state.admin_password = "  longer-secret ".into();
+
+        let mut form = ConfigForm::default();
+        state.apply_to_form(&mut form);
+
+        assert_eq!(form.signup_mode, SignupMode::Open);
+        assert_eq!(form.admin_password, "longer-secret");
+    }
+}
@@ -1,20 +1,38 @@
 use dioxus::prelude::*;
+use pkarr::SignedPacket;
 use pubky::{Keypair, PubkyAuthFlow, PubkySession};
+use std::collections::HashMap;
 
 use crate::components::{NetworkToggleOption, TabButton};
 use crate::style::APP_STYLE;
 use crate::tabs::{
-    AuthTabState, HttpTabState, KeysTabState, PkdnsTabState, SessionsTabState, SocialTabState,
-    StorageTabState, TokensTabState, render_auth_tab, render_http_tab, render_keys_tab,
-    render_pkdns_tab, render_sessions_tab, render_social_tab, render_storage_tab,
-    render_tokens_tab,
+    AuthTabState, HttpTabState, KeysTabState, PkdnsTabState, SessionsTabState, SettingsTabState,
+    SocialTabState, StorageTabState, TokensTabState, render_auth_tab, render_http_tab,
+    render_keys_tab, render_pkdns_tab, render_sessions_tab, render_settings_tab, render_social_tab,
+    render_storage_tab, render_tokens_tab, start_republish_loop,
 };
-use crate::utils::logging::{ActivityLog, LogEntry};
+use crate::utils::accounts::{AccountSwitcher, load_accounts};
+use crate::utils::capabilities::CapabilityPreset;
+use crate::utils::clipboard::copy_to_clipboard;
+use crate::utils::confirm::MainnetGuard;
+use crate::utils::file_dialog::{self, FileDialogResult};
+use crate::utils::http::DEFAULT_MULTIPART_FIELD;
+use crate::utils::links::open_external_link;
+use crate::utils::logging::{ActivityLog, LogEntry, LogFilter, LogLevel, format_log, save_log};
 use crate::utils::mobile::{MobileEnhancementsScript, touch_tooltip};
-use crate::utils::pubky::{PubkyFacadeHandle, PubkyFacadeState, PubkyFacadeStatus};
-
-const TESTNET_DEFAULT_SESSION_HOMESERVER: &str =
-    "8pinxxgqs41n4aididenw5apqp1urfmzdztr8jt4abrkdn435ewo";
+use crate::utils::pkdns::{DEFAULT_REPUBLISH_MINUTES, parse_republish_minutes};
+use crate::utils::prefs::{INPUTS_SAVE_DELAY, InputFields, PrefsHandle, SavedInputs, load_prefs};
+use crate::utils::pubky::{
+    MAX_FACADE_RETRIES, PubkyFacadeHandle, PubkyFacadeState, PubkyFacadeStatus, facade_retry_delay,
+};
+use crate::utils::qr::QrSize;
+use crate::utils::relay::DEFAULT_APPROVAL_TIMEOUT_SECS;
+use crate::utils::session::{SessionHealth, SignupProgress};
+use crate::utils::storage::UploadFile;
+use crate::utils::task::InFlight;
+use crate::utils::testnet::{
+    PORTABLE_HOMESERVER_URL, TESTNET_HOMESERVER_KEY, TestnetProbe, probe_local_testnet,
+};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum NetworkMode {
@@ -31,9 +49,16 @@ impl NetworkMode {
             NetworkMode::Testnet => "Testnet",
         }
     }
+
+    pub fn other(self) -> NetworkMode {
+        match self {
+            NetworkMode::Mainnet => NetworkMode::Testnet,
+            NetworkMode::Testnet => NetworkMode::Mainnet,
+        }
+    }
 }
 
-#[derive(Clone, Copy, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum Tab {
     Keys,
     Tokens,
@@ -43,10 +68,11 @@ pub enum Tab {
     Storage,
     Social,
     Http,
+    Settings,
 }
 
 impl Tab {
-    pub const ALL: [Tab; 8] = [
+    pub const ALL: [Tab; 9] = [
         Tab::Keys,
         Tab::Tokens,
         Tab::Sessions,
@@ -55,8 +81,50 @@ impl Tab {
         Tab::Storage,
         Tab::Social,
         Tab::Http,
+        Tab::Settings,
     ];
 
+    /// The tab Ctrl/Cmd plus `digit` selects, counting from 1 in nav order.
+    pub fn from_shortcut(digit: usize) -> Option<Tab> {
+        digit
+            .checked_sub(1)
+            .and_then(|index| Tab::ALL.get(index).copied())
+    }
+
+    /// Tooltip for the tab nav listing every shortcut.
+    pub fn shortcut_summary() -> String {
+        Tab::ALL
+            .iter()
+            .enumerate()
+            .map(|(index, tab)| format!("Ctrl/Cmd+{}: {}", index + 1, tab.label()))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Stable name stored in `prefs.json`.
+    pub fn key(self) -> &'static str {
+        match self {
+            Tab::Keys => "keys",
+            Tab::Tokens => "tokens",
+            Tab::Sessions => "sessions",
+            Tab::Pkdns => "pkdns",
+            Tab::Auth => "auth",
+            Tab::Storage => "storage",
+            Tab::Social => "social",
+            Tab::Http => "http",
+            Tab::Settings => "settings",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<Tab> {
+        Tab::ALL.into_iter().find(|tab| tab.key() == key)
+    }
+
+    /// Tabs whose tools only work with a usable session.
+    pub fn requires_session(self) -> bool {
+        matches!(self, Tab::Social)
+    }
+
     pub fn label(self) -> &'static str {
         match self {
             Tab::Keys => "Keys",
@@ -67,6 +135,7 @@ impl Tab {
             Tab::Storage => "Storage",
             Tab::Social => "Social",
             Tab::Http => "Raw Requests",
+            Tab::Settings => "Settings",
         }
     }
 
@@ -120,78 +189,178 @@ impl Tab {
                     r#"M12 21a9.004 9.004 0 0 0 8.716-6.747M12 21a9.004 9.004 0 0 1-8.716-6.747M12 21c2.485 0 4.5-4.03 4.5-9S14.485 3 12 3m0 18c-2.485 0-4.5-4.03-4.5-9S9.515 3 12 3m0 0a8.997 8.997 0 0 1 7.843 4.582M12 3a8.997 8.997 0 0 0-7.843 4.582m15.686 0A11.953 11.953 0 0 1 12 10.5c-2.998 0-5.74-1.1-7.843-2.918m15.686 0A8.959 8.959 0 0 1 21 12c0 .778-.099 1.533-.284 2.253m0 0A17.919 17.919 0 0 1 12 16.5c-3.162 0-6.133-.815-8.716-2.247m0 0A9.015 9.015 0 0 1 3 12c0-1.605.42-3.113 1.157-4.418"#,
                 ],
             ),
+            Tab::Settings => (
+                "0 0 24 24",
+                &[
+                    r#"M9.594 3.94c.09-.542.56-.94 1.11-.94h2.593c.55 0 1.02.398 1.11.94l.213 1.281c.063.374.313.686.645.87.074.04.147.083.22.127.325.196.72.257 1.075.124l1.217-.456a1.125 1.125 0 0 1 1.37.49l1.296 2.247a1.125 1.125 0 0 1-.26 1.431l-1.003.827c-.293.241-.438.613-.43.992a7.723 7.723 0 0 1 0 .255c-.008.378.137.75.43.991l1.004.827c.424.35.534.955.26 1.43l-1.298 2.247a1.125 1.125 0 0 1-1.369.491l-1.217-.456c-.355-.133-.75-.072-1.076.124a6.47 6.47 0 0 1-.22.128c-.331.183-.581.495-.644.869l-.213 1.281c-.09.543-.56.94-1.11.94h-2.594c-.55 0-1.019-.398-1.11-.94l-.213-1.281c-.062-.374-.312-.686-.644-.87a6.52 6.52 0 0 1-.22-.127c-.325-.196-.72-.257-1.076-.124l-1.217.456a1.125 1.125 0 0 1-1.369-.49l-1.297-2.247a1.125 1.125 0 0 1 .26-1.431l1.004-.827c.292-.24.437-.613.43-.991a6.932 6.932 0 0 1 0-.255c.007-.38-.138-.751-.43-.992l-1.004-.827a1.125 1.125 0 0 1-.26-1.43l1.297-2.247a1.125 1.125 0 0 1 1.37-.491l1.216.456c.356.133.751.072 1.076-.124.072-.044.146-.086.22-.128.332-.183.582-.495.644-.869l.214-1.28Z"#,
+                    r#"M15 12a3 3 0 1 1-6 0 3 3 0 0 1 6 0Z"#,
+                ],
+            ),
         }
     }
 }
 
+/// Forwards Ctrl/Cmd+1..9 to [`App`] as the digit pressed, unless an input,
+/// textarea, select, or editable element has focus.
+const TAB_SHORTCUT_JS: &str = r#"
+document.addEventListener("keydown", (event) => {
+    if (!(event.ctrlKey || event.metaKey) || event.altKey || event.shiftKey) {
+        return;
+    }
+    const target = event.target;
+    if (target && (target.isContentEditable || ["INPUT", "TEXTAREA", "SELECT"].includes(target.tagName))) {
+        return;
+    }
+    if (!/^[1-9]$/.test(event.key)) {
+        return;
+    }
+    event.preventDefault();
+    dioxus.send(Number(event.key));
+});
+"#;
+
 #[allow(non_snake_case, clippy::clone_on_copy)]
 pub fn App() -> Element {
-    let active_tab = use_signal(|| Tab::Keys);
     let network_mode = use_signal(|| NetworkMode::Mainnet);
     let logs_signal = use_signal(Vec::<LogEntry>::new);
     let activity_log = ActivityLog::new(logs_signal.clone());
     let show_logs = use_signal(|| false);
+    let log_filter = use_signal(LogFilter::default);
 
     let pubky_state = use_signal(|| PubkyFacadeState::loading(NetworkMode::Mainnet));
-    let pubky_facade = PubkyFacadeHandle::new(pubky_state.clone());
+    let pubky_facade = PubkyFacadeHandle::new(pubky_state.clone(), use_signal(|| 0));
     let mut pubky_bootstrapped = use_signal(|| false);
+    let prefs = PrefsHandle::new(use_signal(load_prefs));
+    // No session survives a restart, so a session-gated tab falls back to Keys.
+    let active_tab = use_signal(|| prefs.snapshot().launch_tab(false));
+    let initial_defaults = prefs.snapshot().defaults;
+    let launch_defaults = initial_defaults.for_network(NetworkMode::Mainnet).clone();
+    let mainnet_guard = MainnetGuard::new(prefs, network_mode.clone());
+    let restored_inputs = prefs
+        .snapshot()
+        .inputs
+        .restored()
+        .cloned()
+        .unwrap_or_else(|| SavedInputs::from_defaults(&launch_defaults));
+    let input_fields = InputFields {
+        http_method: use_signal(|| restored_inputs.http_method.clone()),
+        http_url: use_signal(|| restored_inputs.http_url.clone()),
+        http_headers: use_signal(|| restored_inputs.http_headers.clone()),
+        storage_path: use_signal(|| restored_inputs.storage_path.clone()),
+        token_capabilities: use_signal(|| restored_inputs.token_capabilities.clone()),
+        auth_capabilities: use_signal(|| restored_inputs.auth_capabilities.clone()),
+        pkdns_lookup: use_signal(|| restored_inputs.pkdns_lookup.clone()),
+        pkdns_host_override: use_signal(|| restored_inputs.pkdns_host_override.clone()),
+        pkdns_batch: use_signal(|| restored_inputs.pkdns_batch.clone()),
+    };
 
     let keypair = use_signal(|| Option::<Keypair>::None);
     let session = use_signal(|| Option::<PubkySession>::None);
     let session_details = use_signal(String::new);
+    let session_health = use_signal(SessionHealth::default);
+    let launch_session = prefs.snapshot().session;
+    let session_homeserver = use_signal(|| launch_session.homeserver.clone());
+    let accounts_loaded = use_hook(|| load_accounts().map_err(|err| format!("{err:#}")));
+    let accounts_load_error = accounts_loaded.as_ref().err().cloned();
+    let account_switcher = AccountSwitcher::new(
+        use_signal(|| accounts_loaded.unwrap_or_default()),
+        use_signal(String::new),
+        keypair.clone(),
+        session.clone(),
+        session_details.clone(),
+        session_homeserver.clone(),
+        use_signal(HashMap::new),
+    );
 
     let keys_state = KeysTabState {
         keypair: keypair.clone(),
         secret_input: use_signal(String::new),
+        mnemonic_input: use_signal(String::new),
         recovery_path: use_signal(String::new),
         recovery_passphrase: use_signal(String::new),
+        secret_visible: use_signal(|| false),
+        prefs,
+        accounts: account_switcher,
+        account_label: use_signal(String::new),
     };
 
     let tokens_state = TokensTabState {
         keypair: keypair.clone(),
-        capabilities: use_signal(|| String::from("/:rw")),
+        capabilities: input_fields.token_capabilities,
+        capability_preset: use_signal(CapabilityPreset::default),
         output: use_signal(String::new),
     };
 
     let sessions_state = SessionsTabState {
         keypair: keypair.clone(),
         session: session.clone(),
+        session_health: session_health.clone(),
         details: session_details.clone(),
-        homeserver: use_signal(String::new),
-        signup_code: use_signal(String::new),
+        homeserver: session_homeserver.clone(),
+        signup_code: use_signal(|| launch_session.signup_code.clone()),
+        signup_progress: use_signal(SignupProgress::default),
+        invite_input: use_signal(String::new),
+        signup_task: InFlight::new(use_signal(|| None)),
+        prefs,
+        network_mode: network_mode.clone(),
     };
 
     let pkdns_state = PkdnsTabState {
         keypair: keypair.clone(),
-        lookup_input: use_signal(String::new),
+        lookup_input: input_fields.pkdns_lookup,
         lookup_result: use_signal(String::new),
-        host_override: use_signal(String::new),
+        host_override: input_fields.pkdns_host_override,
+        packet: use_signal(|| Option::<SignedPacket>::None),
+        packet_dump: use_signal(String::new),
+        packet_path: use_signal(String::new),
+        batch_input: input_fields.pkdns_batch,
+        batch_rows: use_signal(Vec::new),
+        lookup_task: InFlight::new(use_signal(|| None)),
+        batch_task: InFlight::new(use_signal(|| None)),
+        batch_export_path: use_signal(String::new),
+        republish_task: InFlight::new(use_signal(|| None)),
+        republish_minutes: use_signal(|| DEFAULT_REPUBLISH_MINUTES.to_string()),
+        mainnet_guard,
     };
 
     let auth_state = AuthTabState {
         keypair: keypair.clone(),
         session: session.clone(),
         details: session_details.clone(),
-        capabilities: use_signal(|| String::from("/:rw")),
+        capabilities: input_fields.auth_capabilities,
         relay: use_signal(String::new),
         url_output: use_signal(String::new),
         qr_data: use_signal(|| Option::<String>::None),
         status: use_signal(String::new),
         flow: use_signal(|| Option::<PubkyAuthFlow>::None),
         request_body: use_signal(String::new),
+        await_task: InFlight::new(use_signal(|| None)),
+        approval_timeout: use_signal(|| DEFAULT_APPROVAL_TIMEOUT_SECS.to_string()),
+        qr_size: use_signal(QrSize::default),
+        qr_save_path: use_signal(String::new),
     };
 
     let storage_state = StorageTabState {
         session: session.clone(),
-        path: use_signal(|| String::from("/pub/")),
+        path: input_fields.storage_path,
         body: use_signal(String::new),
         response: use_signal(String::new),
+        response_headers: use_signal(Vec::new),
+        range_start: use_signal(String::new),
+        range_end: use_signal(String::new),
+        listing: use_signal(Vec::new),
+        public_owner: use_signal(String::new),
         public_resource: use_signal(String::new),
         public_response: use_signal(String::new),
+        public_headers: use_signal(Vec::new),
+        public_preview: use_signal(|| Option::<String>::None),
+        upload_file: use_signal(|| Option::<UploadFile>::None),
+        mainnet_guard,
     };
 
     let social_state = SocialTabState {
         session: session.clone(),
+        session_health: session_health.clone(),
         profile_name: use_signal(String::new),
         profile_bio: use_signal(String::new),
         profile_image: use_signal(String::new),
@@ -199,31 +368,223 @@ pub fn App() -> Element {
         profile_links: use_signal(String::new),
         profile_error: use_signal(String::new),
         profile_response: use_signal(String::new),
+        profile_view: use_signal(|| None),
+        profile_avatar_failed: use_signal(|| false),
         post_content: use_signal(String::new),
+        post_preview: use_signal(|| false),
         post_kind: use_signal(|| String::from("short")),
         post_parent: use_signal(String::new),
         post_embed_kind: use_signal(String::new),
         post_embed_uri: use_signal(String::new),
         post_attachments: use_signal(String::new),
         post_response: use_signal(String::new),
+        post_load_uri: use_signal(String::new),
         tag_uri: use_signal(String::new),
         tag_label: use_signal(String::new),
         tag_response: use_signal(String::new),
+        bulk_tag_uris: use_signal(String::new),
+        bulk_tag_labels: use_signal(String::new),
+        bulk_tag_rows: use_signal(Vec::new),
+        tag_listing: use_signal(Vec::new),
+        export_path: use_signal(String::new),
+        export_task: InFlight::new(use_signal(|| None)),
+        bulk_tag_task: InFlight::new(use_signal(|| None)),
+        mainnet_guard,
     };
 
     let http_state = HttpTabState {
-        method: use_signal(|| String::from("GET")),
-        url: use_signal(|| String::from("https://")),
-        headers: use_signal(String::new),
+        method: input_fields.http_method,
+        url: input_fields.http_url,
+        headers: input_fields.http_headers,
         body: use_signal(String::new),
         response: use_signal(String::new),
+        response_headers: use_signal(Vec::new),
+        repeat_count: use_signal(|| String::from("10")),
+        send_task: InFlight::new(use_signal(|| None)),
+        repeat_task: InFlight::new(use_signal(|| None)),
+        prefs,
+        history: use_signal(Vec::new),
+        attachment_path: use_signal(String::new),
+        attachment_field: use_signal(|| String::from(DEFAULT_MULTIPART_FIELD)),
     };
 
+    let accounts_error_logs = activity_log.clone();
+    use_effect(move || {
+        if let Some(err) = accounts_load_error.as_ref() {
+            accounts_error_logs.error(format!(
+                "Saved accounts could not be loaded ({err}). The file is moved to accounts.json.bak before anything new is saved."
+            ));
+        }
+    });
+
+    let probe_on_change_session = session.clone();
+    let probe_on_change_health = session_health.clone();
+    use_effect(move || {
+        // Subscribe to session changes; the probe itself only peeks.
+        let _ = probe_on_change_session.read().is_some();
+        probe_session_health(
+            probe_on_change_session.clone(),
+            probe_on_change_health.clone(),
+        );
+    });
+
+    let probe_on_tab_session = session.clone();
+    let probe_on_tab_active = active_tab.clone();
+    let probe_on_tab_health = session_health.clone();
+    use_effect(move || {
+        if probe_on_tab_active.read().requires_session() {
+            probe_session_health(probe_on_tab_session.clone(), probe_on_tab_health.clone());
+        }
+    });
+
+    let republish_pubky = pubky_facade.clone();
+    let republish_keypair = keypair.clone();
+    let republish_override = pkdns_state.host_override;
+    let republish_minutes = pkdns_state.republish_minutes;
+    let republish_task = pkdns_state.republish_task;
+    let republish_logs = activity_log.clone();
+    use_effect(move || {
+        // Subscribe to key changes so a running schedule follows the new key.
+        let _ = republish_keypair.read().is_some();
+        if !republish_task.peek_running() {
+            return;
+        }
+        if let Ok(minutes) = parse_republish_minutes(&republish_minutes.peek()) {
+            start_republish_loop(
+                republish_pubky.clone(),
+                republish_keypair,
+                republish_override,
+                minutes,
+                republish_task,
+                republish_logs.clone(),
+            );
+        }
+    });
+
+    let settings_state = SettingsTabState {
+        prefs,
+        mainnet_capabilities: use_signal(|| initial_defaults.mainnet.capabilities.clone()),
+        mainnet_storage_path: use_signal(|| initial_defaults.mainnet.storage_path.clone()),
+        testnet_capabilities: use_signal(|| initial_defaults.testnet.capabilities.clone()),
+        testnet_storage_path: use_signal(|| initial_defaults.testnet.storage_path.clone()),
+        network_mode: network_mode.clone(),
+        session: session.clone(),
+        session_health: session_health.clone(),
+        log_entries: logs_signal.clone(),
+        diagnostics_path: use_signal(String::new),
+        input_fields,
+        environment_name: use_signal(String::new),
+        environment_variables: use_signal(String::new),
+    };
+
+    let last_tab_logs = activity_log.clone();
+    use_effect(move || {
+        let tab = *active_tab.read();
+        if prefs.snapshot().last_tab.as_deref() != Some(tab.key()) {
+            prefs.update(&last_tab_logs, |prefs| {
+                prefs.last_tab = Some(tab.key().to_string());
+            });
+        }
+    });
+
+    let inputs_logs = activity_log.clone();
+    let inputs_save = InFlight::new(use_signal(|| None));
+    use_effect(move || {
+        let current = input_fields.current();
+        let inputs = prefs.snapshot().inputs;
+        if inputs.enabled && inputs.saved.as_ref() != Some(&current) {
+            // Each edit restarts the wait, so only the settled values are saved.
+            let logs = inputs_logs.clone();
+            inputs_save.start(async move {
+                tokio::time::sleep(INPUTS_SAVE_DELAY).await;
+                prefs.update(&logs, |prefs| prefs.inputs.saved = Some(current));
+            });
+        } else {
+            inputs_save.cancel();
+        }
+    });
+
+    let mut defaults_network = use_signal(|| NetworkMode::Mainnet);
+    let defaults_mode_signal = network_mode.clone();
+    let mut defaults_token_caps = tokens_state.capabilities.clone();
+    let mut defaults_auth_caps = auth_state.capabilities.clone();
+    let mut defaults_storage_path = storage_state.path.clone();
+    use_effect(move || {
+        let mode = *defaults_mode_signal.read();
+        let previous_mode = *defaults_network.peek();
+        if mode == previous_mode {
+            return;
+        }
+        defaults_network.set(mode);
+
+        let defaults = prefs.snapshot().defaults;
+        let previous = defaults.for_network(previous_mode);
+        let next = defaults.for_network(mode);
+        for (field, old, new) in [
+            (
+                &mut defaults_token_caps,
+                &previous.capabilities,
+                &next.capabilities,
+            ),
+            (
+                &mut defaults_auth_caps,
+                &previous.capabilities,
+                &next.capabilities,
+            ),
+            (
+                &mut defaults_storage_path,
+                &previous.storage_path,
+                &next.storage_path,
+            ),
+        ] {
+            // Only replace values the user has not edited away from the old default.
+            if field.peek().trim() == old.trim() {
+                field.set(new.clone());
+            }
+        }
+    });
+
+    let testnet_probe = use_signal(TestnetProbe::default);
+    let probe_network = network_mode.clone();
+    use_effect(move || {
+        if *probe_network.read() == NetworkMode::Testnet {
+            queue_testnet_probe(testnet_probe, probe_network);
+        } else {
+            let mut probe = testnet_probe;
+            probe.set(TestnetProbe::Idle);
+        }
+    });
+
+    let shortcut_tab = active_tab.clone();
+    let shortcut_session = session.clone();
+    let shortcut_health = session_health.clone();
+    let shortcut_logs = activity_log.clone();
+    use_future(move || {
+        let logs = shortcut_logs.clone();
+        let mut active = shortcut_tab;
+        async move {
+            let mut listener = document::eval(TAB_SHORTCUT_JS);
+            while let Ok(digit) = listener.recv::<usize>().await {
+                let Some(tab) = Tab::from_shortcut(digit) else {
+                    continue;
+                };
+                if tab.requires_session() {
+                    let has_session = shortcut_session.peek().is_some();
+                    if let Some(reason) = shortcut_health.peek().locked_reason(has_session) {
+                        logs.info(format!("{} is unavailable: {reason}", tab.label()));
+                        continue;
+                    }
+                }
+                active.set(tab);
+            }
+        }
+    });
+
     let mut session_homeserver_prefill = sessions_state.homeserver.clone();
     let network_signal_for_prefill = network_mode.clone();
     use_effect(move || {
         if *network_signal_for_prefill.read() == NetworkMode::Testnet {
-            session_homeserver_prefill.set(String::from(TESTNET_DEFAULT_SESSION_HOMESERVER));
+            session_homeserver_prefill.set(String::from(TESTNET_HOMESERVER_KEY));
         }
     });
 
@@ -235,10 +596,14 @@ pub fn App() -> Element {
             network_mode.clone(),
             initial_network,
             true,
+            prefs,
+            activity_log.clone(),
         );
     }
 
     let pubky_state_snapshot = pubky_facade.snapshot();
+    let has_session = session.read().is_some();
+    let session_health_snapshot = *session_health.read();
     let retry_network = pubky_state_snapshot.network;
 
     let show_logs_value = *show_logs.read();
@@ -248,9 +613,44 @@ pub fn App() -> Element {
         "Show activity"
     };
     let has_logs = !logs_signal.read().is_empty();
+    let log_filter_value = *log_filter.read();
+    let visible_logs: Vec<LogEntry> = logs_signal
+        .read()
+        .iter()
+        .filter(|entry| log_filter_value.allows(entry))
+        .cloned()
+        .collect();
+    let hidden_log_count = logs_signal.read().len() - visible_logs.len();
+    let mut log_filter_toggle = log_filter;
     let mut toggle_logs_signal = show_logs.clone();
+    let copy_log_entries = logs_signal.clone();
+    let copy_log = activity_log.clone();
+    let save_log_entries = logs_signal.clone();
+    let save_log_logs = activity_log.clone();
     let retry_handle = pubky_facade.clone();
     let retry_signal = network_mode.clone();
+    let retry_logs = activity_log.clone();
+    let switch_network = retry_network.other();
+    let switch_handle = pubky_facade.clone();
+    let mut switch_signal = network_mode.clone();
+    let switch_build_logs = activity_log.clone();
+    let switch_tooltip = format!(
+        "Switch to {} and initialize Pubky there instead",
+        switch_network.label()
+    );
+
+    let saved_accounts = account_switcher.accounts.read().accounts.clone();
+    let active_account = account_switcher.active_key();
+    let active_is_saved = saved_accounts
+        .iter()
+        .any(|account| Some(&account.public_key) == active_account.as_ref());
+    let switch_logs = activity_log.clone();
+
+    let testnet_probe_snapshot = testnet_probe.read().clone();
+    let testnet_hint = testnet_probe_snapshot.summary();
+    let testnet_checking = testnet_probe_snapshot.is_checking();
+    let recheck_network = network_mode.clone();
+    let testnet_link_logs = activity_log.clone();
 
     rsx! {
         style { {APP_STYLE} }
@@ -269,6 +669,22 @@ pub fn App() -> Element {
                     p { class: "subtitle", "A tidy cockpit for every Pubky homeserver workflow." }
                 }
                 div { class: "header-controls",
+                    if !saved_accounts.is_empty() {
+                        select {
+                            class: "account-switcher",
+                            title: "Switch the active key across every tab",
+                            "data-touch-tooltip": touch_tooltip("Switch the active key across every tab"),
+                            onchange: move |evt| account_switcher.switch_to(&evt.value(), &switch_logs),
+                            option { value: "", disabled: true, selected: !active_is_saved, "Switch account…" }
+                            for account in saved_accounts {
+                                option {
+                                    value: account.public_key.clone(),
+                                    selected: Some(&account.public_key) == active_account.as_ref(),
+                                    "{account.label}"
+                                }
+                            }
+                        }
+                    }
                     div { class: "network-toggle",
                         for mode in NetworkMode::ALL {
                             NetworkToggleOption {
@@ -277,12 +693,15 @@ pub fn App() -> Element {
                                 on_select: {
                                     let toggle_handle = pubky_facade.clone();
                                     let toggle_network = network_mode.clone();
+                                    let toggle_logs = activity_log.clone();
                                     move |selected| {
                                         queue_pubky_build(
                                             toggle_handle.clone(),
                                             toggle_network.clone(),
                                             selected,
                                             false,
+                                            prefs,
+                                            toggle_logs.clone(),
                                         );
                                     }
                                 }
@@ -291,10 +710,51 @@ pub fn App() -> Element {
                     }
                 }
             }
+            if let Some(summary) = testnet_hint {
+                div { class: "testnet-hint", role: "alert",
+                    strong { "No local testnet is running." }
+                    p {
+                        "Testnet mode talks to services on this machine. {summary} Start a local testnet with Portable Homeserver: open it, pick Testnet, and press Start server. Then recheck."
+                    }
+                    div { class: "small-buttons",
+                        button {
+                            class: "action",
+                            disabled: testnet_checking,
+                            title: "Check the local testnet ports again",
+                            "data-touch-tooltip": touch_tooltip("Check the local testnet ports again"),
+                            onclick: move |_| queue_testnet_probe(testnet_probe, recheck_network),
+                            if testnet_checking { "Checking…" } else { "Recheck" }
+                        }
+                        button {
+                            class: "action secondary",
+                            title: "Open the Portable Homeserver instructions in your browser",
+                            "data-touch-tooltip": touch_tooltip(
+                                "Open the Portable Homeserver instructions in your browser",
+                            ),
+                            onclick: move |_| {
+                                if let Err(err) = open_external_link(PORTABLE_HOMESERVER_URL) {
+                                    testnet_link_logs.error(format!(
+                                        "Failed to open {PORTABLE_HOMESERVER_URL}: {err}"
+                                    ));
+                                }
+                            },
+                            "Start a local testnet with Portable Homeserver"
+                        }
+                    }
+                }
+            }
             main {
-                nav { class: "tabs",
+                nav { class: "tabs", title: Tab::shortcut_summary(),
                     for tab in Tab::ALL.iter().copied() {
-                        TabButton { tab, active_tab: active_tab.clone() }
+                        TabButton {
+                            tab,
+                            active_tab: active_tab.clone(),
+                            locked_reason: if tab.requires_session() {
+                                session_health_snapshot.locked_reason(has_session).map(String::from)
+                            } else {
+                                None
+                            },
+                        }
                     }
                 }
                 div { class: "panel",
@@ -331,6 +791,11 @@ pub fn App() -> Element {
                             http_state.clone(),
                             activity_log.clone(),
                         ),
+                        Tab::Settings => render_settings_tab(
+                            pubky_facade.clone(),
+                            settings_state.clone(),
+                            activity_log.clone(),
+                        ),
                     }
                 }
             }
@@ -363,10 +828,29 @@ pub fn App() -> Element {
                                         retry_signal.clone(),
                                         retry_network,
                                         true,
+                                        prefs,
+                                        retry_logs.clone(),
                                     );
                                 },
                                 "Retry"
                             }
+                            button {
+                                class: "action secondary",
+                                title: switch_tooltip.clone(),
+                                "data-touch-tooltip": touch_tooltip(switch_tooltip),
+                                onclick: move |_| {
+                                    switch_signal.set(switch_network);
+                                    queue_pubky_build(
+                                        switch_handle.clone(),
+                                        switch_signal.clone(),
+                                        switch_network,
+                                        true,
+                                        prefs,
+                                        switch_build_logs.clone(),
+                                    );
+                                },
+                                "Switch to {switch_network.label()} and retry"
+                            }
                         }
                     }
                 }
@@ -386,17 +870,91 @@ pub fn App() -> Element {
                 }
                 if show_logs_value {
                     div { class: "logs-panel",
-                        h3 { "Activity" }
+                        div { class: "logs-panel-header",
+                            h3 { "Activity" }
+                            div { class: "small-buttons",
+                                button {
+                                    class: "action secondary",
+                                    disabled: !has_logs,
+                                    title: "Copy the whole log as timestamped plain text",
+                                    "data-touch-tooltip": touch_tooltip(
+                                        "Copy the whole log as timestamped plain text",
+                                    ),
+                                    onclick: move |_| {
+                                        let entries = copy_log_entries.read().clone();
+                                        copy_to_clipboard(&format_log(&entries));
+                                        copy_log.success(format!(
+                                            "Copied {} log entries to the clipboard",
+                                            entries.len()
+                                        ));
+                                    },
+                                    "Copy log"
+                                }
+                                button {
+                                    class: "action secondary",
+                                    disabled: !has_logs,
+                                    title: "Save the whole log to a text file",
+                                    "data-touch-tooltip": touch_tooltip(
+                                        "Save the whole log to a text file",
+                                    ),
+                                    onclick: move |_| {
+                                        let path = match file_dialog::save_file() {
+                                            FileDialogResult::Selected(path) => path,
+                                            FileDialogResult::Unavailable => {
+                                                save_log_logs.info(
+                                                    "No save dialog on this platform. Use Copy log, or Generate diagnostics in Settings, which includes the log.",
+                                                );
+                                                return;
+                                            }
+                                            FileDialogResult::Cancelled => return,
+                                        };
+                                        let entries = save_log_entries.read().clone();
+                                        match save_log(&entries, &path) {
+                                            Ok(()) => save_log_logs.success(format!(
+                                                "Saved {} log entries to {}",
+                                                entries.len(),
+                                                path.display()
+                                            )),
+                                            Err(err) => save_log_logs.error(format!(
+                                                "Failed to save the log: {err:#}"
+                                            )),
+                                        }
+                                    },
+                                    "Save log"
+                                }
+                            }
+                        }
+                        div { class: "log-filters",
+                            for level in LogLevel::ALL {
+                                label {
+                                    class: "log-filter",
+                                    title: "Show or hide {level.label()} entries",
+                                    input {
+                                        r#type: "checkbox",
+                                        checked: log_filter_value.shows(level),
+                                        onchange: move |_| {
+                                            let next = log_filter_toggle.read().toggled(level);
+                                            log_filter_toggle.set(next);
+                                        },
+                                    }
+                                    "{level.label()}"
+                                }
+                            }
+                        }
                         div {
                             class: "log-scroll",
                             role: "log",
                             aria_live: "polite",
-                            if has_logs {
-                                for entry in logs_signal.read().iter() {
-                                    div { class: format_args!("log-entry {}", entry.class()), "{entry.message()}" }
+                            if !has_logs {
+                                div { class: "log-entry log-info", "No activity yet. Trigger any action to see logs here." }
+                            } else if visible_logs.is_empty() {
+                                div { class: "log-entry log-info",
+                                    "All {hidden_log_count} entries are hidden by the level filter. Tick a level above to show them."
                                 }
                             } else {
-                                div { class: "log-entry log-info", "No activity yet. Trigger any action to see logs here." }
+                                for entry in visible_logs.iter() {
+                                    div { class: format_args!("log-entry {}", entry.class()), "{entry.message()}" }
+                                }
                             }
                         }
                     }
@@ -406,11 +964,49 @@ pub fn App() -> Element {
     }
 }
 
+/// Check whether the local testnet services answer. Results that arrive after
+/// switching back to Mainnet are dropped.
+fn queue_testnet_probe(mut probe: Signal<TestnetProbe>, network_signal: Signal<NetworkMode>) {
+    probe.set(TestnetProbe::Checking);
+    spawn(async move {
+        let result = probe_local_testnet().await;
+        if *network_signal.peek() == NetworkMode::Testnet {
+            probe.set(result);
+        }
+    });
+}
+
+/// Revalidate the current session against its homeserver and record whether it
+/// is still usable. Results for a session that was replaced mid-probe are dropped.
+fn probe_session_health(session: Signal<Option<PubkySession>>, mut health: Signal<SessionHealth>) {
+    let Some(current) = session.peek().as_ref().cloned() else {
+        health.set(SessionHealth::Missing);
+        return;
+    };
+    let probed_key = current.info().public_key().clone();
+    health.set(SessionHealth::Checking);
+    spawn(async move {
+        let result = current.revalidate().await;
+        let still_current = session
+            .peek()
+            .as_ref()
+            .is_some_and(|active| *active.info().public_key() == probed_key);
+        if still_current {
+            health.set(SessionHealth::from_probe(&result));
+        }
+    });
+}
+
+/// Build the facade for `target`. With automatic retries enabled, a failed
+/// build is retried with backoff before the error overlay appears; starting
+/// another build, such as a network switch, stops them.
 fn queue_pubky_build(
     pubky_handle: PubkyFacadeHandle,
     network_signal: Signal<NetworkMode>,
     target: NetworkMode,
     force: bool,
+    prefs: PrefsHandle,
+    logs: ActivityLog,
 ) {
     if !force {
         let current = pubky_handle.snapshot();
@@ -422,20 +1018,37 @@ fn queue_pubky_build(
         }
     }
 
-    pubky_handle.set(PubkyFacadeState::loading(target));
+    let build = pubky_handle.begin_build(target);
+    let auto_retry = prefs.snapshot().auto_retry_facade;
 
     let handle = pubky_handle.clone();
     spawn(async move {
-        match crate::utils::pubky::build_pubky_facade(target).await {
-            Ok(pubky) => {
-                if *network_signal.read() == target {
-                    handle.set(PubkyFacadeState::ready(target, pubky));
-                }
+        let mut attempt = 0;
+        loop {
+            let outcome = crate::utils::pubky::build_pubky_facade(target).await;
+            if !handle.is_current_build(build) || *network_signal.read() != target {
+                return;
             }
-            Err(err) => {
-                if *network_signal.read() == target {
-                    handle.set(PubkyFacadeState::error(target, err.to_string()));
+            let err = match outcome {
+                Ok(pubky) => {
+                    handle.set(PubkyFacadeState::ready(target, pubky));
+                    return;
                 }
+                Err(err) => err,
+            };
+            attempt += 1;
+            let Some(delay) = facade_retry_delay(attempt).filter(|_| auto_retry) else {
+                handle.set(PubkyFacadeState::error(target, err.to_string()));
+                return;
+            };
+            logs.info(format!(
+                "Pubky client for {} failed: {err}. Retrying in {}s ({attempt}/{MAX_FACADE_RETRIES})",
+                target.label(),
+                delay.as_secs()
+            ));
+            tokio::time::sleep(delay).await;
+            if !handle.is_current_build(build) {
+                return;
             }
         }
     });
@@ -1,7 +1,25 @@
 use dioxus::prelude::*;
+use std::time::Duration;
 
 use crate::app::{NetworkMode, Tab};
+use crate::utils::clipboard::copy_to_clipboard;
+use crate::utils::encoding::{decode_blob, find_encoded_blobs};
+use crate::utils::http::{HeaderRow, ResponseSections, filter_header_rows};
 use crate::utils::mobile::touch_tooltip;
+use crate::utils::qr::generate_capped_qr_data_url;
+
+/// Reads the text selected in the focused textarea or input, falling back to
+/// the page selection.
+const READ_SELECTION_JS: &str = r#"
+const el = document.activeElement;
+if (el && (el.tagName === "TEXTAREA" || el.tagName === "INPUT") && el.selectionStart !== el.selectionEnd) {
+    return el.value.substring(el.selectionStart, el.selectionEnd);
+}
+return window.getSelection ? window.getSelection().toString() : "";
+"#;
+
+/// How long a [`ConfirmButton`] waits for the second click before disarming.
+const CONFIRM_WINDOW: Duration = Duration::from_secs(4);
 
 #[component]
 pub fn NetworkToggleOption(
@@ -41,16 +59,24 @@ pub fn NetworkToggleOption(
 }
 
 #[component]
-pub fn TabButton(tab: Tab, active_tab: Signal<Tab>) -> Element {
+pub fn TabButton(tab: Tab, active_tab: Signal<Tab>, locked_reason: Option<String>) -> Element {
     let is_active = *active_tab.read() == tab;
     let mut setter = active_tab;
-    let class_name = if is_active { "action active" } else { "action" };
+    let class_name = match (is_active, locked_reason.is_some()) {
+        (true, true) => "action active locked",
+        (true, false) => "action active",
+        (false, true) => "action locked",
+        (false, false) => "action",
+    };
     let tab_label = tab.label();
     let (view_box, paths) = tab.icon();
-    let tab_tooltip = format!(
-        "Show the {} toolbox for exploring that part of Pubky",
-        tab.label()
-    );
+    let tab_tooltip = match locked_reason {
+        Some(reason) => format!("{} is unavailable: {reason}", tab.label()),
+        None => format!(
+            "Show the {} toolbox for exploring that part of Pubky",
+            tab.label()
+        ),
+    };
     rsx! {
         button {
             class: class_name,
@@ -77,3 +103,293 @@ pub fn TabButton(tab: Tab, active_tab: Signal<Tab>) -> Element {
         }
     }
 }
+
+/// "Copy body / headers / all" actions for a response rendered by
+/// `format_response_parts`. Body and headers are only offered when the output
+/// can be split into sections. "Decode" turns a selected base64 or hex value
+/// into text, or lists the encoded values found in the body when nothing is
+/// selected.
+#[component]
+pub fn ResponseCopyButtons(response: String) -> Element {
+    let mut copied = use_signal(|| Option::<&'static str>::None);
+    let mut decoded = use_signal(Vec::<Result<String, String>>::new);
+    let sections = ResponseSections::parse(&response);
+    let body = sections.as_ref().map(|sections| sections.body.to_string());
+    let head = sections.as_ref().map(|sections| sections.head.to_string());
+    let all = response.clone();
+    let scan_text = body.clone().unwrap_or_else(|| response.clone());
+    let decoded_lines: Vec<(&'static str, String)> = decoded
+        .read()
+        .iter()
+        .map(|line| match line {
+            Ok(text) => ("mono", text.clone()),
+            Err(message) => ("decoded-error", message.clone()),
+        })
+        .collect();
+
+    rsx! {
+        div { class: "small-buttons copy-buttons",
+            if let Some(body) = body {
+                button {
+                    class: "action secondary",
+                    title: "Copy only the response body",
+                    "data-touch-tooltip": touch_tooltip("Copy only the response body"),
+                    onclick: move |_| {
+                        copy_to_clipboard(&body);
+                        copied.set(Some("Copied body"));
+                    },
+                    "Copy body"
+                }
+            }
+            if let Some(head) = head {
+                button {
+                    class: "action secondary",
+                    title: "Copy the status line and headers",
+                    "data-touch-tooltip": touch_tooltip("Copy the status line and headers"),
+                    onclick: move |_| {
+                        copy_to_clipboard(&head);
+                        copied.set(Some("Copied headers"));
+                    },
+                    "Copy headers"
+                }
+            }
+            button {
+                class: "action secondary",
+                title: "Copy the full formatted response",
+                "data-touch-tooltip": touch_tooltip("Copy the full formatted response"),
+                onclick: move |_| {
+                    copy_to_clipboard(&all);
+                    copied.set(Some("Copied response"));
+                },
+                "Copy all"
+            }
+            button {
+                class: "action secondary",
+                title: "Decode the selected base64 or hex text, or find encoded values in the body",
+                "data-touch-tooltip": touch_tooltip(
+                    "Decode the selected base64 or hex text, or find encoded values in the body",
+                ),
+                // Keep focus (and the selection) in the response view.
+                onmousedown: move |evt| evt.prevent_default(),
+                onclick: move |_| {
+                    let scan_text = scan_text.clone();
+                    spawn(async move {
+                        let selection = document::eval(READ_SELECTION_JS)
+                            .join::<String>()
+                            .await
+                            .unwrap_or_default();
+                        let results = if selection.trim().is_empty() {
+                            let blobs = find_encoded_blobs(&scan_text);
+                            if blobs.is_empty() {
+                                vec![Err(String::from(
+                                    "No base64 or hex values found; select one to decode it",
+                                ))]
+                            } else {
+                                blobs
+                                    .into_iter()
+                                    .map(|(_, blob)| Ok(blob.describe()))
+                                    .collect()
+                            }
+                        } else {
+                            vec![decode_blob(&selection).map(|blob| blob.describe())]
+                        };
+                        decoded.set(results);
+                    });
+                },
+                "Decode"
+            }
+            if let Some(message) = *copied.read() {
+                span { class: "copy-status", "{message}" }
+            }
+        }
+        if !decoded_lines.is_empty() {
+            div { class: "decoded-values",
+                for (index, (class, text)) in decoded_lines.into_iter().enumerate() {
+                    p { key: "{index}", class, "{text}" }
+                }
+                button {
+                    class: "action secondary",
+                    onclick: move |_| decoded.set(Vec::new()),
+                    "Dismiss"
+                }
+            }
+        }
+    }
+}
+
+/// Optional structured view of response headers with search, sorting by
+/// name, and per-value copy. The raw response text stays the default view.
+#[component]
+pub fn HeaderTable(headers: Vec<HeaderRow>) -> Element {
+    let mut open = use_signal(|| false);
+    let mut query = use_signal(String::new);
+    let mut descending = use_signal(|| false);
+    let mut copied = use_signal(|| Option::<String>::None);
+
+    if headers.is_empty() {
+        return rsx! {};
+    }
+
+    let is_open = *open.read();
+    let toggle_label = if is_open {
+        "Hide header table"
+    } else {
+        "Show header table"
+    };
+    let query_value = query.read().clone();
+    let is_descending = *descending.read();
+    let sort_label = if is_descending {
+        "Name ↓"
+    } else {
+        "Name ↑"
+    };
+    let rows = filter_header_rows(&headers, &query_value, is_descending);
+    let copied_name = copied.read().clone();
+
+    rsx! {
+        div { class: "header-table",
+            div { class: "small-buttons",
+                button {
+                    class: "action secondary",
+                    title: "Switch between the raw headers and a searchable table",
+                    "data-touch-tooltip": touch_tooltip(
+                        "Switch between the raw headers and a searchable table",
+                    ),
+                    onclick: move |_| open.set(!is_open),
+                    "{toggle_label}"
+                }
+            }
+            if is_open {
+                input {
+                    value: query_value,
+                    oninput: move |evt| query.set(evt.value()),
+                    placeholder: "Filter by header name or value",
+                }
+                table {
+                    thead {
+                        tr {
+                            th {
+                                button {
+                                    class: "sort-toggle",
+                                    title: "Sort headers by name",
+                                    onclick: move |_| descending.set(!is_descending),
+                                    "{sort_label}"
+                                }
+                            }
+                            th { "Value" }
+                            th {}
+                        }
+                    }
+                    tbody {
+                        for row in rows {
+                            tr {
+                                td { class: "mono", "{row.name}" }
+                                td { class: "mono header-value", "{row.value}" }
+                                td {
+                                    button {
+                                        class: "action secondary",
+                                        title: "Copy this header value",
+                                        onclick: {
+                                            let value = row.value.clone();
+                                            let name = row.name.clone();
+                                            move |_| {
+                                                copy_to_clipboard(&value);
+                                                copied.set(Some(name.clone()));
+                                            }
+                                        },
+                                        "Copy"
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+                if let Some(name) = copied_name {
+                    span { class: "copy-status", "Copied {name}" }
+                }
+            }
+        }
+    }
+}
+
+/// "Show QR" toggle for a copyable value, so it can be scanned from a phone.
+#[component]
+pub fn QrToggle(value: String) -> Element {
+    let mut open = use_signal(|| false);
+    let is_open = *open.read();
+    let qr_view = if !is_open {
+        rsx! {}
+    } else {
+        match generate_capped_qr_data_url(&value) {
+            Ok(src) => rsx! {
+                img { class: "qr-inline", src, alt: "QR code for this value" }
+            },
+            Err(err) => rsx! {
+                p { class: "helper-text", "{err}" }
+            },
+        }
+    };
+    let label = if is_open { "Hide QR" } else { "Show QR" };
+
+    rsx! {
+        div { class: "qr-toggle",
+            button {
+                class: "action secondary",
+                title: "Show this value as a QR code",
+                "data-touch-tooltip": touch_tooltip("Show this value as a QR code"),
+                onclick: move |_| open.set(!is_open),
+                "{label}"
+            }
+            {qr_view}
+        }
+    }
+}
+
+/// Button that needs a second click within [`CONFIRM_WINDOW`] before
+/// `on_confirm` runs. The first click swaps the label to `confirm_label`.
+#[component]
+pub fn ConfirmButton(
+    #[props(into)] label: String,
+    #[props(into)] title: String,
+    on_confirm: EventHandler<()>,
+    #[props(into, default = String::from("Confirm?"))] confirm_label: String,
+    #[props(into, default = String::from("action secondary"))] class: String,
+    #[props(default)] danger: bool,
+    #[props(default)] disabled: bool,
+) -> Element {
+    let mut armed = use_signal(|| false);
+    let mut generation = use_signal(|| 0_u64);
+    let is_armed = *armed.read();
+    let class_name = match (is_armed, danger) {
+        (true, _) => format!("{class} danger armed"),
+        (false, true) => format!("{class} danger"),
+        (false, false) => class.clone(),
+    };
+    let text = if is_armed { confirm_label } else { label };
+
+    rsx! {
+        button {
+            class: class_name,
+            disabled,
+            title: title.clone(),
+            "data-touch-tooltip": touch_tooltip(title),
+            onclick: move |_| {
+                if *armed.peek() {
+                    armed.set(false);
+                    on_confirm.call(());
+                    return;
+                }
+                armed.set(true);
+                let ticket = *generation.peek() + 1;
+                generation.set(ticket);
+                spawn(async move {
+                    tokio::time::sleep(CONFIRM_WINDOW).await;
+                    if *generation.peek() == ticket {
+                        armed.set(false);
+                    }
+                });
+            },
+            "{text}"
+        }
+    }
+}
@@ -1,15 +1,17 @@
-use anyhow::{Context, anyhow};
+use anyhow::anyhow;
 use dioxus::events::MouseData;
 use dioxus::prelude::*;
 use pubky::{Capabilities, PubkyAuthFlow};
-use url::Url;
 
 use crate::tabs::{AuthTabState, format_session_info};
-use crate::utils::links::open_pubkyauth_link;
+use crate::utils::clipboard::read_clipboard;
+use crate::utils::file_dialog::{self, FileDialogResult};
+use crate::utils::links::{is_pubkyauth_url, open_pubkyauth_link};
 use crate::utils::logging::ActivityLog;
 use crate::utils::mobile::{is_android_touch, touch_copy_option, touch_tooltip};
 use crate::utils::pubky::PubkyFacadeHandle;
-use crate::utils::qr::generate_qr_data_url;
+use crate::utils::qr::{QrSize, generate_sized_qr_data_url, save_qr_svg};
+use crate::utils::relay::{normalize_relay_url, parse_approval_timeout};
 
 fn open_link_handler(logs: ActivityLog, link: String) -> impl FnMut(Event<MouseData>) + 'static {
     move |_| {
@@ -42,10 +44,22 @@ pub fn render_auth_tab(
         status,
         flow,
         request_body,
+        await_task,
+        approval_timeout,
+        qr_size,
+        qr_save_path,
     } = state;
 
     let caps_value = { capabilities.read().clone() };
     let relay_value = { relay.read().clone() };
+    let relay_preview = if relay_value.trim().is_empty() {
+        None
+    } else {
+        Some(match normalize_relay_url(&relay_value) {
+            Ok(url) => format!("Effective relay: {url}"),
+            Err(err) => format!("Invalid relay: {err}"),
+        })
+    };
     let url_value = { url_output.read().clone() };
     let copyable_url = if url_value.trim().is_empty() {
         None
@@ -61,16 +75,33 @@ pub fn render_auth_tab(
     let link_tooltip = "Share this link with someone to request delegated capabilities";
     let qr_value = { qr_data.read().clone() };
     let request_value = { request_body.read().clone() };
+    let timeout_value = { approval_timeout.read().clone() };
+    let awaiting = await_task.is_running();
+    let size_value = *qr_size.read();
+    let qr_save_path_value = { qr_save_path.read().clone() };
 
     let mut caps_binding = capabilities.clone();
     let mut relay_binding = relay.clone();
     let mut request_binding = request_body.clone();
+    let mut timeout_binding = approval_timeout.clone();
+    let mut qr_size_binding = qr_size.clone();
+    let mut qr_save_path_binding = qr_save_path.clone();
+
+    let resize_url_signal = url_output.clone();
+    let mut resize_qr_signal = qr_data.clone();
+    let resize_logs = logs.clone();
+
+    let save_url_signal = url_output.clone();
+    let save_size_signal = qr_size.clone();
+    let save_path_signal = qr_save_path.clone();
+    let save_logs = logs.clone();
 
     let start_caps_signal = capabilities.clone();
     let start_relay_signal = relay.clone();
     let start_flow_signal = flow.clone();
     let start_url_signal = url_output.clone();
     let start_qr_signal = qr_data.clone();
+    let start_size_signal = qr_size.clone();
     let start_status_signal = status.clone();
     let start_logs = logs.clone();
 
@@ -80,6 +111,7 @@ pub fn render_auth_tab(
     let await_qr_signal = qr_data.clone();
     let await_session_signal = session.clone();
     let await_details_signal = details.clone();
+    let await_timeout_signal = approval_timeout.clone();
     let await_logs = logs.clone();
 
     let mut cancel_flow_signal = flow.clone();
@@ -95,6 +127,10 @@ pub fn render_auth_tab(
     let approve_request_signal = request_body.clone();
     let approve_logs = logs.clone();
 
+    let show_paste = is_android_touch();
+    let paste_request_signal = request_body.clone();
+    let paste_logs = logs.clone();
+
     rsx! {
         div { class: "tab-body",
             section { class: "card span-2",
@@ -125,6 +161,65 @@ pub fn render_auth_tab(
                             placeholder: "https://your-relay.example/link/"
                         }
                     }
+                    label {
+                        "Approval timeout (seconds)"
+                        input {
+                            r#type: "number",
+                            min: "1",
+                            value: timeout_value,
+                            oninput: move |evt| timeout_binding.set(evt.value()),
+                            title: "How long Await approval waits before giving up",
+                            "data-touch-tooltip": touch_tooltip(
+                                "How long Await approval waits before giving up",
+                            ),
+                        }
+                    }
+                    label {
+                        "QR size"
+                        select {
+                            value: size_value.key(),
+                            title: "Pick a larger code for projectors and high-DPI screens",
+                            "data-touch-tooltip": touch_tooltip(
+                                "Pick a larger code for projectors and high-DPI screens",
+                            ),
+                            onchange: move |evt| {
+                                let Some(selected) = QrSize::from_key(&evt.value()) else {
+                                    return;
+                                };
+                                qr_size_binding.set(selected);
+                                let auth_url = resize_url_signal.read().clone();
+                                if auth_url.trim().is_empty() {
+                                    return;
+                                }
+                                match generate_sized_qr_data_url(&auth_url, selected) {
+                                    Ok(data_url) => resize_qr_signal.set(Some(data_url)),
+                                    Err(err) => resize_logs.error(format!("Failed to render QR code: {err}")),
+                                }
+                            },
+                            for option in QrSize::ALL {
+                                option {
+                                    value: option.key(),
+                                    selected: option == size_value,
+                                    {option.label()}
+                                }
+                            }
+                        }
+                    }
+                    label {
+                        "QR file path"
+                        input {
+                            value: qr_save_path_value,
+                            oninput: move |evt| qr_save_path_binding.set(evt.value()),
+                            placeholder: "Leave blank to choose with a file dialog",
+                            title: "Where Save QR writes the code as an SVG image",
+                            "data-touch-tooltip": touch_tooltip(
+                                "Where Save QR writes the code as an SVG image",
+                            ),
+                        }
+                    }
+                }
+                if let Some(preview) = relay_preview {
+                    p { class: "helper-text mono", {preview} }
                 }
                 div { class: "small-buttons",
                     button {
@@ -146,6 +241,7 @@ pub fn render_auth_tab(
                         let mut flow_slot = start_flow_signal.clone();
                         let mut url_slot = start_url_signal.clone();
                         let mut qr_slot = start_qr_signal.clone();
+                        let qr_size = *start_size_signal.read();
                         let mut status_slot = start_status_signal.clone();
                         let logs_task = start_logs.clone();
                         spawn(async move {
@@ -155,15 +251,14 @@ pub fn render_auth_tab(
                                 let flow = if relay_text.trim().is_empty() {
                                     pubky.start_auth_flow(&capabilities)?
                                 } else {
-                                    let relay = Url::parse(relay_text.trim())
-                                        .context("Relay URL must be valid")?;
+                                    let relay = normalize_relay_url(&relay_text)?;
                                     PubkyAuthFlow::builder(&capabilities)
                                         .client(pubky.client().clone())
                                         .relay(relay)
                                         .start()?
                                 };
                                 let auth_url = flow.authorization_url().to_string();
-                                let data_url = generate_qr_data_url(&auth_url)?;
+                                let data_url = generate_sized_qr_data_url(&auth_url, qr_size)?;
                                 flow_slot.set(Some(flow));
                                 url_slot.set(auth_url.clone());
                                 qr_slot.set(Some(data_url));
@@ -190,21 +285,42 @@ pub fn render_auth_tab(
                         "data-touch-tooltip": touch_tooltip(
                             "Wait for the other party to approve and retrieve the resulting session",
                         ),
+                        disabled: awaiting,
                         onclick: move |_| {
+                        let timeout = match parse_approval_timeout(&await_timeout_signal.read()) {
+                            Ok(timeout) => timeout,
+                            Err(err) => {
+                                await_logs.error(format!("Invalid approval timeout: {err}"));
+                                return;
+                            }
+                        };
                         let maybe_flow = {
                             let mut guard = await_flow_signal.write();
                             guard.take()
                         };
                         if let Some(flow) = maybe_flow {
-                            await_status_signal.set(String::from("Waiting for remote approval..."));
+                            await_status_signal.set(format!(
+                                "Waiting up to {}s for remote approval...",
+                                timeout.as_secs()
+                            ));
                             let mut url_slot = await_url_signal.clone();
                             let mut qr_slot = await_qr_signal.clone();
                             let mut status_slot = await_status_signal.clone();
                             let mut session_slot = await_session_signal.clone();
                             let mut details_slot = await_details_signal.clone();
                             let logs_task = await_logs.clone();
-                            spawn(async move {
-                                match flow.await_approval().await {
+                            await_task.start(async move {
+                                let Ok(outcome) = tokio::time::timeout(timeout, flow.await_approval()).await else {
+                                    status_slot.set(String::from("Request timed out. Start a new auth flow to try again."));
+                                    url_slot.set(String::new());
+                                    qr_slot.set(None);
+                                    logs_task.error(format!(
+                                        "Auth request timed out after {}s without approval",
+                                        timeout.as_secs()
+                                    ));
+                                    return;
+                                };
+                                match outcome {
                                     Ok(new_session) => {
                                         let info = new_session.info().clone();
                                         details_slot.set(format_session_info(&info));
@@ -227,19 +343,20 @@ pub fn render_auth_tab(
                             await_logs.error("Start an auth flow first");
                         }
                         },
-                    "Await approval",
+                    if awaiting { "Awaiting approval..." } else { "Await approval" }
                     }
                     button {
                         class: "action secondary",
-                        title: "Cancel the current authorization request",
+                        title: "Cancel the current authorization request, including a running wait for approval",
                         "data-touch-tooltip": touch_tooltip(
-                            "Cancel the current authorization request",
+                            "Cancel the current authorization request, including a running wait for approval",
                         ),
                         onclick: move |_| {
+                            let stopped_wait = await_task.cancel();
                             let had_flow = {
                                 let mut guard = cancel_flow_signal.write();
                                 guard.take().is_some()
-                            };
+                            } || stopped_wait;
                             cancel_status_signal.set(String::new());
                             cancel_url_signal.set(String::new());
                             cancel_qr_signal.set(None);
@@ -260,6 +377,7 @@ pub fn render_auth_tab(
                         if let Some(data_url) = qr_value.clone() {
                             div { class: "qr-visual",
                                 img {
+                                    class: format!("qr-{}", size_value.key()),
                                     src: data_url,
                                     alt: "pubkyauth QR code",
                                     title: "Open this pubkyauth:// link locally",
@@ -278,6 +396,46 @@ pub fn render_auth_tab(
                                     onclick: open_link_handler(logs.clone(), url_value.clone()),
                                     "Open link locally",
                                 }
+                                button {
+                                    class: "action secondary qr-launch",
+                                    r#type: "button",
+                                    title: "Save the QR code as an SVG image at the selected size",
+                                    "data-touch-tooltip": touch_tooltip(
+                                        "Save the QR code as an SVG image at the selected size",
+                                    ),
+                                    onclick: move |_| {
+                                        let auth_url = save_url_signal.read().clone();
+                                        if auth_url.trim().is_empty() {
+                                            save_logs.error("Start an auth flow first");
+                                            return;
+                                        }
+                                        let raw_path = save_path_signal.read().clone();
+                                        let chosen_path = if raw_path.trim().is_empty() {
+                                            match file_dialog::save_file() {
+                                                FileDialogResult::Selected(path) => Some(path.display().to_string()),
+                                                FileDialogResult::Unavailable => {
+                                                    save_logs.info(file_dialog::MANUAL_ENTRY_HINT);
+                                                    None
+                                                }
+                                                FileDialogResult::Cancelled => None,
+                                            }
+                                        } else {
+                                            Some(raw_path)
+                                        };
+                                        if let Some(selected_path) = chosen_path {
+                                            let mut path_signal = save_path_signal;
+                                            let size = *save_size_signal.read();
+                                            match save_qr_svg(&auth_url, size, &selected_path) {
+                                                Ok(path) => {
+                                                    path_signal.set(path.display().to_string());
+                                                    save_logs.success(format!("QR code saved to {}", path.display()));
+                                                }
+                                                Err(err) => save_logs.error(format!("Failed to save QR code: {err}")),
+                                            }
+                                        }
+                                    },
+                                    "Save QR",
+                                }
                             }
                         }
                         div {
@@ -315,6 +473,32 @@ pub fn render_auth_tab(
                     }
                 }
                 div { class: "small-buttons",
+                    if show_paste {
+                        button {
+                            class: "action secondary",
+                            title: "Fill the URL from the clipboard",
+                            "data-touch-tooltip": touch_tooltip("Fill the URL from the clipboard"),
+                            onclick: move |_| {
+                                let mut request_signal = paste_request_signal.clone();
+                                let logs_task = paste_logs.clone();
+                                spawn(async move {
+                                    match read_clipboard().await {
+                                        Some(text) if is_pubkyauth_url(&text) => {
+                                            request_signal.set(text.trim().to_string());
+                                            logs_task.info("Pasted pubkyauth URL from the clipboard");
+                                        }
+                                        Some(_) => logs_task.error(
+                                            "The clipboard does not hold a pubkyauth: URL. Copy the whole request link and try again.",
+                                        ),
+                                        None => logs_task.error(
+                                            "Nothing to paste. Copy a pubkyauth: request link first, or allow clipboard access.",
+                                        ),
+                                    }
+                                });
+                            },
+                            "Paste from clipboard"
+                        }
+                    }
                     button {
                         class: "action",
                         title: "Approve the request using your loaded key",
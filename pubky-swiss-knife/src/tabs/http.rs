@@ -1,16 +1,128 @@
-use anyhow::anyhow;
+use anyhow::{Result, anyhow};
+use dioxus::events::MouseData;
 use dioxus::prelude::*;
+use futures::stream::{self, StreamExt};
 use pubky::PubkyHttpClient;
-use reqwest::Method;
-use reqwest::header::HeaderName;
+use reqwest::header::{CONTENT_TYPE, HeaderName};
+use reqwest::{Method, RequestBuilder};
+use std::path::Path;
+use std::time::{Instant, SystemTime};
 use url::Url;
 
 use crate::app::NetworkMode;
+use crate::components::{HeaderTable, ResponseCopyButtons};
 use crate::tabs::HttpTabState;
-use crate::utils::http::format_response;
+use crate::utils::environments::{HttpEnvironment, describe_missing, has_placeholders, substitute};
+use crate::utils::file_dialog::{self, FileDialogResult};
+use crate::utils::http::{
+    HistoryEntry, LoadSummary, MAX_CONCURRENT_REQUESTS, MAX_HISTORY_ENTRIES, MultipartBody,
+    format_latency, format_response_with_headers, multipart_from_file, network_mismatch,
+    parse_homeserver_key, push_history, utc_clock,
+};
 use crate::utils::logging::ActivityLog;
 use crate::utils::mobile::{is_android_touch, touch_copy_option, touch_tooltip};
 
+/// Upper bound for the repeat runner so a typo cannot flood a homeserver.
+const MAX_REPEAT_COUNT: usize = 200;
+
+fn client_for(network: NetworkMode) -> Result<PubkyHttpClient> {
+    Ok(match network {
+        NetworkMode::Mainnet => PubkyHttpClient::new()?,
+        NetworkMode::Testnet => PubkyHttpClient::testnet()?,
+    })
+}
+
+/// Build the request. With an attachment the text body is ignored and a typed
+/// `Content-Type` header gives way to the multipart one, which carries the
+/// boundary.
+fn build_request(
+    client: &PubkyHttpClient,
+    method: &Method,
+    url: &Url,
+    headers: &str,
+    body: &str,
+    multipart: Option<&MultipartBody>,
+) -> Result<RequestBuilder> {
+    let mut request = client.request(method.clone(), url.clone());
+    for line in headers.lines() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let (name, value) = line
+            .split_once(':')
+            .ok_or_else(|| anyhow!("Header must use Name: Value format"))?;
+        let header_name: HeaderName = name.trim().parse()?;
+        if multipart.is_some() && header_name == CONTENT_TYPE {
+            continue;
+        }
+        request = request.header(header_name, value.trim());
+    }
+    if let Some(form) = multipart {
+        request = request
+            .header(CONTENT_TYPE, form.content_type.as_str())
+            .body(form.bytes.clone());
+    } else if !body.is_empty() {
+        request = request.body(body.to_string());
+    }
+    Ok(request)
+}
+
+/// The attached file as a multipart body, or `None` when nothing is attached.
+fn load_attachment(path: &str, field: &str) -> Result<Option<MultipartBody>> {
+    let path = path.trim();
+    if path.is_empty() {
+        return Ok(None);
+    }
+    multipart_from_file(field, Path::new(path)).map(Some)
+}
+
+/// Substitute the active environment into the URL, headers, and body, failing
+/// on the first field that references an undefined variable.
+fn resolve_request(
+    environment: Option<&HttpEnvironment>,
+    url: &str,
+    headers: &str,
+    body: &str,
+) -> Result<(String, String, String)> {
+    let field = |label: &str, text: &str| {
+        substitute(text, environment).map_err(|missing| anyhow!(describe_missing(label, &missing)))
+    };
+    Ok((
+        field("URL", url)?,
+        field("headers", headers)?,
+        field("body", body)?,
+    ))
+}
+
+fn parse_repeat_count(raw: &str) -> Result<usize> {
+    let count: usize = raw
+        .trim()
+        .parse()
+        .map_err(|_| anyhow!("Repeat count must be a whole number"))?;
+    if count == 0 || count > MAX_REPEAT_COUNT {
+        return Err(anyhow!(
+            "Repeat count must be between 1 and {MAX_REPEAT_COUNT}"
+        ));
+    }
+    Ok(count)
+}
+
+/// Put a history entry back into the form without sending it.
+fn refill_handler(
+    mut method: Signal<String>,
+    mut url: Signal<String>,
+    mut headers: Signal<String>,
+    mut body: Signal<String>,
+    entry: HistoryEntry,
+) -> impl FnMut(Event<MouseData>) + 'static {
+    move |_| {
+        method.set(entry.method.clone());
+        url.set(entry.url.clone());
+        headers.set(entry.headers.clone());
+        body.set(entry.body.clone());
+    }
+}
+
 pub fn render_http_tab(
     network_mode: Signal<NetworkMode>,
     state: HttpTabState,
@@ -22,13 +134,75 @@ pub fn render_http_tab(
         headers,
         body,
         response,
+        response_headers,
+        repeat_count,
+        send_task,
+        repeat_task,
+        prefs,
+        history,
+        attachment_path,
+        attachment_field,
     } = state;
 
     let method_value = { method.read().clone() };
     let url_value = { url.read().clone() };
     let headers_value = { headers.read().clone() };
     let body_value = { body.read().clone() };
+    let repeat_value = { repeat_count.read().clone() };
+    let send_running = send_task.is_running();
+    let repeat_running = repeat_task.is_running();
     let response_value = { response.read().clone() };
+    let header_rows_value = { response_headers.read().clone() };
+    let history_value = { history.read().clone() };
+    let attachment_value = { attachment_path.read().clone() };
+    let attachment_field_value = { attachment_field.read().clone() };
+    let attached = !attachment_value.trim().is_empty();
+    let mut clear_history = history;
+    let environment_prefs = prefs.snapshot().environments;
+    let active_environment_value = environment_prefs.active.clone().unwrap_or_default();
+    let environment_names: Vec<String> = environment_prefs
+        .environments
+        .iter()
+        .map(|env| env.name.clone())
+        .collect();
+    let network_value = *network_mode.read();
+    let network_key = network_value.label().to_lowercase();
+    let resolved_url = substitute(&url_value, environment_prefs.active_environment())
+        .unwrap_or_else(|_| url_value.clone());
+    let network_warning =
+        parse_homeserver_key(&resolved_url).and_then(|key| network_mismatch(network_value, &key));
+    let resolved_preview = if [&url_value, &headers_value, &body_value]
+        .iter()
+        .any(|text| has_placeholders(text))
+    {
+        Some(
+            match resolve_request(
+                environment_prefs.active_environment(),
+                &url_value,
+                &headers_value,
+                &body_value,
+            ) {
+                Ok((url, headers, body)) => {
+                    let mut preview = format!("{method_value} {url}");
+                    if !headers.trim().is_empty() {
+                        preview.push_str(&format!("\n{}", headers.trim_end()));
+                    }
+                    if !body.is_empty() {
+                        preview.push_str(&format!("\n\n{body}"));
+                    }
+                    rsx! {
+                        pre { class: "mono", "{preview}" }
+                    }
+                }
+                Err(err) => rsx! {
+                    p { class: "request-preview-error", "{err}" }
+                },
+            },
+        )
+    } else {
+        None
+    };
+    let environment_logs = logs.clone();
     let response_copy_value = if response_value.trim().is_empty() {
         None
     } else {
@@ -44,19 +218,50 @@ pub fn render_http_tab(
     let mut url_binding = url;
     let mut headers_binding = headers;
     let mut body_binding = body;
+    let mut repeat_binding = repeat_count;
+    let mut attachment_binding = attachment_path;
+    let mut attachment_field_binding = attachment_field;
+    let mut attach_path_signal = attachment_path;
+    let mut detach_path_signal = attachment_path;
+    let attach_logs = logs.clone();
 
     let request_method_signal = method;
     let request_url_signal = url;
     let request_headers_signal = headers;
     let request_body_signal = body;
     let request_response_signal = response;
+    let request_headers_rows = response_headers;
     let request_logs = logs.clone();
     let request_network = network_mode;
+    let request_prefs = prefs;
+    let request_history = history;
+    let request_attachment = attachment_path;
+    let request_attachment_field = attachment_field;
+
+    let repeat_method_signal = method;
+    let repeat_url_signal = url;
+    let repeat_headers_signal = headers;
+    let repeat_body_signal = body;
+    let repeat_count_signal = repeat_count;
+    let repeat_response_signal = response;
+    let mut repeat_headers_rows = response_headers;
+    let repeat_logs = logs.clone();
+    let repeat_network = network_mode;
+    let repeat_prefs = prefs;
+    let repeat_attachment = attachment_path;
+    let repeat_attachment_field = attachment_field;
 
     rsx! {
         div { class: "tab-body single-column",
             section { class: "card",
-                h2 { "Raw Pubky/HTTPS request" }
+                div { class: "card-title-row",
+                    h2 { "Raw Pubky/HTTPS request" }
+                    span {
+                        class: "network-badge network-badge-{network_key}",
+                        title: "Requests go out through the {network_value.label()} client",
+                        "{network_value.label()}"
+                    }
+                }
                 div { class: "form-grid",
                     label {
                         "Method"
@@ -77,13 +282,42 @@ pub fn render_http_tab(
                         input {
                             value: url_value.clone(),
                             oninput: move |evt| url_binding.set(evt.value()),
-                            placeholder: "https:// or pubky://",
-                            title: "Enter the destination URL, either https:// or pubky://",
+                            placeholder: "https://, pubky://, or {{{{base}}}}/path",
+                            title: "Enter the destination URL, either https:// or pubky://; {{{{name}}}} uses the active environment",
                             "data-touch-tooltip": touch_tooltip(
-                                "Enter the destination URL, either https:// or pubky://",
+                                "Enter the destination URL, either https:// or pubky://; {{name}} uses the active environment",
                             ),
                         }
                     }
+                    if !environment_names.is_empty() {
+                        label {
+                            "Environment"
+                            select {
+                                value: active_environment_value.clone(),
+                                title: "Variables substituted for {{{{name}}}} in the URL, headers, and body",
+                                "data-touch-tooltip": touch_tooltip(
+                                    "Variables substituted for {{name}} in the URL, headers, and body",
+                                ),
+                                onchange: move |evt| {
+                                    let selected = evt.value();
+                                    prefs.update(&environment_logs, move |prefs| {
+                                        prefs.environments.active = (!selected.is_empty()).then_some(selected);
+                                    });
+                                },
+                                option { value: "", selected: active_environment_value.is_empty(), "None" }
+                                for name in environment_names.iter() {
+                                    option {
+                                        value: "{name}",
+                                        selected: active_environment_value == *name,
+                                        "{name}"
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+                if let Some(warning) = network_warning {
+                    p { class: "helper-text warning-text", "{warning}" }
                 }
                 div { class: "form-grid",
                     label {
@@ -105,7 +339,8 @@ pub fn render_http_tab(
                             class: "tall",
                             value: body_value.clone(),
                             oninput: move |evt| body_binding.set(evt.value()),
-                            placeholder: "Request body (optional)",
+                            disabled: attached,
+                            placeholder: if attached { "Not sent while a file is attached" } else { "Request body (optional)" },
                             title: "Optional request body to send",
                             "data-touch-tooltip": touch_tooltip(
                                 "Optional request body to send",
@@ -113,14 +348,84 @@ pub fn render_http_tab(
                         }
                     }
                 }
+                div { class: "form-grid",
+                    label {
+                        "Attached file (sent as multipart/form-data)"
+                        input {
+                            value: attachment_value.clone(),
+                            oninput: move |evt| attachment_binding.set(evt.value()),
+                            placeholder: "No file attached; the text body is sent",
+                            title: "Path of a file to upload as a multipart form instead of the text body",
+                            "data-touch-tooltip": touch_tooltip(
+                                "Path of a file to upload as a multipart form instead of the text body",
+                            ),
+                        }
+                    }
+                    label {
+                        "Form field name"
+                        input {
+                            value: attachment_field_value.clone(),
+                            oninput: move |evt| attachment_field_binding.set(evt.value()),
+                            placeholder: "file",
+                            title: "Name of the form field that carries the file",
+                            "data-touch-tooltip": touch_tooltip(
+                                "Name of the form field that carries the file",
+                            ),
+                        }
+                    }
+                }
                 div { class: "small-buttons",
                     button {
-                        class: "action",
-                        title: "Send the request through the Pubky-aware client",
+                        class: "action secondary",
+                        r#type: "button",
+                        title: "Choose a local file to upload as multipart/form-data",
                         "data-touch-tooltip": touch_tooltip(
-                            "Send the request through the Pubky-aware client",
+                            "Choose a local file to upload as multipart/form-data",
                         ),
+                        onclick: move |_| match file_dialog::pick_file() {
+                            FileDialogResult::Selected(path) => {
+                                attach_path_signal.set(path.display().to_string());
+                            }
+                            FileDialogResult::Unavailable => {
+                                attach_logs.info(file_dialog::MANUAL_ENTRY_HINT)
+                            }
+                            FileDialogResult::Cancelled => {}
+                        },
+                        "Attach file"
+                    }
+                    if attached {
+                        button {
+                            class: "action secondary",
+                            r#type: "button",
+                            title: "Send the text body again instead of the file",
+                            "data-touch-tooltip": touch_tooltip(
+                                "Send the text body again instead of the file",
+                            ),
+                            onclick: move |_| detach_path_signal.set(String::new()),
+                            "Detach"
+                        }
+                    }
+                }
+                if let Some(preview) = resolved_preview {
+                    div { class: "request-preview",
+                        span { class: "request-preview-label", "Resolved request" }
+                        {preview}
+                    }
+                }
+                div { class: "small-buttons",
+                    button {
+                        class: if send_running { "action secondary" } else { "action" },
+                        title: if send_running { "Abort the request that is still in flight" } else { "Send the request through the Pubky-aware client" },
+                        "data-touch-tooltip": touch_tooltip(if send_running {
+                            "Abort the request that is still in flight"
+                        } else {
+                            "Send the request through the Pubky-aware client"
+                        }),
                         onclick: move |_| {
+                            if send_task.cancel() {
+                                request_logs.info("Request cancelled");
+                                return;
+                            }
                             let method = request_method_signal.read().clone();
                             let url = request_url_signal.read().clone();
                             if url.trim().is_empty() {
@@ -129,45 +434,234 @@ pub fn render_http_tab(
                             }
                             let headers = request_headers_signal.read().clone();
                             let body = request_body_signal.read().clone();
+                            let mut history_entry = HistoryEntry {
+                                method: method.clone(),
+                                url: url.clone(),
+                                headers: headers.clone(),
+                                body: body.clone(),
+                                status: String::new(),
+                                succeeded: false,
+                                sent_at: utc_clock(SystemTime::now()),
+                            };
+                            let environments = request_prefs.snapshot().environments;
+                            let (url, headers, body) = match resolve_request(
+                                environments.active_environment(),
+                                &url,
+                                &headers,
+                                &body,
+                            ) {
+                                Ok(resolved) => resolved,
+                                Err(err) => {
+                                    request_logs.error(err.to_string());
+                                    return;
+                                }
+                            };
+                            let multipart = match load_attachment(
+                                &request_attachment.read(),
+                                &request_attachment_field.read(),
+                            ) {
+                                Ok(multipart) => multipart,
+                                Err(err) => {
+                                    request_logs.error(format!("Failed to attach file: {err}"));
+                                    return;
+                                }
+                            };
                             let mut response_signal = request_response_signal;
+                            let mut header_rows_signal = request_headers_rows;
+                            let mut history_signal = request_history;
                             let logs_task = request_logs.clone();
                             let network = *request_network.read();
-                            spawn(async move {
+                            send_task.start(async move {
                                 let result = async move {
                                     let method_parsed = Method::from_bytes(method.as_bytes())
                                         .map_err(|e| anyhow!("Invalid HTTP method: {e}"))?;
                                     let parsed_url = Url::parse(&url)?;
                                     let url_display = parsed_url.to_string();
-                                    let client = match network {
-                                        NetworkMode::Mainnet => PubkyHttpClient::new()?,
-                                        NetworkMode::Testnet => PubkyHttpClient::testnet()?,
-                                    };
-                                    let mut request = client.request(method_parsed.clone(), parsed_url);
-                                    for line in headers.lines() {
-                                        if line.trim().is_empty() {
-                                            continue;
-                                        }
-                                        let (name, value) = line
-                                            .split_once(':')
-                                            .ok_or_else(|| anyhow!("Header must use Name: Value format"))?;
-                                        let header_name: HeaderName = name.trim().parse()?;
-                                        request = request.header(header_name, value.trim());
+                                    let client = client_for(network)?;
+                                    let request = build_request(
+                                        &client,
+                                        &method_parsed,
+                                        &parsed_url,
+                                        &headers,
+                                        &body,
+                                        multipart.as_ref(),
+                                    )?;
+                                    let started = Instant::now();
+                                    let response = request.send().await?;
+                                    let elapsed = started.elapsed();
+                                    let status = response.status();
+                                    let (formatted, rows) = format_response_with_headers(response).await?;
+                                    response_signal.set(formatted);
+                                    header_rows_signal.set(rows);
+                                    Ok::<_, anyhow::Error>((
+                                        status,
+                                        format!("{method_parsed} {url_display} in {}", format_latency(elapsed)),
+                                    ))
+                                };
+                                match result.await {
+                                    Ok((status, msg)) => {
+                                        history_entry.status = status.to_string();
+                                        history_entry.succeeded = status.is_success();
+                                        logs_task.success(format!("Request completed: {msg}"));
                                     }
-                                    if !body.is_empty() {
-                                        request = request.body(body.clone());
+                                    Err(err) => {
+                                        history_entry.status = String::from("failed");
+                                        logs_task.error(format!("Request failed: {err}"));
                                     }
-                                    let response = request.send().await?;
-                                    let formatted = format_response(response).await?;
-                                    response_signal.set(formatted.clone());
-                                    Ok::<_, anyhow::Error>(format!("{method_parsed} {url_display}"))
+                                }
+                                push_history(&mut history_signal.write(), history_entry);
+                            });
+                        },
+                        if send_running { "Cancel" } else { "Send" }
+                    }
+                    label { class: "inline-field",
+                        "Repeat"
+                        input {
+                            r#type: "number",
+                            min: "1",
+                            max: "{MAX_REPEAT_COUNT}",
+                            value: repeat_value.clone(),
+                            oninput: move |evt| repeat_binding.set(evt.value()),
+                            title: "How many times to fire the request when running it concurrently",
+                            "data-touch-tooltip": touch_tooltip(
+                                "How many times to fire the request when running it concurrently",
+                            ),
+                        }
+                    }
+                    button {
+                        class: "action secondary",
+                        title: if repeat_running { "Stop the run; requests still in flight are aborted" } else { "Fire the request repeatedly with bounded concurrency and summarize the results" },
+                        "data-touch-tooltip": touch_tooltip(if repeat_running {
+                            "Stop the run; requests still in flight are aborted"
+                        } else {
+                            "Fire the request repeatedly with bounded concurrency and summarize the results"
+                        }),
+                        onclick: move |_| {
+                            if repeat_task.cancel() {
+                                let mut response_signal = repeat_response_signal;
+                                response_signal.set(String::from("Repeat run cancelled."));
+                                repeat_logs.info("Repeat run cancelled");
+                                return;
+                            }
+                            let url = repeat_url_signal.read().clone();
+                            if url.trim().is_empty() {
+                                repeat_logs.error("Provide a URL");
+                                return;
+                            }
+                            let count = match parse_repeat_count(&repeat_count_signal.read()) {
+                                Ok(count) => count,
+                                Err(err) => {
+                                    repeat_logs.error(err.to_string());
+                                    return;
+                                }
+                            };
+                            let method = repeat_method_signal.read().clone();
+                            let headers = repeat_headers_signal.read().clone();
+                            let body = repeat_body_signal.read().clone();
+                            let environments = repeat_prefs.snapshot().environments;
+                            let (url, headers, body) = match resolve_request(
+                                environments.active_environment(),
+                                &url,
+                                &headers,
+                                &body,
+                            ) {
+                                Ok(resolved) => resolved,
+                                Err(err) => {
+                                    repeat_logs.error(err.to_string());
+                                    return;
+                                }
+                            };
+                            let multipart = match load_attachment(
+                                &repeat_attachment.read(),
+                                &repeat_attachment_field.read(),
+                            ) {
+                                Ok(multipart) => multipart,
+                                Err(err) => {
+                                    repeat_logs.error(format!("Failed to attach file: {err}"));
+                                    return;
+                                }
+                            };
+                            let mut response_signal = repeat_response_signal;
+                            let logs_task = repeat_logs.clone();
+                            let network = *repeat_network.read();
+                            response_signal.set(format!("Running {count} requests..."));
+                            repeat_headers_rows.set(Vec::new());
+                            repeat_task.start(async move {
+                                let result = async {
+                                    let method_parsed = Method::from_bytes(method.as_bytes())
+                                        .map_err(|e| anyhow!("Invalid HTTP method: {e}"))?;
+                                    let parsed_url = Url::parse(&url)?;
+                                    let client = client_for(network)?;
+                                    // Validate headers once so a typo fails fast instead of N times.
+                                    build_request(
+                                        &client,
+                                        &method_parsed,
+                                        &parsed_url,
+                                        &headers,
+                                        &body,
+                                        multipart.as_ref(),
+                                    )?;
+                                    let outcomes = stream::iter(1..=count)
+                                        .map(|index| {
+                                            let request = build_request(
+                                                &client,
+                                                &method_parsed,
+                                                &parsed_url,
+                                                &headers,
+                                                &body,
+                                                multipart.as_ref(),
+                                            );
+                                            async move {
+                                                let started = Instant::now();
+                                                let outcome = match request {
+                                                    Ok(request) => request.send().await.map_err(anyhow::Error::from),
+                                                    Err(err) => Err(err),
+                                                };
+                                                (index, outcome.map(|resp| resp.status()), started.elapsed())
+                                            }
+                                        })
+                                        .buffer_unordered(MAX_CONCURRENT_REQUESTS)
+                                        .collect::<Vec<_>>()
+                                        .await;
+                                    let mut summary = LoadSummary::default();
+                                    for (index, outcome, elapsed) in outcomes {
+                                        match outcome {
+                                            Ok(status) => {
+                                                if !status.is_success() {
+                                                    logs_task.error(format!("Request #{index} returned {status}"));
+                                                }
+                                                summary.record_response(status, elapsed);
+                                            }
+                                            Err(err) => {
+                                                logs_task.error(format!("Request #{index} failed: {err}"));
+                                                summary.record_error(elapsed);
+                                            }
+                                        }
+                                    }
+                                    Ok::<_, anyhow::Error>((format!("{method_parsed} {parsed_url}"), summary))
                                 };
                                 match result.await {
-                                    Ok(msg) => logs_task.success(format!("Request completed: {msg}")),
-                                    Err(err) => logs_task.error(format!("Request failed: {err}")),
+                                    Ok((target, summary)) => {
+                                        response_signal.set(format!("{target}\n{}", summary.render()));
+                                        let message = format!(
+                                            "Repeated {target} {} times ({} failed)",
+                                            summary.total(),
+                                            summary.failures()
+                                        );
+                                        if summary.failures() == 0 {
+                                            logs_task.success(message);
+                                        } else {
+                                            logs_task.info(message);
+                                        }
+                                    }
+                                    Err(err) => {
+                                        response_signal.set(String::new());
+                                        logs_task.error(format!("Repeat run failed: {err}"));
+                                    }
                                 }
                             });
                         },
-                        "Send"
+                        if repeat_running { "Cancel run" } else { "Run concurrently" }
                     }
                 }
                 if !response_value.is_empty() {
@@ -178,7 +672,39 @@ pub fn render_http_tab(
                         ),
                         "data-touch-copy": touch_copy_option(response_copy_value.clone()),
                         "data-copy-success": response_copy_success.clone(),
-                        {response_value}
+                        {response_value.clone()}
+                    }
+                    ResponseCopyButtons { response: response_value }
+                    HeaderTable { headers: header_rows_value }
+                }
+            }
+            if !history_value.is_empty() {
+                section { class: "card",
+                    h2 { "History" }
+                    p { class: "helper-text", "The last {MAX_HISTORY_ENTRIES} requests sent with Send. Click one to put it back in the form; nothing is sent until you press Send." }
+                    div { class: "request-history",
+                        for entry in history_value.iter() {
+                            button {
+                                class: if entry.succeeded { "history-entry" } else { "history-entry history-error" },
+                                r#type: "button",
+                                title: "Refill the form with this request",
+                                "data-touch-tooltip": touch_tooltip("Refill the form with this request"),
+                                onclick: refill_handler(method, url, headers, body, entry.clone()),
+                                span { class: "history-method", {entry.method.clone()} }
+                                span { class: "history-url mono", {entry.url.clone()} }
+                                span { class: "history-status", {entry.status.clone()} }
+                                span { class: "history-time", {entry.sent_at.clone()} }
+                            }
+                        }
+                    }
+                    div { class: "small-buttons",
+                        button {
+                            class: "action secondary",
+                            title: "Forget the request history",
+                            "data-touch-tooltip": touch_tooltip("Forget the request history"),
+                            onclick: move |_| clear_history.set(Vec::new()),
+                            "Clear history"
+                        }
                     }
                 }
             }
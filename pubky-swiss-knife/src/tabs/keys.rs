@@ -1,24 +1,33 @@
-use anyhow::Result;
 use base64::{Engine as _, engine::general_purpose::STANDARD};
 use dioxus::prelude::*;
 use pubky::Keypair;
 use std::path::PathBuf;
 
+use crate::components::{ConfirmButton, QrToggle};
 use crate::tabs::KeysTabState;
+use crate::utils::accounts::AccountSwitcher;
+use crate::utils::clipboard::copy_to_clipboard;
 use crate::utils::file_dialog::{self, FileDialogResult};
 use crate::utils::logging::ActivityLog;
 use crate::utils::mobile::{is_android_touch, touch_copy, touch_tooltip};
+use crate::utils::prefs::PrefsHandle;
 use crate::utils::recovery::{
-    decode_secret_key, load_keypair_from_recovery, normalize_pkarr_path,
-    save_keypair_to_recovery_file,
+    MNEMONIC_WORDS, RecoveryError, decode_secret_key, first_recovery_file, keypair_from_mnemonic,
+    load_keypair_from_recovery, mnemonic_from_keypair, recovery_path,
+    save_keypair_to_recovery_file, secret_key_hex, verify_recovery_file,
 };
 
 pub fn render_keys_tab(state: KeysTabState, logs: ActivityLog) -> Element {
     let KeysTabState {
         keypair,
         secret_input,
+        mnemonic_input,
         recovery_path,
         recovery_passphrase,
+        secret_visible,
+        prefs,
+        accounts,
+        account_label,
     } = state;
     let current_public = {
         let guard = keypair.read();
@@ -38,36 +47,97 @@ pub fn render_keys_tab(state: KeysTabState, logs: ActivityLog) -> Element {
         None
     };
     let secret_value = { secret_input.read().clone() };
+    let export_allowed = prefs.snapshot().allows_secret_export();
+    let secret_shown = *secret_visible.read() && export_allowed;
+    let secret_class = if secret_shown {
+        "tall"
+    } else {
+        "tall masked-secret"
+    };
+    let secret_toggle_label = if secret_shown {
+        "Hide secret"
+    } else {
+        "Reveal secret"
+    };
+    let show_secret_title = if export_allowed {
+        "Copy the active signer secret (as base64) into the editor and reveal it, without touching disk"
+    } else {
+        "Turn on \"Allow exporting secret material\" in Settings to show the secret key"
+    };
+    let copy_hex_title = if export_allowed {
+        "Copy the active signer secret to the clipboard as lowercase hex, for the pkarr CLI and other SDKs"
+    } else {
+        "Turn on \"Allow exporting secret material\" in Settings to copy the secret key"
+    };
+    let reveal_title = if export_allowed || secret_shown {
+        "Show or mask the secret key in the editor"
+    } else {
+        "Turn on \"Allow exporting secret material\" in Settings to reveal the secret key"
+    };
+    let mnemonic_value = { mnemonic_input.read().clone() };
+    let mnemonic_export_title = if export_allowed {
+        "Write the active signer secret into the editor as a 24-word BIP39 phrase"
+    } else {
+        "Turn on \"Allow exporting secret material\" in Settings to show the mnemonic"
+    };
     let recovery_path_value = { recovery_path.read().clone() };
     let recovery_pass_value = { recovery_passphrase.read().clone() };
+    let recovery_prefs = prefs.snapshot().recovery;
+    let recent_recovery_files = recovery_prefs.recent_files.clone();
+    let choose_start_dir = recovery_prefs.last_dir.clone();
+    let load_start_dir = recovery_prefs.last_dir.clone();
+    let save_start_dir = recovery_prefs.last_dir;
 
     let mut generate_secret_input = secret_input;
     let mut generate_keypair = keypair;
     let generate_logs = logs.clone();
 
     let mut export_secret_input = secret_input;
+    let mut export_secret_visible = secret_visible;
     let export_keypair = keypair;
     let export_logs = logs.clone();
 
+    let hex_keypair = keypair;
+    let hex_logs = logs.clone();
+
     let mut import_keypair_signal = keypair;
     let import_secret_signal = secret_input;
     let import_logs = logs.clone();
 
+    let mut mnemonic_binding = mnemonic_input;
+    let mut mnemonic_export_signal = mnemonic_input;
+    let mut mnemonic_export_visible = secret_visible;
+    let mnemonic_export_keypair = keypair;
+    let mnemonic_export_logs = logs.clone();
+
+    let mnemonic_import_signal = mnemonic_input;
+    let mut mnemonic_import_keypair = keypair;
+    let mut mnemonic_import_secret = secret_input;
+    let mnemonic_import_logs = logs.clone();
+
     let load_path_signal = recovery_path;
     let load_pass_signal = recovery_passphrase;
     let load_keypair_signal = keypair;
     let load_secret_signal = secret_input;
     let load_logs = logs.clone();
 
+    let verify_path_signal = recovery_path;
+    let verify_pass_signal = recovery_passphrase;
+    let verify_logs = logs.clone();
+
     let save_path_signal = recovery_path;
     let save_pass_signal = recovery_passphrase;
     let save_keypair_signal = keypair;
     let save_logs = logs.clone();
 
     let mut secret_input_binding = secret_input;
+    let mut secret_visible_toggle = secret_visible;
     let mut recovery_pass_binding = recovery_passphrase;
     let mut choose_recovery_path_signal = recovery_path;
     let mut recovery_path_binding = recovery_path;
+    let mut drop_path_signal = recovery_path;
+    let drop_pass_signal = recovery_passphrase;
+    let drop_logs = logs.clone();
     let choose_logs = logs;
 
     rsx! {
@@ -93,6 +163,9 @@ pub fn render_keys_tab(state: KeysTabState, logs: ActivityLog) -> Element {
                         { format!(" Current public key: {}.", current_public) }
                     }
                 }
+                if let Some(value) = public_copy_value.clone() {
+                    QrToggle { value }
+                }
                 div { class: "small-buttons",
                     button {
                         class: "action",
@@ -102,7 +175,7 @@ pub fn render_keys_tab(state: KeysTabState, logs: ActivityLog) -> Element {
                         ),
                         onclick: move |_| {
                             let kp = Keypair::random();
-                            generate_secret_input.set(STANDARD.encode(kp.secret_key()));
+                            generate_secret_input.set(editor_secret(prefs, &kp));
                             generate_keypair.set(Some(kp.clone()));
                             generate_logs.success(format!("Generated signer {}", kp.public_key()));
                         },
@@ -110,13 +183,16 @@ pub fn render_keys_tab(state: KeysTabState, logs: ActivityLog) -> Element {
                     }
                     button {
                         class: "action secondary",
-                        title: "Copy the active signer secret (as base64) into the editor without touching disk",
-                        "data-touch-tooltip": touch_tooltip(
-                            "Copy the active signer secret (as base64) into the editor without touching disk",
-                        ),
+                        disabled: !export_allowed,
+                        title: show_secret_title,
+                        "data-touch-tooltip": touch_tooltip(show_secret_title),
                         onclick: move |_| {
+                            if !export_allowed {
+                                return;
+                            }
                             if let Some(kp) = export_keypair.read().as_ref() {
                                 export_secret_input.set(STANDARD.encode(kp.secret_key()));
+                                export_secret_visible.set(true);
                                 export_logs.info("Secret key exported to editor");
                             } else {
                                 export_logs.error("No key loaded");
@@ -124,19 +200,49 @@ pub fn render_keys_tab(state: KeysTabState, logs: ActivityLog) -> Element {
                         },
                         "Show secret key"
                     }
+                    button {
+                        class: "action secondary",
+                        disabled: !export_allowed,
+                        title: copy_hex_title,
+                        "data-touch-tooltip": touch_tooltip(copy_hex_title),
+                        onclick: move |_| {
+                            if !export_allowed {
+                                return;
+                            }
+                            if let Some(kp) = hex_keypair.read().as_ref() {
+                                copy_to_clipboard(&secret_key_hex(kp));
+                                hex_logs.info("Secret key copied to clipboard as hex");
+                            } else {
+                                hex_logs.error("No key loaded");
+                            }
+                        },
+                        "Copy secret as hex"
+                    }
+                    button {
+                        class: "action secondary",
+                        disabled: !export_allowed && !secret_shown,
+                        title: reveal_title,
+                        "data-touch-tooltip": touch_tooltip(reveal_title),
+                        onclick: move |_| {
+                            secret_visible_toggle.set(!secret_shown && export_allowed);
+                        },
+                        "{secret_toggle_label}"
+                    }
                 }
                 div { class: "form-grid",
                     label {
-                        "Secret key (base64)"
+                        "Secret key (base64 or hex)"
                         textarea {
-                            class: "tall",
+                            class: secret_class,
+                            spellcheck: "false",
+                            autocomplete: "off",
                             value: secret_value,
                             oninput: move |evt| secret_input_binding.set(evt.value()),
-                            title: "Paste or edit the base64-encoded 32-byte secret for your signing key",
+                            title: "Paste or edit the 32-byte secret for your signing key, as base64 or 64 hex characters",
                             "data-touch-tooltip": touch_tooltip(
-                                "Paste or edit the base64-encoded 32-byte secret for your signing key",
+                                "Paste or edit the 32-byte secret for your signing key, as base64 or 64 hex characters",
                             ),
-                            placeholder: "Base64 encoded 32-byte secret key",
+                            placeholder: "Base64 or hex encoded 32-byte secret key",
                         }
                     }
                 }
@@ -160,9 +266,94 @@ pub fn render_keys_tab(state: KeysTabState, logs: ActivityLog) -> Element {
                         "Import secret"
                     }
                 }
+                div { class: "form-grid",
+                    label {
+                        "Mnemonic (BIP39)"
+                        textarea {
+                            class: secret_class,
+                            spellcheck: "false",
+                            autocomplete: "off",
+                            value: mnemonic_value,
+                            oninput: move |evt| mnemonic_binding.set(evt.value()),
+                            title: "A 24-word BIP39 phrase whose entropy is the 32-byte secret",
+                            "data-touch-tooltip": touch_tooltip(
+                                "A 24-word BIP39 phrase whose entropy is the 32-byte secret",
+                            ),
+                            placeholder: "{MNEMONIC_WORDS} words separated by spaces",
+                        }
+                    }
+                }
+                div { class: "small-buttons",
+                    button {
+                        class: "action",
+                        title: "Activate the signer encoded by the mnemonic",
+                        "data-touch-tooltip": touch_tooltip("Activate the signer encoded by the mnemonic"),
+                        onclick: move |_| {
+                            let phrase = mnemonic_import_signal.read().clone();
+                            match keypair_from_mnemonic(&phrase) {
+                                Ok(kp) => {
+                                    mnemonic_import_secret.set(editor_secret(prefs, &kp));
+                                    mnemonic_import_keypair.set(Some(kp.clone()));
+                                    mnemonic_import_logs
+                                        .success(format!("Loaded key for {} from mnemonic", kp.public_key()));
+                                }
+                                Err(err) => mnemonic_import_logs.error(format!("Invalid mnemonic: {err}")),
+                            }
+                        },
+                        "Import mnemonic"
+                    }
+                    button {
+                        class: "action secondary",
+                        disabled: !export_allowed,
+                        title: mnemonic_export_title,
+                        "data-touch-tooltip": touch_tooltip(mnemonic_export_title),
+                        onclick: move |_| {
+                            if !export_allowed {
+                                return;
+                            }
+                            if let Some(kp) = mnemonic_export_keypair.read().as_ref() {
+                                mnemonic_export_signal.set(mnemonic_from_keypair(kp));
+                                mnemonic_export_visible.set(true);
+                                mnemonic_export_logs.info("Mnemonic exported to editor");
+                            } else {
+                                mnemonic_export_logs.error("No key loaded");
+                            }
+                        },
+                        "Show mnemonic"
+                    }
+                }
             }
-            section { class: "card",
+            section {
+                class: "card",
+                ondragover: move |evt| evt.prevent_default(),
+                ondrop: move |evt| {
+                    evt.prevent_default();
+                    let dropped: Vec<PathBuf> = evt.files().into_iter().map(|file| file.path()).collect();
+                    if dropped.is_empty() {
+                        return;
+                    }
+                    match first_recovery_file(dropped) {
+                        Some(path) => {
+                            drop_path_signal.set(path.display().to_string());
+                            if drop_pass_signal.read().is_empty() {
+                                drop_logs.info(format!(
+                                    "Recovery file set to {}; enter its passphrase to load it",
+                                    path.display()
+                                ));
+                            } else {
+                                drop_logs.info(format!(
+                                    "Recovery file set to {}; press Load from recovery file",
+                                    path.display()
+                                ));
+                            }
+                        }
+                        None => drop_logs.error("Drop a .pkarr recovery file"),
+                    }
+                },
                 h2 { "Recovery files" }
+                if !is_android_touch() {
+                    p { class: "helper-text", "Drop a .pkarr file anywhere on this card to use its path." }
+                }
                 div { class: "form-grid",
                     label {
                         "Recovery file path"
@@ -180,7 +371,7 @@ pub fn render_keys_tab(state: KeysTabState, logs: ActivityLog) -> Element {
                                     "Browse for an existing PKARR or Pubky recovery file to import",
                                 ),
                                 onclick: move |_| {
-                                    match file_dialog::pick_file() {
+                                    match file_dialog::pick_file_in(choose_start_dir.as_deref()) {
                                         FileDialogResult::Selected(path) => {
                                             choose_recovery_path_signal.set(path.display().to_string());
                                         }
@@ -193,6 +384,23 @@ pub fn render_keys_tab(state: KeysTabState, logs: ActivityLog) -> Element {
                                 "Choose file"
                             }
                         }
+                        if !recent_recovery_files.is_empty() {
+                            div { class: "breadcrumbs",
+                                span { class: "helper-text", "Recent:" }
+                                for recent in recent_recovery_files {
+                                    button {
+                                        class: "crumb",
+                                        title: format!("Use {}", recent.display()),
+                                        onclick: {
+                                            let mut recent_path_signal = recovery_path;
+                                            let recent_display = recent.display().to_string();
+                                            move |_| recent_path_signal.set(recent_display.clone())
+                                        },
+                                        {recent.file_name().map(|name| name.to_string_lossy().into_owned()).unwrap_or_default()}
+                                    }
+                                }
+                            }
+                        }
                     }
                     label {
                         "Passphrase"
@@ -219,7 +427,7 @@ pub fn render_keys_tab(state: KeysTabState, logs: ActivityLog) -> Element {
                             let passphrase = load_pass_signal.read().clone();
                             let mut immediate_path_signal = load_path_signal;
                             let chosen_path = if raw_path.trim().is_empty() {
-                                match file_dialog::pick_file() {
+                                match file_dialog::pick_file_in(load_start_dir.as_deref()) {
                                     FileDialogResult::Selected(path) => {
                                         let display = path.display().to_string();
                                         immediate_path_signal.set(display.clone());
@@ -241,24 +449,28 @@ pub fn render_keys_tab(state: KeysTabState, logs: ActivityLog) -> Element {
                                 let logs_task = load_logs.clone();
                                 let passphrase_for_task = passphrase.clone();
                                 spawn(async move {
-                                    let outcome = (|| -> Result<(Keypair, PathBuf)> {
-                                        let normalized = normalize_pkarr_path(&selected_path)?;
+                                    let outcome = (|| -> Result<(Keypair, PathBuf), RecoveryError> {
+                                        let normalized = recovery_path(&selected_path)?;
                                         let kp = load_keypair_from_recovery(&normalized, &passphrase_for_task)?;
                                         Ok((kp, normalized))
                                     })();
                                     match outcome {
                                         Ok((kp, normalized)) => {
-                                            secret_signal.set(STANDARD.encode(kp.secret_key()));
+                                            secret_signal.set(editor_secret(prefs, &kp));
                                             keypair_signal.set(Some(kp.clone()));
                                             path_signal.set(normalized.display().to_string());
+                                            prefs.update(&logs_task, |stored| {
+                                                stored.recovery.remember(&normalized)
+                                            });
                                             logs_task.success(format!(
                                                 "Decrypted recovery file {} for {}",
                                                 normalized.display(),
                                                 kp.public_key()
                                             ));
                                         }
-                                        Err(err) => logs_task.error(format!(
-                                            "Failed to load recovery file: {err}"
+                                        Err(err) => logs_task.error(recovery_failure(
+                                            "Failed to load recovery file",
+                                            &err,
                                         )),
                                     }
                                 });
@@ -266,6 +478,39 @@ pub fn render_keys_tab(state: KeysTabState, logs: ActivityLog) -> Element {
                         },
                         "Load from recovery file"
                     }
+                    button {
+                        class: "action secondary",
+                        title: "Check that the passphrase opens this recovery file and show its public key, without loading the key",
+                        "data-touch-tooltip": touch_tooltip(
+                            "Check that the passphrase opens this recovery file and show its public key, without loading the key",
+                        ),
+                        onclick: move |_| {
+                            let raw_path = verify_path_signal.read().clone();
+                            if raw_path.trim().is_empty() {
+                                verify_logs.error("Enter or choose a recovery file to verify");
+                                return;
+                            }
+                            let passphrase = verify_pass_signal.read().clone();
+                            let logs_task = verify_logs.clone();
+                            spawn(async move {
+                                let outcome = recovery_path(&raw_path).and_then(|normalized| {
+                                    verify_recovery_file(&normalized, &passphrase)
+                                        .map(|public_key| (public_key, normalized))
+                                });
+                                match outcome {
+                                    Ok((public_key, normalized)) => logs_task.success(format!(
+                                        "Recovery file {} is valid for {public_key} (active key unchanged)",
+                                        normalized.display()
+                                    )),
+                                    Err(err) => logs_task.error(recovery_failure(
+                                        "Recovery file check failed",
+                                        &err,
+                                    )),
+                                }
+                            });
+                        },
+                        "Verify only"
+                    }
                     button {
                         class: "action secondary",
                         title: "Encrypt the active keypair into a PKARR-compatible bundle and save it",
@@ -277,7 +522,7 @@ pub fn render_keys_tab(state: KeysTabState, logs: ActivityLog) -> Element {
                                 let raw_path = save_path_signal.read().clone();
                                 let mut immediate_path_signal = save_path_signal;
                                 let chosen_path = if raw_path.trim().is_empty() {
-                                    match file_dialog::save_file() {
+                                    match file_dialog::save_file_in(save_start_dir.as_deref()) {
                                         FileDialogResult::Selected(path) => {
                                             let display = path.display().to_string();
                                             immediate_path_signal.set(display.clone());
@@ -300,13 +545,17 @@ pub fn render_keys_tab(state: KeysTabState, logs: ActivityLog) -> Element {
                                         match save_keypair_to_recovery_file(&kp, &selected_path, &passphrase) {
                                             Ok(path) => {
                                                 path_signal.set(path.display().to_string());
+                                                prefs.update(&logs_task, |stored| {
+                                                    stored.recovery.remember(&path)
+                                                });
                                                 logs_task.success(format!(
                                                     "Recovery file saved to {}",
                                                     path.display()
                                                 ));
                                             }
-                                            Err(err) => logs_task.error(format!(
-                                                "Failed to save recovery file: {err}"
+                                            Err(err) => logs_task.error(recovery_failure(
+                                                "Failed to save recovery file",
+                                                &err,
                                             )),
                                         }
                                     });
@@ -319,6 +568,145 @@ pub fn render_keys_tab(state: KeysTabState, logs: ActivityLog) -> Element {
                     }
                 }
             }
+            {render_accounts_card(accounts, account_label, logs.clone())}
+        }
+    }
+}
+
+#[allow(clippy::clone_on_copy)]
+fn render_accounts_card(
+    switcher: AccountSwitcher,
+    account_label: Signal<String>,
+    logs: ActivityLog,
+) -> Element {
+    let active_key = switcher.active_key();
+    let rows: Vec<_> = switcher
+        .accounts
+        .read()
+        .accounts
+        .iter()
+        .map(|account| {
+            let is_active = active_key.as_deref() == Some(account.public_key.as_str());
+            let homeserver = if account.homeserver.is_empty() {
+                String::from("no homeserver yet")
+            } else {
+                account.homeserver.clone()
+            };
+            (account.clone(), is_active, homeserver)
+        })
+        .collect();
+    let passphrase_value = switcher.passphrase.read().clone();
+    let label_value = account_label.read().clone();
+
+    let mut passphrase_binding = switcher.passphrase;
+    let mut label_binding = account_label.clone();
+    let save_logs = logs.clone();
+
+    rsx! {
+        section { class: "card",
+            h2 { "Saved accounts" }
+            p { class: "helper-text",
+                "Keep several identities and switch between them from the header. Secrets are encrypted with the accounts passphrase, which stays in memory only; each account remembers its homeserver, and its session is kept while the app runs."
+            }
+            div { class: "form-grid",
+                label {
+                    "Accounts passphrase"
+                    input {
+                        r#type: "password",
+                        value: passphrase_value,
+                        oninput: move |evt| passphrase_binding.set(evt.value()),
+                        title: "Encrypts saved accounts and unlocks them when switching",
+                        "data-touch-tooltip": touch_tooltip(
+                            "Encrypts saved accounts and unlocks them when switching",
+                        ),
+                    }
+                }
+                label {
+                    "Account label"
+                    input {
+                        value: label_value,
+                        oninput: move |evt| label_binding.set(evt.value()),
+                        placeholder: "Optional, e.g. Work",
+                    }
+                }
+            }
+            div { class: "small-buttons",
+                button {
+                    class: "action",
+                    title: "Encrypt the loaded key and add it to the saved accounts",
+                    "data-touch-tooltip": touch_tooltip(
+                        "Encrypt the loaded key and add it to the saved accounts",
+                    ),
+                    onclick: move |_| {
+                        let label = account_label.read().clone();
+                        switcher.save_current(&label, &save_logs);
+                    },
+                    "Save current key"
+                }
+            }
+            if rows.is_empty() {
+                p { class: "helper-text", "No saved accounts yet." }
+            } else {
+                ul { class: "account-list",
+                    for (account, is_active, homeserver) in rows {
+                        li {
+                            key: "{account.public_key}",
+                            class: if is_active { "account-row active" } else { "account-row" },
+                            div { class: "account-meta",
+                                strong { "{account.label}" }
+                                span { class: "mono", "{account.public_key}" }
+                                span { class: "helper-text", "{homeserver}" }
+                            }
+                            div { class: "small-buttons",
+                                button {
+                                    class: "action secondary",
+                                    disabled: is_active,
+                                    title: "Make this the active key across all tabs",
+                                    "data-touch-tooltip": touch_tooltip(
+                                        "Make this the active key across all tabs",
+                                    ),
+                                    onclick: {
+                                        let switch_key = account.public_key.clone();
+                                        let switch_logs = logs.clone();
+                                        move |_| switcher.switch_to(&switch_key, &switch_logs)
+                                    },
+                                    if is_active { "Active" } else { "Switch" }
+                                }
+                                ConfirmButton {
+                                    label: "Remove",
+                                    confirm_label: "Confirm remove?",
+                                    danger: true,
+                                    title: "Forget this saved account (click twice to confirm)",
+                                    on_confirm: {
+                                        let remove_key = account.public_key.clone();
+                                        let remove_logs = logs.clone();
+                                        move |_| switcher.remove(&remove_key, &remove_logs)
+                                    },
+                                }
+                            }
+                        }
+                    }
+                }
+            }
         }
     }
 }
+
+/// `"<prefix>: <error>"`, followed by what to do next when the error has a hint.
+fn recovery_failure(prefix: &str, err: &RecoveryError) -> String {
+    match err.hint() {
+        Some(hint) => format!("{prefix}: {err}. {hint}"),
+        None => format!("{prefix}: {err}"),
+    }
+}
+
+/// The secret key editor's text for a newly loaded `kp`: its base64 secret
+/// only while secret export is allowed. The masking is just a CSS style, so
+/// otherwise the editor is cleared to keep the secret out of the page.
+fn editor_secret(prefs: PrefsHandle, kp: &Keypair) -> String {
+    if prefs.snapshot().allows_secret_export() {
+        STANDARD.encode(kp.secret_key())
+    } else {
+        String::new()
+    }
+}
@@ -1,9 +1,84 @@
+use std::time::Duration;
+
 use dioxus::prelude::*;
-use pubky::PublicKey;
+use pubky::{Keypair, PublicKey};
 
 use crate::tabs::PkdnsTabState;
+use crate::utils::file_dialog::{self, FileDialogResult};
 use crate::utils::logging::ActivityLog;
+use crate::utils::mobile::{is_android_touch, touch_copy_option, touch_tooltip};
+use crate::utils::packet::{
+    format_packet_dump, load_signed_packet, resolve_signed_packet, save_signed_packet,
+};
+use crate::utils::pkdns::{
+    BatchLookupOutcome, format_lookup_table, parse_key_list, parse_republish_minutes,
+    resolve_homeserver, resolve_homeservers, save_batch_csv,
+};
 use crate::utils::pubky::PubkyFacadeHandle;
+use crate::utils::recovery::normalize_pkarr_path;
+use crate::utils::task::InFlight;
+
+/// Republish the active key's `_pubky` record if stale, now and then every
+/// `minutes`, until `task` is cancelled. The key and override are read at
+/// each run.
+pub fn start_republish_loop(
+    pubky: PubkyFacadeHandle,
+    keypair: Signal<Option<Keypair>>,
+    host_override: Signal<String>,
+    minutes: u64,
+    task: InFlight,
+    logs: ActivityLog,
+) {
+    task.start(async move {
+        loop {
+            republish_once(&pubky, keypair, host_override, minutes, &logs).await;
+            tokio::time::sleep(Duration::from_secs(minutes * 60)).await;
+        }
+    });
+}
+
+async fn republish_once(
+    pubky: &PubkyFacadeHandle,
+    keypair: Signal<Option<Keypair>>,
+    host_override: Signal<String>,
+    minutes: u64,
+    logs: &ActivityLog,
+) {
+    let Some(kp) = keypair.peek().as_ref().cloned() else {
+        logs.error("Scheduled republish skipped: no key loaded");
+        return;
+    };
+    let Some(pubky_arc) = pubky.ready_or_log(logs) else {
+        return;
+    };
+    let override_input = host_override.peek().trim().to_string();
+    let override_pk = if override_input.is_empty() {
+        None
+    } else {
+        match PublicKey::try_from(override_input.as_str()) {
+            Ok(pk) => Some(pk),
+            Err(err) => {
+                logs.error(format!(
+                    "Scheduled republish skipped: invalid homeserver override: {err}"
+                ));
+                return;
+            }
+        }
+    };
+    let public = kp.public_key();
+    logs.info(format!("Scheduled republish of _pubky for {public}"));
+    let signer = pubky_arc.signer(kp);
+    match signer
+        .pkdns()
+        .publish_homeserver_if_stale(override_pk.as_ref())
+        .await
+    {
+        Ok(_) => logs.success(format!(
+            "Scheduled republish for {public} done; next in {minutes} min"
+        )),
+        Err(err) => logs.error(format!("Scheduled republish for {public} failed: {err}")),
+    }
+}
 
 #[allow(clippy::clone_on_copy)]
 pub fn render_pkdns_tab(
@@ -16,19 +91,68 @@ pub fn render_pkdns_tab(
         lookup_input,
         lookup_result,
         host_override,
+        packet,
+        packet_dump,
+        packet_path,
+        batch_input,
+        batch_rows,
+        lookup_task,
+        batch_task,
+        batch_export_path,
+        republish_task,
+        republish_minutes,
+        mainnet_guard,
     } = state;
 
     let lookup_value = { lookup_input.read().clone() };
     let lookup_result_value = { lookup_result.read().clone() };
     let host_override_value = { host_override.read().clone() };
+    let packet_dump_value = { packet_dump.read().clone() };
+    let packet_path_value = { packet_path.read().clone() };
+    let batch_input_value = { batch_input.read().clone() };
+    let batch_rows_value = { batch_rows.read().clone() };
+    let lookup_running = lookup_task.is_running();
+    let batch_running_value = batch_task.is_running();
+    let batch_export_value = { batch_export_path.read().clone() };
+    let republish_running = republish_task.is_running();
+    let republish_minutes_value = { republish_minutes.read().clone() };
+    let batch_summary = if batch_rows_value.is_empty() {
+        None
+    } else {
+        let found = batch_rows_value
+            .iter()
+            .filter(|row| matches!(row.outcome, BatchLookupOutcome::Homeserver(_)))
+            .count();
+        Some(format!(
+            "{found} of {} keys advertise a homeserver",
+            batch_rows_value.len()
+        ))
+    };
+    let packet_copy_value = if packet_dump_value.trim().is_empty() {
+        None
+    } else {
+        Some(packet_dump_value.clone())
+    };
+    let packet_copy_success = if is_android_touch() {
+        Some(String::from("Copied packet dump to clipboard"))
+    } else {
+        None
+    };
 
     let mut lookup_binding = lookup_input.clone();
     let mut override_binding = host_override.clone();
+    let mut packet_path_binding = packet_path.clone();
+    let mut batch_input_binding = batch_input.clone();
+    let mut batch_export_binding = batch_export_path.clone();
 
     let lookup_logs = logs.clone();
     let lookup_pubky = pubky.clone();
     let lookup_result_signal = lookup_result.clone();
 
+    let lookup_all_logs = logs.clone();
+    let lookup_all_pubky = pubky.clone();
+    let lookup_all_result_signal = lookup_result.clone();
+
     let self_lookup_logs = logs.clone();
     let self_lookup_pubky = pubky.clone();
     let self_lookup_result_signal = lookup_result.clone();
@@ -46,6 +170,37 @@ pub fn render_pkdns_tab(
     let publish_force_override = host_override.clone();
     let publish_force_result_signal = lookup_result.clone();
 
+    let dump_logs = logs.clone();
+    let dump_pubky = pubky.clone();
+    let dump_keypair = keypair.clone();
+    let dump_lookup = lookup_input.clone();
+    let dump_packet_signal = packet.clone();
+    let dump_output_signal = packet_dump.clone();
+
+    let save_packet_logs = logs.clone();
+    let save_packet_signal = packet.clone();
+    let save_packet_path = packet_path.clone();
+
+    let load_packet_logs = logs.clone();
+    let load_packet_signal = packet.clone();
+    let load_packet_path = packet_path.clone();
+    let load_packet_output = packet_dump.clone();
+
+    let batch_logs = logs.clone();
+    let batch_pubky = pubky.clone();
+    let batch_input_signal = batch_input.clone();
+    let batch_rows_signal = batch_rows.clone();
+
+    let mut republish_minutes_binding = republish_minutes;
+    let republish_logs = logs.clone();
+    let republish_pubky = pubky.clone();
+    let republish_keypair = keypair.clone();
+    let republish_override = host_override.clone();
+
+    let export_logs = logs.clone();
+    let export_rows = batch_rows.clone();
+    let export_path = batch_export_path.clone();
+
     rsx! {
         div { class: "tab-body single-column",
             section { class: "card",
@@ -54,19 +209,25 @@ pub fn render_pkdns_tab(
                 div { class: "form-grid",
                     label {
                         "User public key"
-                        input {
+                        textarea {
                             value: lookup_value,
                             oninput: move |evt| lookup_binding.set(evt.value()),
-                            title: "Enter a user's public key to resolve their homeserver via PKDNS",
-                            placeholder: "Base32 public key",
+                            title: "Enter a user's public key to resolve their homeserver via PKDNS, or one per line for Lookup all",
+                            placeholder: "Base32 public key (one per line for Lookup all)",
                         }
                     }
                 }
                 div { class: "small-buttons",
                     button {
                         class: "action",
-                        title: "Resolve the homeserver registered for this user via PKARR",
+                        title: if lookup_running { "Abort the lookup that is still running" } else { "Resolve the homeserver registered for this user via PKARR" },
                         onclick: move |_| {
+                            if lookup_task.cancel() {
+                                let mut result_signal = lookup_result_signal.clone();
+                                result_signal.set(String::from("Lookup cancelled."));
+                                lookup_logs.info("Homeserver lookup cancelled");
+                                return;
+                            }
                             let query = lookup_input.read().clone();
                             let trimmed = query.trim().to_string();
                             if trimmed.is_empty() {
@@ -89,10 +250,8 @@ pub fn render_pkdns_tab(
                             }
                             let logs_task = lookup_logs.clone();
                             let mut result_signal = lookup_result_signal.clone();
-                            spawn(async move {
-                                let pkdns = pubky_arc.pkdns();
-                                let resolved = pkdns.get_homeserver_of(&target_pk).await;
-                                match resolved {
+                            lookup_task.start(async move {
+                                match resolve_homeserver(&pubky_arc, &target_pk).await {
                                     Some(host) => {
                                         result_signal.set(format!("Homeserver for {target_pk}: {host}"));
                                         logs_task.success(format!("Resolved homeserver for {target_pk}: {host}"));
@@ -104,10 +263,41 @@ pub fn render_pkdns_tab(
                                 }
                             });
                         },
-                        "Lookup public key",
+                        if lookup_running { "Cancel lookup" } else { "Lookup public key" }
+                    }
+                    button {
+                        class: "action secondary",
+                        disabled: lookup_running,
+                        title: "Resolve every public key in the field, one per line, a few at a time",
+                        "data-touch-tooltip": touch_tooltip("Resolve every public key in the field, one per line"),
+                        onclick: move |_| {
+                            let keys = parse_key_list(&lookup_input.read());
+                            if keys.is_empty() {
+                                lookup_all_logs.error("Enter at least one public key");
+                                return;
+                            }
+                            let Some(pubky_arc) = lookup_all_pubky.ready_or_log(&lookup_all_logs) else {
+                                return;
+                            };
+                            let total = keys.len();
+                            let mut result_signal = lookup_all_result_signal.clone();
+                            result_signal.set(format!("Looking up {total} homeservers..."));
+                            let logs_task = lookup_all_logs.clone();
+                            lookup_task.start(async move {
+                                let rows = resolve_homeservers(&pubky_arc, keys).await;
+                                let found = rows
+                                    .iter()
+                                    .filter(|row| matches!(row.outcome, BatchLookupOutcome::Homeserver(_)))
+                                    .count();
+                                result_signal.set(format_lookup_table(&rows));
+                                logs_task.success(format!("Lookup all finished: {found}/{total} resolved"));
+                            });
+                        },
+                        "Lookup all"
                     }
                     button {
                         class: "action secondary",
+                        disabled: lookup_running,
                         title: "Check which homeserver the loaded key currently advertises",
                         onclick: move |_| {
                             let Some(kp) = self_lookup_keypair.read().as_ref().cloned() else {
@@ -123,7 +313,7 @@ pub fn render_pkdns_tab(
                             }
                             let logs_task = self_lookup_logs.clone();
                             let mut result_signal = self_lookup_result_signal.clone();
-                            spawn(async move {
+                            lookup_task.start(async move {
                                 let signer = pubky_arc.signer(kp.clone());
                                 let pkdns = signer.pkdns();
                                 match pkdns.get_homeserver().await {
@@ -153,6 +343,136 @@ pub fn render_pkdns_tab(
                     div { class: "outputs", {lookup_result_value} }
                 }
             }
+            section { class: "card",
+                h2 { "Batch lookups" }
+                p { class: "helper-text", "Resolve the homeservers of many users at once. Enter one public key per line." }
+                div { class: "form-grid",
+                    label {
+                        "Public keys"
+                        textarea {
+                            class: "tall",
+                            value: batch_input_value,
+                            oninput: move |evt| batch_input_binding.set(evt.value()),
+                            placeholder: "One base32 public key per line",
+                            title: "Newline-separated public keys to resolve",
+                            "data-touch-tooltip": touch_tooltip("Newline-separated public keys to resolve"),
+                        }
+                    }
+                    label {
+                        "CSV export path"
+                        input {
+                            value: batch_export_value,
+                            oninput: move |evt| batch_export_binding.set(evt.value()),
+                            placeholder: "Leave blank to choose with a file dialog",
+                            title: "Where to write the batch results as CSV",
+                            "data-touch-tooltip": touch_tooltip("Where to write the batch results as CSV"),
+                        }
+                    }
+                }
+                div { class: "small-buttons",
+                    button {
+                        class: "action",
+                        title: if batch_running_value { "Abort the batch; lookups still running are dropped" } else { "Resolve every listed key with a few lookups in parallel" },
+                        "data-touch-tooltip": touch_tooltip(if batch_running_value {
+                            "Abort the batch; lookups still running are dropped"
+                        } else {
+                            "Resolve every listed key with a few lookups in parallel"
+                        }),
+                        onclick: move |_| {
+                            if batch_task.cancel() {
+                                batch_logs.info("Batch lookup cancelled");
+                                return;
+                            }
+                            let keys = parse_key_list(&batch_input_signal.read());
+                            if keys.is_empty() {
+                                batch_logs.error("Enter at least one public key");
+                                return;
+                            }
+                            let Some(pubky_arc) = batch_pubky.ready_or_log(&batch_logs) else {
+                                return;
+                            };
+                            let mut rows_signal = batch_rows_signal.clone();
+                            rows_signal.set(Vec::new());
+                            let logs_task = batch_logs.clone();
+                            let total = keys.len();
+                            logs_task.info(format!("Resolving {total} homeservers..."));
+                            batch_task.start(async move {
+                                let rows = resolve_homeservers(&pubky_arc, keys).await;
+                                let found = rows
+                                    .iter()
+                                    .filter(|row| matches!(row.outcome, BatchLookupOutcome::Homeserver(_)))
+                                    .count();
+                                rows_signal.set(rows);
+                                logs_task.success(format!("Batch lookup finished: {found}/{total} resolved"));
+                            });
+                        },
+                        if batch_running_value { "Cancel batch" } else { "Resolve all" }
+                    }
+                    button {
+                        class: "action secondary",
+                        disabled: batch_rows_value.is_empty(),
+                        title: "Save the results table as CSV",
+                        "data-touch-tooltip": touch_tooltip("Save the results table as CSV"),
+                        onclick: move |_| {
+                            let rows = export_rows.read().clone();
+                            if rows.is_empty() {
+                                export_logs.error("Run a batch lookup first");
+                                return;
+                            }
+                            let raw_path = export_path.read().clone();
+                            let chosen_path = if raw_path.trim().is_empty() {
+                                match file_dialog::save_file() {
+                                    FileDialogResult::Selected(path) => Some(path.display().to_string()),
+                                    FileDialogResult::Unavailable => {
+                                        export_logs.info(file_dialog::MANUAL_ENTRY_HINT);
+                                        None
+                                    }
+                                    FileDialogResult::Cancelled => None,
+                                }
+                            } else {
+                                Some(raw_path)
+                            };
+                            if let Some(selected_path) = chosen_path {
+                                let mut path_signal = export_path;
+                                match save_batch_csv(&rows, &selected_path) {
+                                    Ok(path) => {
+                                        path_signal.set(path.display().to_string());
+                                        export_logs.success(format!(
+                                            "Batch results saved to {}",
+                                            path.display()
+                                        ));
+                                    }
+                                    Err(err) => export_logs.error(format!(
+                                        "Failed to save batch results: {err}"
+                                    )),
+                                }
+                            }
+                        },
+                        "Export CSV",
+                    }
+                }
+                if let Some(summary) = batch_summary {
+                    p { class: "helper-text", "{summary}" }
+                    div { class: "table-scroll",
+                        table { class: "lookup-table",
+                            thead {
+                                tr {
+                                    th { "Public key" }
+                                    th { "Homeserver" }
+                                }
+                            }
+                            tbody {
+                                for row in batch_rows_value.iter() {
+                                    tr { key: "{row.public_key}", class: "lookup-{row.outcome.status()}",
+                                        td { class: "mono", "{row.public_key}" }
+                                        td { class: "mono", "{row.outcome.summary()}" }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
             section { class: "card",
                 h2 { "Publish homeserver" }
                 p { class: "helper-text", "Publish or refresh your `_pubky` record. Leave the override blank to reuse the current host." }
@@ -271,6 +591,12 @@ pub fn render_pkdns_tab(
                             let logs_task = publish_force_logs.clone();
                             let mut result_signal = publish_force_result_signal.clone();
                             spawn(async move {
+                                let action = format!("Replace the _pubky record of {}", kp.public_key());
+                                if !mainnet_guard.allow(&action).await {
+                                    result_signal.set(String::from("Force publish cancelled"));
+                                    logs_task.info("Force publish cancelled");
+                                    return;
+                                }
                                 let signer = pubky_arc.signer(kp.clone());
                                 let pkdns = signer.pkdns();
                                 let override_for_task = override_pk.clone();
@@ -313,6 +639,232 @@ pub fn render_pkdns_tab(
                         "Force publish",
                     }
                 }
+                div { class: "form-grid",
+                    label {
+                        "Republish every (minutes)"
+                        input {
+                            r#type: "number",
+                            min: "1",
+                            value: republish_minutes_value,
+                            disabled: republish_running,
+                            oninput: move |evt| republish_minutes_binding.set(evt.value()),
+                            title: "How often the schedule below republishes the record if stale",
+                            "data-touch-tooltip": touch_tooltip(
+                                "How often the schedule below republishes the record if stale",
+                            ),
+                        }
+                    }
+                }
+                label { class: "checkbox-row",
+                    input {
+                        r#type: "checkbox",
+                        checked: republish_running,
+                        onchange: move |evt| {
+                            if !evt.checked() {
+                                if republish_task.cancel() {
+                                    republish_logs.info("Scheduled republish stopped");
+                                }
+                                return;
+                            }
+                            if republish_keypair.read().is_none() {
+                                republish_logs.error("Load or generate a key first");
+                                return;
+                            }
+                            match parse_republish_minutes(&republish_minutes.read()) {
+                                Ok(minutes) => {
+                                    republish_logs.info(format!(
+                                        "Republishing _pubky if stale every {minutes} min while the app is open"
+                                    ));
+                                    start_republish_loop(
+                                        republish_pubky.clone(),
+                                        republish_keypair,
+                                        republish_override,
+                                        minutes,
+                                        republish_task,
+                                        republish_logs.clone(),
+                                    );
+                                }
+                                Err(message) => republish_logs.error(message),
+                            }
+                        },
+                    }
+                    span { "Keep the record warm: republish if stale on a schedule (follows the active key)" }
+                }
+            }
+            section { class: "card",
+                h2 { "Signed packets" }
+                p { class: "helper-text", "Dump the raw PKARR packet published for the key above (or the active key), archive it as a .pkarr file, or inspect a saved packet offline." }
+                div { class: "form-grid",
+                    label {
+                        "Packet file path"
+                        div { class: "file-picker-row",
+                            input {
+                                class: "file-path-display",
+                                value: packet_path_value.clone(),
+                                oninput: move |evt| packet_path_binding.set(evt.value()),
+                                placeholder: "Enter packet file path",
+                                title: "Where to save or load a signed packet (.pkarr)",
+                                "data-touch-tooltip": touch_tooltip(
+                                    "Where to save or load a signed packet (.pkarr)",
+                                ),
+                            }
+                        }
+                    }
+                }
+                div { class: "small-buttons",
+                    button {
+                        class: "action",
+                        title: "Resolve and dump the most recent signed packet for this key",
+                        "data-touch-tooltip": touch_tooltip(
+                            "Resolve and dump the most recent signed packet for this key",
+                        ),
+                        onclick: move |_| {
+                            let query = dump_lookup.read().clone();
+                            let trimmed = query.trim().to_string();
+                            let target_pk = if trimmed.is_empty() {
+                                match dump_keypair.read().as_ref() {
+                                    Some(kp) => kp.public_key(),
+                                    None => {
+                                        dump_logs.error("Enter a public key or load a key first");
+                                        return;
+                                    }
+                                }
+                            } else {
+                                match PublicKey::try_from(trimmed.as_str()) {
+                                    Ok(pk) => pk,
+                                    Err(err) => {
+                                        dump_logs.error(format!("Invalid public key: {err}"));
+                                        return;
+                                    }
+                                }
+                            };
+                            let Some(pubky_arc) = dump_pubky.ready_or_log(&dump_logs) else {
+                                return;
+                            };
+                            {
+                                let mut immediate = dump_output_signal.clone();
+                                immediate.set(String::from("Resolving signed packet..."));
+                            }
+                            let logs_task = dump_logs.clone();
+                            let mut packet_signal = dump_packet_signal.clone();
+                            let mut output_signal = dump_output_signal.clone();
+                            spawn(async move {
+                                match resolve_signed_packet(&pubky_arc, &target_pk).await {
+                                    Some(signed) => {
+                                        output_signal.set(format_packet_dump(&signed));
+                                        packet_signal.set(Some(signed));
+                                        logs_task.success(format!("Dumped signed packet for {target_pk}"));
+                                    }
+                                    None => {
+                                        packet_signal.set(None);
+                                        output_signal.set(format!("No signed packet found for {target_pk}"));
+                                        logs_task.info(format!("No signed packet found for {target_pk}"));
+                                    }
+                                }
+                            });
+                        },
+                        "Dump packet",
+                    }
+                    button {
+                        class: "action secondary",
+                        title: "Save the dumped packet bytes to a .pkarr file",
+                        "data-touch-tooltip": touch_tooltip(
+                            "Save the dumped packet bytes to a .pkarr file",
+                        ),
+                        onclick: move |_| {
+                            let Some(signed) = save_packet_signal.read().as_ref().cloned() else {
+                                save_packet_logs.error("Dump or load a packet first");
+                                return;
+                            };
+                            let raw_path = save_packet_path.read().clone();
+                            let chosen_path = if raw_path.trim().is_empty() {
+                                match file_dialog::save_file() {
+                                    FileDialogResult::Selected(path) => Some(path.display().to_string()),
+                                    FileDialogResult::Unavailable => {
+                                        save_packet_logs.info(file_dialog::MANUAL_ENTRY_HINT);
+                                        None
+                                    }
+                                    FileDialogResult::Cancelled => None,
+                                }
+                            } else {
+                                Some(raw_path)
+                            };
+                            if let Some(selected_path) = chosen_path {
+                                let mut path_signal = save_packet_path;
+                                match save_signed_packet(&signed, &selected_path) {
+                                    Ok(path) => {
+                                        path_signal.set(path.display().to_string());
+                                        save_packet_logs.success(format!(
+                                            "Signed packet saved to {}",
+                                            path.display()
+                                        ));
+                                    }
+                                    Err(err) => save_packet_logs.error(format!(
+                                        "Failed to save signed packet: {err}"
+                                    )),
+                                }
+                            }
+                        },
+                        "Save .pkarr",
+                    }
+                    button {
+                        class: "action secondary",
+                        title: "Load a saved .pkarr packet and inspect it without network access",
+                        "data-touch-tooltip": touch_tooltip(
+                            "Load a saved .pkarr packet and inspect it without network access",
+                        ),
+                        onclick: move |_| {
+                            let raw_path = load_packet_path.read().clone();
+                            let chosen_path = if raw_path.trim().is_empty() {
+                                match file_dialog::pick_file() {
+                                    FileDialogResult::Selected(path) => Some(path.display().to_string()),
+                                    FileDialogResult::Unavailable => {
+                                        load_packet_logs.info(file_dialog::MANUAL_ENTRY_HINT);
+                                        None
+                                    }
+                                    FileDialogResult::Cancelled => None,
+                                }
+                            } else {
+                                Some(raw_path)
+                            };
+                            if let Some(selected_path) = chosen_path {
+                                let mut path_signal = load_packet_path;
+                                let mut packet_signal = load_packet_signal;
+                                let mut output_signal = load_packet_output;
+                                let outcome = normalize_pkarr_path(&selected_path).and_then(|normalized| {
+                                    load_signed_packet(&normalized).map(|signed| (signed, normalized))
+                                });
+                                match outcome {
+                                    Ok((signed, normalized)) => {
+                                        path_signal.set(normalized.display().to_string());
+                                        output_signal.set(format_packet_dump(&signed));
+                                        load_packet_logs.success(format!(
+                                            "Loaded signed packet for {} from {}",
+                                            signed.public_key(),
+                                            normalized.display()
+                                        ));
+                                        packet_signal.set(Some(signed));
+                                    }
+                                    Err(err) => load_packet_logs.error(format!(
+                                        "Failed to load signed packet: {err}"
+                                    )),
+                                }
+                            }
+                        },
+                        "Load .pkarr",
+                    }
+                }
+                if !packet_dump_value.is_empty() {
+                    div {
+                        class: "outputs copyable",
+                        "data-touch-tooltip": touch_tooltip(
+                            "Tap to copy the packet dump",
+                        ),
+                        "data-touch-copy": touch_copy_option(packet_copy_value.clone()),
+                        "data-copy-success": packet_copy_success.clone(),
+                        {packet_dump_value}
+                    }
+                }
             }
         }
     }
@@ -1,11 +1,22 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
 use anyhow::anyhow;
 use dioxus::prelude::*;
-use pubky::PublicKey;
+use pubky::{PubkySession, PublicKey};
+use reqwest::Method;
+use url::Url;
 
+use crate::app::NetworkMode;
 use crate::tabs::{SessionsTabState, format_session_info};
+use crate::utils::errors::{AuthAttempt, describe_auth_error};
 use crate::utils::logging::ActivityLog;
 use crate::utils::mobile::{is_android_touch, touch_copy_option, touch_tooltip};
+use crate::utils::packet::resolve_signed_packet;
+use crate::utils::prefs::PrefsHandle;
 use crate::utils::pubky::PubkyFacadeHandle;
+use crate::utils::session::{
+    SessionExpiry, SessionHealth, SignupProgress, SignupStep, expiry_from_details, parse_invite,
+};
 
 #[allow(clippy::clone_on_copy)]
 pub fn render_sessions_tab(
@@ -16,14 +27,38 @@ pub fn render_sessions_tab(
     let SessionsTabState {
         keypair,
         session,
+        session_health,
         details,
         homeserver,
         signup_code,
+        signup_progress,
+        invite_input,
+        signup_task,
+        prefs,
+        network_mode,
     } = state;
 
     let homeserver_value = { homeserver.read().clone() };
     let signup_value = { signup_code.read().clone() };
+    let invite_value = { invite_input.read().clone() };
     let details_value = { details.read().clone() };
+    let has_session = session.read().is_some();
+    let session_expired = *session_health.read() == SessionHealth::Expired;
+    let progress_value = { signup_progress.read().clone() };
+    let signup_running = signup_task.is_running();
+    let signup_steps: Vec<(&'static str, &'static str)> = if progress_value == SignupProgress::Idle
+    {
+        Vec::new()
+    } else {
+        SignupStep::ALL
+            .iter()
+            .map(|step| (step.label(), progress_value.step_state(*step)))
+            .collect()
+    };
+    let signup_failure = match &progress_value {
+        SignupProgress::Failed { message, .. } => Some(message.clone()),
+        _ => None,
+    };
     let details_copy_value = if details_value.trim().is_empty() {
         None
     } else {
@@ -37,12 +72,19 @@ pub fn render_sessions_tab(
 
     let mut homeserver_binding = homeserver.clone();
     let mut signup_binding = signup_code.clone();
+    let mut invite_binding = invite_input.clone();
+
+    let invite_source = invite_input.clone();
+    let mut invite_homeserver = homeserver.clone();
+    let mut invite_signup_code = signup_code.clone();
+    let invite_logs = logs.clone();
 
     let signup_keypair = keypair.clone();
     let signup_homeserver = homeserver.clone();
     let signup_code_signal = signup_code.clone();
     let signup_session_signal = session.clone();
     let signup_details_signal = details.clone();
+    let signup_progress_signal = signup_progress.clone();
     let signup_logs = logs.clone();
     let signup_pubky = pubky.clone();
 
@@ -51,8 +93,11 @@ pub fn render_sessions_tab(
     let signin_details_signal = details.clone();
     let signin_logs = logs.clone();
     let signin_pubky = pubky.clone();
+    let signin_homeserver = homeserver.clone();
+    let signin_signup_code = signup_code.clone();
 
     let revalidate_session_signal = session.clone();
+    let revalidate_health_signal = session_health.clone();
     let revalidate_details_signal = details.clone();
     let revalidate_logs = logs.clone();
 
@@ -64,7 +109,45 @@ pub fn render_sessions_tab(
         div { class: "tab-body single-column",
             section { class: "card",
                 h2 { "Session lifecycle" }
+                if session_expired {
+                    p { class: "helper-text warning-text",
+                        "The loaded session has expired or was revoked. Sign in or sign up again to refresh it."
+                    }
+                }
                 div { class: "form-grid",
+                    label {
+                        "Invite (optional)"
+                        div { class: "file-picker-row",
+                            input {
+                                value: invite_value,
+                                oninput: move |evt| invite_binding.set(evt.value()),
+                                placeholder: "pubky-invite:…",
+                                title: "Paste an invite from a homeserver operator to fill in the homeserver and signup code",
+                                "data-touch-tooltip": touch_tooltip(
+                                    "Paste an invite from a homeserver operator to fill in the homeserver and signup code",
+                                ),
+                            }
+                            button {
+                                class: "action secondary",
+                                title: "Fill the homeserver key and signup code from the invite",
+                                "data-touch-tooltip": touch_tooltip(
+                                    "Fill the homeserver key and signup code from the invite",
+                                ),
+                                onclick: move |_| {
+                                    let raw = invite_source.read().clone();
+                                    match parse_invite(&raw) {
+                                        Ok(invite) => {
+                                            invite_homeserver.set(invite.homeserver);
+                                            invite_signup_code.set(invite.signup_code);
+                                            invite_logs.info("Filled homeserver and signup code from the invite");
+                                        }
+                                        Err(err) => invite_logs.error(format!("Invalid invite: {err}")),
+                                    }
+                                },
+                                "Use invite"
+                            }
+                        }
+                    }
                     label {
                         "Homeserver public key"
                         input {
@@ -90,50 +173,101 @@ pub fn render_sessions_tab(
                 }
                 div { class: "small-buttons",
                     button {
-                        class: "action",
-                        title: "Create a new session on this homeserver with the loaded key",
-                        "data-touch-tooltip": touch_tooltip(
-                            "Create a new session on this homeserver with the loaded key",
-                        ),
+                        class: if signup_running { "action secondary" } else { "action" },
+                        title: if signup_running { "Abort the signup that is still in progress" } else { "Create a new session on this homeserver with the loaded key" },
+                        "data-touch-tooltip": touch_tooltip(if signup_running {
+                            "Abort the signup that is still in progress"
+                        } else {
+                            "Create a new session on this homeserver with the loaded key"
+                        }),
                         onclick: move |_| {
+                            if signup_task.cancel() {
+                                let mut progress_signal = signup_progress_signal.clone();
+                                progress_signal.set(SignupProgress::Idle);
+                                signup_logs.info("Signup cancelled");
+                                return;
+                            }
                             if let Some(kp) = signup_keypair.read().as_ref().cloned() {
                                 let homeserver = signup_homeserver.read().clone();
                                 if homeserver.trim().is_empty() {
                                     signup_logs.error("Homeserver public key is required");
                                     return;
                                 }
-                                let signup_code_value = signup_code_signal.read().clone();
+                                let signup_code_value = signup_code_signal.read().trim().to_string();
                                 let Some(pubky) = signup_pubky.ready_or_log(&signup_logs) else {
                                     return;
                                 };
                                 let mut session_signal = signup_session_signal.clone();
                                 let mut details_signal = signup_details_signal.clone();
+                                let mut progress_signal = signup_progress_signal.clone();
                                 let logs_task = signup_logs.clone();
-                                spawn(async move {
-                                    let result = async move {
-                                        let homeserver_pk = PublicKey::try_from(homeserver.as_str())
-                                            .map_err(|e| anyhow!("Invalid homeserver key: {e}"))?;
-                                        let signer = pubky.signer(kp.clone());
-                                        let code = if signup_code_value.trim().is_empty() {
-                                            None
-                                        } else {
-                                            Some(signup_code_value.as_str())
-                                        };
-                                        let session = signer.signup(&homeserver_pk, code).await?;
-                                        session_signal.set(Some(session.clone()));
-                                        details_signal.set(format_session_info(session.info()));
-                                        Ok::<_, anyhow::Error>(format!("Signed up as {}", session.info().public_key()))
+                                progress_signal.set(SignupProgress::Running(SignupStep::Resolving));
+                                signup_task.start(async move {
+                                    let attempt = AuthAttempt::Signup {
+                                        with_code: !signup_code_value.is_empty(),
                                     };
-                                    match result.await {
-                                        Ok(msg) => logs_task.success(msg),
-                                        Err(err) => logs_task.error(format!("Signup failed: {err}")),
+                                    let outcome = async {
+                                        let homeserver_pk = PublicKey::try_from(homeserver.trim())
+                                            .map_err(|e| {
+                                                (SignupStep::Resolving, anyhow!("Invalid homeserver key: {e}"))
+                                            })?;
+                                        if resolve_signed_packet(&pubky, &homeserver_pk).await.is_none() {
+                                            return Err((
+                                                SignupStep::Resolving,
+                                                anyhow!("could not resolve homeserver {homeserver_pk}: no PKARR record found"),
+                                            ));
+                                        }
+
+                                        progress_signal.set(SignupProgress::Running(SignupStep::Connecting));
+                                        let url = Url::parse(&format!("https://{homeserver_pk}/"))
+                                            .map_err(|err| (SignupStep::Connecting, anyhow::Error::from(err)))?;
+                                        pubky
+                                            .client()
+                                            .request(Method::GET, url)
+                                            .send()
+                                            .await
+                                            .map_err(|err| (SignupStep::Connecting, anyhow::Error::from(err)))?;
+
+                                        progress_signal.set(SignupProgress::Running(SignupStep::SigningUp));
+                                        let code = (!signup_code_value.is_empty())
+                                            .then_some(signup_code_value.as_str());
+                                        pubky
+                                            .signer(kp.clone())
+                                            .signup(&homeserver_pk, code)
+                                            .await
+                                            .map_err(|err| (SignupStep::SigningUp, anyhow::Error::from(err)))
+                                    }
+                                    .await;
+                                    match outcome {
+                                        Ok(session) => {
+                                            details_signal.set(format_session_info(session.info()));
+                                            let public_key = session.info().public_key().to_string();
+                                            session_signal.set(Some(session));
+                                            progress_signal.set(SignupProgress::Succeeded);
+                                            remember_session_fields(
+                                                prefs,
+                                                network_mode,
+                                                &homeserver,
+                                                &signup_code_value,
+                                                &logs_task,
+                                            );
+                                            logs_task.success(format!("Signed up as {public_key}"));
+                                        }
+                                        Err((step, err)) => {
+                                            let message = describe_auth_error("Signup", attempt, &err);
+                                            progress_signal.set(SignupProgress::Failed {
+                                                step,
+                                                message: message.clone(),
+                                            });
+                                            logs_task.error(message);
+                                        }
                                     }
                                 });
                             } else {
                                 signup_logs.error("Load or generate a key first");
                             }
                         },
-                        "Sign up"
+                        if signup_running { "Cancel signup" } else { "Sign up" }
                     }
                     button {
                         class: "action secondary",
@@ -149,6 +283,8 @@ pub fn render_sessions_tab(
                                 let mut session_signal = signin_session_signal.clone();
                                 let mut details_signal = signin_details_signal.clone();
                                 let logs_task = signin_logs.clone();
+                                let homeserver = signin_homeserver.read().clone();
+                                let signup_code_value = signin_signup_code.read().clone();
                                 spawn(async move {
                                     let result = async move {
                                         let signer = pubky.signer(kp.clone());
@@ -161,8 +297,21 @@ pub fn render_sessions_tab(
                                         ))
                                     };
                                     match result.await {
-                                        Ok(msg) => logs_task.success(msg),
-                                        Err(err) => logs_task.error(format!("Signin (root) failed: {err}")),
+                                        Ok(msg) => {
+                                            remember_session_fields(
+                                                prefs,
+                                                network_mode,
+                                                &homeserver,
+                                                &signup_code_value,
+                                                &logs_task,
+                                            );
+                                            logs_task.success(msg);
+                                        }
+                                        Err(err) => logs_task.error(describe_auth_error(
+                                            "Signin (root)",
+                                            AuthAttempt::Signin,
+                                            &err,
+                                        )),
                                     }
                                 });
                             } else {
@@ -181,10 +330,12 @@ pub fn render_sessions_tab(
                             if let Some(session) = revalidate_session_signal.read().as_ref().cloned() {
                                 let mut session_signal = revalidate_session_signal.clone();
                                 let mut details_signal = revalidate_details_signal.clone();
+                                let mut health_signal = revalidate_health_signal.clone();
                                 let logs_task = revalidate_logs.clone();
                                 spawn(async move {
                                     match session.revalidate().await {
                                         Ok(Some(info)) => {
+                                            health_signal.set(SessionHealth::Valid);
                                             details_signal.set(format_session_info(&info));
                                             logs_task.success("Session still valid");
                                         }
@@ -236,6 +387,19 @@ pub fn render_sessions_tab(
                         "Sign out"
                     }
                 }
+                if !signup_steps.is_empty() {
+                    ol { class: "signup-steps",
+                        for (label, step_state) in signup_steps {
+                            li { class: step_state, "{label}" }
+                        }
+                    }
+                }
+                if let Some(message) = signup_failure {
+                    p { class: "helper-text warning-text", "{message}" }
+                }
+                if has_session {
+                    SessionExpiryReadout { session, details, logs: logs.clone() }
+                }
                 if !details_value.is_empty() {
                     div {
                         class: "outputs copyable",
@@ -251,3 +415,87 @@ pub fn render_sessions_tab(
         }
     }
 }
+
+/// Save the Sessions fields for the next launch. Testnet has its own prefill,
+/// so only mainnet values are kept.
+fn remember_session_fields(
+    prefs: PrefsHandle,
+    network_mode: Signal<NetworkMode>,
+    homeserver: &str,
+    signup_code: &str,
+    logs: &ActivityLog,
+) {
+    if *network_mode.peek() == NetworkMode::Mainnet {
+        prefs.update(logs, |stored| {
+            stored.session.remember(homeserver, signup_code)
+        });
+    }
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or_default()
+}
+
+/// "Expires in" line for the loaded session. The countdown only runs when
+/// the session details carry an expiry.
+#[component]
+fn SessionExpiryReadout(
+    session: Signal<Option<PubkySession>>,
+    details: Signal<String>,
+    logs: ActivityLog,
+) -> Element {
+    match expiry_from_details(&details.read()) {
+        Some(expires_at) => rsx! {
+            SessionCountdown { key: "{expires_at}", expires_at, session, details, logs }
+        },
+        None => rsx! {
+            p { class: "helper-text session-expiry", "{SessionExpiry::Unknown.label()}" }
+        },
+    }
+}
+
+/// Ticks every second until `expires_at`, then drops the session.
+#[component]
+fn SessionCountdown(
+    expires_at: u64,
+    session: Signal<Option<PubkySession>>,
+    details: Signal<String>,
+    logs: ActivityLog,
+) -> Element {
+    let mut now = use_signal(unix_now);
+    let mut expired_session = session;
+    let mut expired_details = details;
+    let tick_logs = logs.clone();
+    use_future(move || {
+        let logs = tick_logs.clone();
+        async move {
+            loop {
+                tokio::time::sleep(Duration::from_secs(1)).await;
+                let current = unix_now();
+                now.set(current);
+                if SessionExpiry::at(Some(expires_at), current) == SessionExpiry::Expired {
+                    if expired_session.peek().is_some() {
+                        expired_session.set(None);
+                        expired_details.set(String::new());
+                        logs.warn("Session expired; sign in again to continue");
+                    }
+                    break;
+                }
+            }
+        }
+    });
+
+    let expiry = SessionExpiry::at(Some(expires_at), *now.read());
+    let class = if expiry.is_urgent() {
+        "helper-text session-expiry urgent"
+    } else {
+        "helper-text session-expiry"
+    };
+
+    rsx! {
+        p { class, "{expiry.label()}" }
+    }
+}
@@ -0,0 +1,553 @@
+use dioxus::prelude::*;
+
+use crate::app::NetworkMode;
+use crate::components::ConfirmButton;
+use crate::tabs::SettingsTabState;
+use crate::utils::diagnostics::{DiagnosticsSnapshot, format_diagnostics, save_diagnostics};
+use crate::utils::environments::HttpEnvironment;
+use crate::utils::file_dialog::{self, FileDialogResult};
+use crate::utils::logging::ActivityLog;
+use crate::utils::mobile::touch_tooltip;
+use crate::utils::prefs::{NetworkDefaults, OverwriteCheck, PrefsHandle, SavedInputs};
+use crate::utils::pubky::{FACADE_RETRY_BASE_DELAY, MAX_FACADE_RETRIES, PubkyFacadeHandle};
+
+#[allow(clippy::clone_on_copy)]
+pub fn render_settings_tab(
+    pubky: PubkyFacadeHandle,
+    state: SettingsTabState,
+    logs: ActivityLog,
+) -> Element {
+    let SettingsTabState {
+        prefs,
+        mainnet_capabilities,
+        mainnet_storage_path,
+        testnet_capabilities,
+        testnet_storage_path,
+        network_mode,
+        session,
+        session_health,
+        log_entries,
+        diagnostics_path,
+        input_fields,
+        environment_name,
+        environment_variables,
+    } = state;
+
+    let safety = prefs.snapshot().safety;
+    let confirm_destructive = safety.confirm_mainnet_destructive;
+    let allow_secret_export = safety.allow_secret_export;
+    let overwrite_check = safety.overwrite_check;
+    let overwrite_logs = logs.clone();
+    let show_export_warning = safety.allow_secret_export && !safety.secret_export_warning_seen;
+    let safety_logs = logs.clone();
+    let export_logs = logs.clone();
+    let warning_logs = logs.clone();
+
+    let inputs_prefs = prefs.snapshot().inputs;
+    let remember_inputs = inputs_prefs.enabled;
+    let has_saved_inputs = inputs_prefs.saved.is_some();
+    let remember_logs = logs.clone();
+    let clear_inputs_logs = logs.clone();
+
+    let auto_retry_facade = prefs.snapshot().auto_retry_facade;
+    let auto_retry_logs = logs.clone();
+
+    let diagnostics_path_value = diagnostics_path.read().clone();
+    let mut diagnostics_path_binding = diagnostics_path.clone();
+    let diagnostics_logs = logs.clone();
+
+    rsx! {
+        div { class: "tab-body",
+            {render_network_defaults_card(
+                NetworkMode::Mainnet,
+                prefs,
+                mainnet_capabilities,
+                mainnet_storage_path,
+                logs.clone(),
+            )}
+            {render_network_defaults_card(
+                NetworkMode::Testnet,
+                prefs,
+                testnet_capabilities,
+                testnet_storage_path,
+                logs.clone(),
+            )}
+            section { class: "card",
+                h2 { "Safety" }
+                label { class: "checkbox-row",
+                    input {
+                        r#type: "checkbox",
+                        checked: confirm_destructive,
+                        onchange: move |evt| {
+                            let enabled = evt.checked();
+                            prefs.update(&safety_logs, |prefs| {
+                                prefs.safety.confirm_mainnet_destructive = enabled;
+                            });
+                        },
+                    }
+                    span { "Confirm destructive mainnet actions" }
+                }
+                p { class: "helper-text",
+                    "Ask before storage deletes and writes, profile overwrites, and forced _pubky publishes while on Mainnet. Testnet actions never prompt."
+                }
+                label { class: "inline-field",
+                    "Check for an existing entry before PUT"
+                    select {
+                        value: overwrite_check.key(),
+                        title: "Look up the storage path first and warn before replacing what is there",
+                        "data-touch-tooltip": touch_tooltip(
+                            "Look up the storage path first and warn before replacing what is there",
+                        ),
+                        onchange: move |evt| {
+                            if let Some(check) = OverwriteCheck::from_key(&evt.value()) {
+                                prefs.update(&overwrite_logs, |prefs| {
+                                    prefs.safety.overwrite_check = check;
+                                });
+                            }
+                        },
+                        for option in OverwriteCheck::ALL {
+                            option {
+                                value: option.key(),
+                                selected: option == overwrite_check,
+                                "{option.label()}"
+                            }
+                        }
+                    }
+                }
+                p { class: "helper-text",
+                    "A Storage tab PUT first fetches the path and asks before overwriting an existing entry. Leave it off for Testnet to keep quick iterations to a single request."
+                }
+                label { class: "checkbox-row",
+                    input {
+                        r#type: "checkbox",
+                        checked: allow_secret_export,
+                        onchange: move |evt| {
+                            let enabled = evt.checked();
+                            prefs.update(&export_logs, |prefs| {
+                                prefs.safety.allow_secret_export = enabled;
+                            });
+                            if enabled {
+                                export_logs.info("Secret key export enabled");
+                            } else {
+                                export_logs.info("Secret key export disabled");
+                            }
+                        },
+                    }
+                    span { "Allow exporting secret material" }
+                }
+                p { class: "helper-text",
+                    "Off by default. While off, Show secret key and Reveal secret in the Keys tab are disabled. Encrypted recovery files and saved accounts are not affected."
+                }
+                if show_export_warning {
+                    div { class: "secret-export-warning",
+                        strong { "Secret keys can now leave the app." }
+                        p {
+                            "Anyone who sees or copies a revealed secret key controls that identity for good, and it cannot be revoked. Reveal secrets only on a trusted screen, never paste them into chats or websites, and turn this off again when you are done."
+                        }
+                        button {
+                            class: "action secondary",
+                            onclick: move |_| {
+                                prefs.update(&warning_logs, |prefs| {
+                                    prefs.safety.secret_export_warning_seen = true;
+                                });
+                            },
+                            "I understand"
+                        }
+                    }
+                }
+            }
+            section { class: "card",
+                h2 { "Saved inputs" }
+                label { class: "checkbox-row",
+                    input {
+                        r#type: "checkbox",
+                        checked: remember_inputs,
+                        onchange: move |evt| {
+                            let enabled = evt.checked();
+                            prefs.update(&remember_logs, |prefs| {
+                                prefs.inputs.enabled = enabled;
+                                if !enabled {
+                                    prefs.inputs.saved = None;
+                                }
+                            });
+                            if enabled {
+                                remember_logs.info("Tab inputs will be restored on next launch");
+                            } else {
+                                remember_logs.info("Stopped remembering tab inputs");
+                            }
+                        },
+                    }
+                    span { "Remember tab inputs between launches" }
+                }
+                p { class: "helper-text",
+                    "Saves the Raw Requests method, URL, and headers, the storage path, the token and auth capabilities, and the PKDNS lookup, host override, and batch list. Secret keys, passphrases, signup codes, request bodies, and credential headers such as Authorization or Cookie are never saved."
+                }
+                div { class: "small-buttons",
+                    ConfirmButton {
+                        label: "Clear saved inputs",
+                        confirm_label: "Confirm clear?",
+                        danger: true,
+                        disabled: !remember_inputs && !has_saved_inputs,
+                        title: "Forget the saved inputs and reset those fields (click twice to confirm)",
+                        on_confirm: move |_| {
+                            let defaults = prefs
+                                .snapshot()
+                                .defaults
+                                .for_network(*network_mode.read())
+                                .clone();
+                            prefs.update(&clear_inputs_logs, |prefs| prefs.inputs.saved = None);
+                            input_fields.fill(&SavedInputs::from_defaults(&defaults));
+                            clear_inputs_logs.info("Cleared saved tab inputs");
+                        },
+                    }
+                }
+            }
+            section { class: "card",
+                h2 { "Pubky client" }
+                label { class: "checkbox-row",
+                    input {
+                        r#type: "checkbox",
+                        checked: auto_retry_facade,
+                        onchange: move |evt| {
+                            let enabled = evt.checked();
+                            prefs.update(&auto_retry_logs, |prefs| {
+                                prefs.auto_retry_facade = enabled;
+                            });
+                        },
+                    }
+                    span { "Retry a failed client start automatically" }
+                }
+                p { class: "helper-text",
+                    "When the Pubky client fails to start, try again up to {MAX_FACADE_RETRIES} times, waiting {FACADE_RETRY_BASE_DELAY.as_secs()}s and doubling each time, before showing the error. Useful while a local testnet is still starting. Switching networks cancels pending retries."
+                }
+            }
+            {render_environments_card(
+                prefs,
+                environment_name,
+                environment_variables,
+                logs.clone(),
+            )}
+            section { class: "card",
+                h2 { "Diagnostics" }
+                p { class: "helper-text",
+                    "Collect the network, Pubky facade and session status, app version, saved defaults, and the activity log into one text file to attach to bug reports. Keys, tokens, and passphrases are never included."
+                }
+                div { class: "form-grid",
+                    label {
+                        "Save to"
+                        input {
+                            value: diagnostics_path_value,
+                            oninput: move |evt| diagnostics_path_binding.set(evt.value()),
+                            placeholder: "Leave empty to choose a file",
+                        }
+                    }
+                }
+                div { class: "small-buttons",
+                    button {
+                        class: "action",
+                        title: "Write a diagnostics report for bug reports",
+                        "data-touch-tooltip": touch_tooltip("Write a diagnostics report for bug reports"),
+                        onclick: move |_| {
+                            let raw_path = diagnostics_path.read().clone();
+                            let chosen_path = if raw_path.trim().is_empty() {
+                                match file_dialog::save_file() {
+                                    FileDialogResult::Selected(path) => Some(path.display().to_string()),
+                                    FileDialogResult::Unavailable => {
+                                        diagnostics_logs.info(file_dialog::MANUAL_ENTRY_HINT);
+                                        None
+                                    }
+                                    FileDialogResult::Cancelled => None,
+                                }
+                            } else {
+                                Some(raw_path)
+                            };
+                            let Some(selected_path) = chosen_path else {
+                                return;
+                            };
+                            let prefs_snapshot = prefs.snapshot();
+                            let facade_snapshot = pubky.snapshot();
+                            let entries = log_entries.read().clone();
+                            let report = format_diagnostics(&DiagnosticsSnapshot {
+                                network: *network_mode.read(),
+                                facade: &facade_snapshot,
+                                session_present: session.read().is_some(),
+                                session_health: *session_health.read(),
+                                prefs: &prefs_snapshot,
+                                logs: &entries,
+                            });
+                            let mut path_signal = diagnostics_path;
+                            match save_diagnostics(&report, &selected_path) {
+                                Ok(path) => {
+                                    path_signal.set(path.display().to_string());
+                                    diagnostics_logs.success(format!(
+                                        "Diagnostics saved to {}",
+                                        path.display()
+                                    ));
+                                }
+                                Err(err) => diagnostics_logs.error(format!(
+                                    "Failed to save diagnostics: {err}"
+                                )),
+                            }
+                        },
+                        "Generate diagnostics"
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[allow(clippy::clone_on_copy)]
+fn render_environments_card(
+    prefs: PrefsHandle,
+    name: Signal<String>,
+    variables: Signal<String>,
+    logs: ActivityLog,
+) -> Element {
+    let environment_prefs = prefs.snapshot().environments;
+    let name_value = name.read().clone();
+    let variables_value = variables.read().clone();
+    let editing_existing = environment_prefs
+        .environments
+        .iter()
+        .any(|env| env.name == name_value.trim());
+    let rows: Vec<(HttpEnvironment, bool)> = environment_prefs
+        .environments
+        .iter()
+        .map(|env| {
+            let is_active = environment_prefs.active.as_deref() == Some(env.name.as_str());
+            (env.clone(), is_active)
+        })
+        .collect();
+
+    let mut name_binding = name.clone();
+    let mut variables_binding = variables.clone();
+
+    let save_name = name.clone();
+    let save_variables = variables.clone();
+    let save_logs = logs.clone();
+
+    let mut delete_name = name.clone();
+    let mut delete_variables = variables.clone();
+    let delete_logs = logs.clone();
+
+    rsx! {
+        section { class: "card",
+            h2 { "HTTP environments" }
+            p { class: "helper-text",
+                "Named sets of variables for the Raw Requests tab. Write {{{{name}}}} in the URL, headers, or body and the active environment's value is substituted when the request is sent. Values are stored unencrypted with the other preferences, so keep secrets out of them."
+            }
+            if rows.is_empty() {
+                p { class: "helper-text", "No environments yet." }
+            } else {
+                ul { class: "account-list",
+                    for (env, is_active) in rows {
+                        li {
+                            key: "{env.name}",
+                            class: if is_active { "account-row active" } else { "account-row" },
+                            div { class: "account-meta",
+                                strong { "{env.name}" }
+                                span { class: "helper-text", "{env.variables.len()} variables" }
+                            }
+                            div { class: "small-buttons",
+                                button {
+                                    class: "action secondary",
+                                    title: "Load this environment into the editor below",
+                                    "data-touch-tooltip": touch_tooltip(
+                                        "Load this environment into the editor below",
+                                    ),
+                                    onclick: {
+                                        let env = env.clone();
+                                        let mut name_signal = name;
+                                        let mut variables_signal = variables;
+                                        move |_| {
+                                            name_signal.set(env.name.clone());
+                                            variables_signal.set(env.variables_text());
+                                        }
+                                    },
+                                    "Edit"
+                                }
+                                button {
+                                    class: "action secondary",
+                                    title: "Substitute this environment's variables in Raw Requests",
+                                    "data-touch-tooltip": touch_tooltip(
+                                        "Substitute this environment's variables in Raw Requests",
+                                    ),
+                                    onclick: {
+                                        let env_name = env.name.clone();
+                                        let activate_logs = logs.clone();
+                                        move |_| {
+                                            let selected = (!is_active).then(|| env_name.clone());
+                                            prefs.update(&activate_logs, |prefs| {
+                                                prefs.environments.active = selected;
+                                            });
+                                        }
+                                    },
+                                    if is_active { "Deactivate" } else { "Activate" }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+            div { class: "form-grid",
+                label {
+                    "Environment name"
+                    input {
+                        value: name_value,
+                        oninput: move |evt| name_binding.set(evt.value()),
+                        placeholder: "testnet",
+                        title: "Saving under an existing name replaces that environment",
+                        "data-touch-tooltip": touch_tooltip(
+                            "Saving under an existing name replaces that environment",
+                        ),
+                    }
+                }
+                label {
+                    "Variables (one per line, name = value)"
+                    textarea {
+                        class: "tall",
+                        value: variables_value,
+                        oninput: move |evt| variables_binding.set(evt.value()),
+                        placeholder: "base = http://localhost:6286",
+                        title: "Letters, digits, _ and - in names; lines starting with # are ignored",
+                        "data-touch-tooltip": touch_tooltip(
+                            "Letters, digits, _ and - in names; lines starting with # are ignored",
+                        ),
+                    }
+                }
+            }
+            div { class: "small-buttons",
+                button {
+                    class: "action",
+                    title: "Validate and remember this environment",
+                    "data-touch-tooltip": touch_tooltip("Validate and remember this environment"),
+                    onclick: move |_| {
+                        let parsed = HttpEnvironment::parse(
+                            &save_name.read(),
+                            &save_variables.read(),
+                        );
+                        match parsed {
+                            Ok(environment) => {
+                                let label = environment.name.clone();
+                                prefs.update(&save_logs, |prefs| {
+                                    prefs.environments.upsert(environment);
+                                });
+                                save_logs.success(format!("Saved environment {label}"));
+                            }
+                            Err(err) => save_logs.error(format!("Environment not saved: {err}")),
+                        }
+                    },
+                    "Save environment"
+                }
+                ConfirmButton {
+                    label: "Delete",
+                    confirm_label: "Confirm delete?",
+                    danger: true,
+                    disabled: !editing_existing,
+                    title: "Forget the environment named above (click twice to confirm)",
+                    on_confirm: move |_| {
+                        let target = delete_name.read().trim().to_string();
+                        prefs.update(&delete_logs, |prefs| prefs.environments.remove(&target));
+                        delete_name.set(String::new());
+                        delete_variables.set(String::new());
+                        delete_logs.info(format!("Deleted environment {target}"));
+                    },
+                }
+            }
+        }
+    }
+}
+
+#[allow(clippy::clone_on_copy)]
+fn render_network_defaults_card(
+    mode: NetworkMode,
+    prefs: PrefsHandle,
+    capabilities: Signal<String>,
+    storage_path: Signal<String>,
+    logs: ActivityLog,
+) -> Element {
+    let caps_value = capabilities.read().clone();
+    let path_value = storage_path.read().clone();
+
+    let mut caps_binding = capabilities.clone();
+    let mut path_binding = storage_path.clone();
+
+    let save_caps = capabilities.clone();
+    let mut save_path = storage_path.clone();
+    let save_logs = logs.clone();
+
+    let mut reset_caps = capabilities.clone();
+    let mut reset_path = storage_path.clone();
+    let reset_logs = logs.clone();
+
+    rsx! {
+        section { class: "card",
+            h2 { "{mode.label()} defaults" }
+            p { class: "helper-text",
+                "Starting values for the token, auth, and storage tools. They are applied at launch and whenever you switch to {mode.label()}, unless you have already edited the field."
+            }
+            div { class: "form-grid",
+                label {
+                    "Default capabilities"
+                    input {
+                        value: caps_value,
+                        oninput: move |evt| caps_binding.set(evt.value()),
+                        title: "Capability string prefilled in the token and auth tabs",
+                        "data-touch-tooltip": touch_tooltip(
+                            "Capability string prefilled in the token and auth tabs",
+                        ),
+                    }
+                }
+                label {
+                    "Default storage path"
+                    input {
+                        value: path_value,
+                        oninput: move |evt| path_binding.set(evt.value()),
+                        title: "Directory under /pub/ prefilled in the storage tab",
+                        "data-touch-tooltip": touch_tooltip(
+                            "Directory under /pub/ prefilled in the storage tab",
+                        ),
+                    }
+                }
+            }
+            div { class: "small-buttons",
+                button {
+                    class: "action",
+                    title: "Validate and remember these defaults",
+                    "data-touch-tooltip": touch_tooltip("Validate and remember these defaults"),
+                    onclick: move |_| {
+                        let caps = save_caps.read().clone();
+                        let path = save_path.read().clone();
+                        match NetworkDefaults::validated(&caps, &path) {
+                            Ok(defaults) => {
+                                save_path.set(defaults.storage_path.clone());
+                                prefs.update(&save_logs, |prefs| {
+                                    *prefs.defaults.for_network_mut(mode) = defaults;
+                                });
+                                save_logs.success(format!("Saved {} defaults", mode.label()));
+                            }
+                            Err(err) => save_logs.error(format!("Defaults not saved: {err}")),
+                        }
+                    },
+                    "Save defaults"
+                }
+                ConfirmButton {
+                    label: "Reset",
+                    confirm_label: "Confirm reset?",
+                    danger: true,
+                    title: "Restore the built-in defaults (click twice to confirm)",
+                    on_confirm: move |_| {
+                        let defaults = NetworkDefaults::default();
+                        reset_caps.set(defaults.capabilities.clone());
+                        reset_path.set(defaults.storage_path.clone());
+                        prefs.update(&reset_logs, |prefs| {
+                            *prefs.defaults.for_network_mut(mode) = defaults;
+                        });
+                        reset_logs.info(format!("Restored built-in {} defaults", mode.label()));
+                    },
+                }
+            }
+        }
+    }
+}
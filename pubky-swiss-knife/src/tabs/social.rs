@@ -3,25 +3,37 @@ use std::str::FromStr;
 use anyhow::anyhow;
 use dioxus::prelude::*;
 use pubky_app_specs::{
-    PubkyAppPost, PubkyAppPostEmbed, PubkyAppPostKind, PubkyAppTag, PubkyAppUser, PubkyAppUserLink,
-    traits::{HasIdPath, HasPath, HashId, TimestampId, Validatable},
+    PubkyAppPost, PubkyAppPostEmbed, PubkyAppPostKind, PubkyAppUser, PubkyAppUserLink,
+    traits::{HasIdPath, HasPath, TimestampId, Validatable},
 };
 use serde_json::to_string_pretty;
 
+use crate::components::{ConfirmButton, ResponseCopyButtons};
 use crate::tabs::SocialTabState;
+use crate::utils::export::{export_pubky_app_data, save_export};
+use crate::utils::file_dialog::{self, FileDialogResult};
 use crate::utils::http::{format_response, format_response_parts};
+use crate::utils::links::open_external_link;
 use crate::utils::logging::ActivityLog;
+use crate::utils::markdown::{render_markdown, supports_markdown_preview};
 use crate::utils::mobile::{is_android_touch, touch_copy_option, touch_tooltip};
+use crate::utils::posts::{ContentLength, composer_fields};
+use crate::utils::profile::{LinkLineCheck, ProfileAvatar, ProfileView, check_link_lines};
 use crate::utils::pubky::PubkyFacadeHandle;
+use crate::utils::tags::{
+    BulkTagOutcome, MAX_CONCURRENT_TAG_WRITES, RemoveTagOutcome, TAGS_DIRECTORY, apply_bulk_tags,
+    bulk_pairs, list_session_tags, parse_label_list, parse_uri_list, prepare_tag, remove_tag,
+};
 
 #[allow(clippy::too_many_arguments, clippy::clone_on_copy)]
 pub fn render_social_tab(
-    _pubky: PubkyFacadeHandle,
+    pubky: PubkyFacadeHandle,
     state: SocialTabState,
     logs: ActivityLog,
 ) -> Element {
     let SocialTabState {
         session,
+        session_health,
         profile_name,
         profile_bio,
         profile_image,
@@ -29,39 +41,88 @@ pub fn render_social_tab(
         profile_links,
         profile_error,
         profile_response,
+        profile_view,
+        profile_avatar_failed,
         post_content,
+        post_preview,
         post_kind,
         post_parent,
         post_embed_kind,
         post_embed_uri,
         post_attachments,
         post_response,
+        post_load_uri,
         tag_uri,
         tag_label,
         tag_response,
+        bulk_tag_uris,
+        bulk_tag_labels,
+        bulk_tag_rows,
+        bulk_tag_task,
+        tag_listing,
+        export_path,
+        export_task,
+        mainnet_guard,
     } = state;
 
-    let has_session = session.read().is_some();
+    let session_lock = session_health
+        .read()
+        .locked_reason(session.read().is_some());
 
     let profile_name_value = profile_name.read().clone();
     let profile_bio_value = profile_bio.read().clone();
     let profile_image_value = profile_image.read().clone();
     let profile_status_value = profile_status.read().clone();
     let profile_links_value = profile_links.read().clone();
+    let link_checks = check_link_lines(&profile_links_value);
     let profile_error_value = profile_error.read().clone();
     let profile_response_value = profile_response.read().clone();
+    let profile_card = profile_view
+        .read()
+        .clone()
+        .map(|view| render_profile_card(view, profile_avatar_failed, logs.clone()));
 
     let post_content_value = post_content.read().clone();
     let post_kind_value = post_kind.read().clone();
+    let post_preview_enabled = *post_preview.read();
+    let post_preview_supported = supports_markdown_preview(&post_kind_value);
+    let post_length = ContentLength::measure(&post_content_value, &post_kind_value);
+    let post_preview_html = if post_preview_enabled && post_preview_supported {
+        Some(render_markdown(&post_content_value))
+    } else {
+        None
+    };
     let post_parent_value = post_parent.read().clone();
     let post_embed_kind_value = post_embed_kind.read().clone();
     let post_embed_uri_value = post_embed_uri.read().clone();
     let post_attachments_value = post_attachments.read().clone();
     let post_response_value = post_response.read().clone();
+    let post_load_uri_value = post_load_uri.read().clone();
 
     let tag_uri_value = tag_uri.read().clone();
     let tag_label_value = tag_label.read().clone();
     let tag_response_value = tag_response.read().clone();
+    let bulk_tag_uris_value = bulk_tag_uris.read().clone();
+    let bulk_tag_labels_value = bulk_tag_labels.read().clone();
+    let bulk_tag_rows_value = bulk_tag_rows.read().clone();
+    let tag_listing_value = tag_listing.read().clone();
+    let bulk_tag_running = bulk_tag_task.is_running();
+    let export_running = export_task.is_running();
+    let export_path_value = export_path.read().clone();
+    let bulk_tag_planned =
+        parse_uri_list(&bulk_tag_uris_value).len() * parse_label_list(&bulk_tag_labels_value).len();
+    let bulk_tag_summary = if bulk_tag_rows_value.is_empty() {
+        None
+    } else {
+        let created = bulk_tag_rows_value
+            .iter()
+            .filter(|row| matches!(row.outcome, BulkTagOutcome::Created(_)))
+            .count();
+        Some(format!(
+            "{created} of {} tags created",
+            bulk_tag_rows_value.len()
+        ))
+    };
 
     let profile_copy_value = if profile_response_value.trim().is_empty() {
         None
@@ -94,6 +155,8 @@ pub fn render_social_tab(
     let profile_fetch_links = profile_links.clone();
     let profile_fetch_error = profile_error.clone();
     let profile_fetch_response = profile_response.clone();
+    let profile_fetch_view = profile_view.clone();
+    let profile_fetch_avatar_failed = profile_avatar_failed.clone();
 
     let profile_save_session = session.clone();
     let profile_save_logs = logs.clone();
@@ -104,6 +167,20 @@ pub fn render_social_tab(
     let profile_save_links = profile_links.clone();
     let mut profile_save_error = profile_error.clone();
     let profile_save_response = profile_response.clone();
+    let profile_save_view = profile_view.clone();
+    let profile_save_avatar_failed = profile_avatar_failed.clone();
+
+    let mut post_load_uri_binding = post_load_uri.clone();
+    let post_load_pubky = pubky.clone();
+    let post_load_logs = logs.clone();
+    let post_load_uri_signal = post_load_uri.clone();
+    let post_load_content = post_content.clone();
+    let post_load_kind = post_kind.clone();
+    let post_load_parent = post_parent.clone();
+    let post_load_embed_kind = post_embed_kind.clone();
+    let post_load_embed_uri = post_embed_uri.clone();
+    let post_load_attachments = post_attachments.clone();
+    let post_load_response = post_response.clone();
 
     let post_create_session = session.clone();
     let post_create_logs = logs.clone();
@@ -121,6 +198,25 @@ pub fn render_social_tab(
     let tag_create_label = tag_label.clone();
     let tag_create_response = tag_response.clone();
 
+    let tag_remove_session = session.clone();
+    let tag_remove_logs = logs.clone();
+    let tag_remove_uri = tag_uri.clone();
+    let tag_remove_label = tag_label.clone();
+
+    let tag_list_session = session.clone();
+    let tag_list_logs = logs.clone();
+    let tag_list_signal = tag_listing.clone();
+
+    let export_session = session.clone();
+    let export_logs = logs.clone();
+    let mut export_path_binding = export_path.clone();
+
+    let bulk_tag_session = session.clone();
+    let bulk_tag_logs = logs.clone();
+    let bulk_tag_uris_signal = bulk_tag_uris.clone();
+    let bulk_tag_labels_signal = bulk_tag_labels.clone();
+    let bulk_tag_rows_signal = bulk_tag_rows.clone();
+
     let mut profile_name_binding = profile_name.clone();
     let mut profile_bio_binding = profile_bio.clone();
     let mut profile_image_binding = profile_image.clone();
@@ -129,6 +225,7 @@ pub fn render_social_tab(
 
     let mut post_content_binding = post_content.clone();
     let mut post_kind_binding = post_kind.clone();
+    let mut post_preview_toggle = post_preview.clone();
     let mut post_parent_binding = post_parent.clone();
     let mut post_embed_kind_binding = post_embed_kind.clone();
     let mut post_embed_uri_binding = post_embed_uri.clone();
@@ -136,13 +233,16 @@ pub fn render_social_tab(
 
     let mut tag_uri_binding = tag_uri.clone();
     let mut tag_label_binding = tag_label.clone();
+    let mut bulk_tag_uris_binding = bulk_tag_uris.clone();
+    let mut bulk_tag_labels_binding = bulk_tag_labels.clone();
 
     rsx! {
         div { class: "tab-body",
-            if !has_session {
+            if let Some(reason) = session_lock {
                 section { class: "card",
                     h2 { "Session required" }
-                    p { class: "helper-text", "Load or create a session to manage pubky.app social data." }
+                    p { class: "helper-text", "A usable session is needed to manage pubky.app social data." }
+                    p { class: "helper-text", "{reason}" }
                 }
             } else {
                 section { class: "card",
@@ -162,6 +262,8 @@ pub fn render_social_tab(
                                     let mut image_signal = profile_fetch_image.clone();
                                     let mut status_signal = profile_fetch_status.clone();
                                     let mut links_signal = profile_fetch_links.clone();
+                                    let mut view_signal = profile_fetch_view.clone();
+                                    let mut avatar_failed_signal = profile_fetch_avatar_failed.clone();
                                     let logs_task = profile_fetch_logs.clone();
                                     spawn(async move {
                                         let result = async {
@@ -181,6 +283,8 @@ pub fn render_social_tab(
                                         };
                                         match result.await {
                                             Ok((formatted, profile)) => {
+                                                view_signal.set(Some(ProfileView::from_user(&profile)));
+                                                avatar_failed_signal.set(false);
                                                 name_signal.set(profile.name.clone());
                                                 bio_signal.set(profile.bio.unwrap_or_default());
                                                 image_signal.set(profile.image.unwrap_or_default());
@@ -204,31 +308,7 @@ pub fn render_social_tab(
                             "Load profile",
                         }
                     }
-                    if !profile_name_value.trim().is_empty() || !profile_bio_value.trim().is_empty() || !profile_image_value.trim().is_empty() || !profile_status_value.trim().is_empty() {
-                        div { class: "profile-preview",
-                            h3 { "{profile_name_value}" }
-                            if !profile_status_value.trim().is_empty() {
-                                p { class: "helper-text", "Status: {profile_status_value}" }
-                            }
-                            if !profile_bio_value.trim().is_empty() {
-                                p { class: "helper-text", "Bio: {profile_bio_value}" }
-                            }
-                            if !profile_image_value.trim().is_empty() {
-                                img {
-                                    class: "avatar-preview",
-                                    src: profile_image_value.clone(),
-                                    alt: "Profile avatar",
-                                }
-                            }
-                            if !profile_links_value.trim().is_empty() {
-                                ul { class: "helper-text",
-                                    for link in profile_links_value.lines().filter(|line| !line.trim().is_empty()) {
-                                        li { "{link}" }
-                                    }
-                                }
-                            }
-                        }
-                    }
+                    {profile_card}
                     if !profile_error_value.trim().is_empty() {
                         p { class: "helper-text", style: "color: var(--danger-600);", "{profile_error_value}" }
                     }
@@ -280,6 +360,9 @@ pub fn render_social_tab(
                             }
                         }
                     }
+                    if !link_checks.is_empty() {
+                        {render_link_checks(link_checks, logs.clone())}
+                    }
                     div { class: "small-buttons",
                         button {
                             class: "action secondary",
@@ -326,10 +409,17 @@ pub fn render_social_tab(
                                             return;
                                         }
                                     };
+                                    let saved_view = ProfileView::from_user(&user);
                                     let mut response_signal = profile_save_response.clone();
                                     let mut error_signal = profile_save_error.clone();
+                                    let mut view_signal = profile_save_view.clone();
+                                    let mut avatar_failed_signal = profile_save_avatar_failed.clone();
                                     let logs_task = profile_save_logs.clone();
                                     spawn(async move {
+                                        if !mainnet_guard.allow("Overwrite profile.json").await {
+                                            logs_task.info("Profile save cancelled");
+                                            return;
+                                        }
                                         let result = async {
                                             let response = session.storage().put(path.clone(), body.clone()).await?;
                                             let formatted = format_response(response).await?;
@@ -339,6 +429,8 @@ pub fn render_social_tab(
                                             Ok(formatted) => {
                                                 response_signal.set(formatted.clone());
                                                 error_signal.set(String::new());
+                                                view_signal.set(Some(saved_view));
+                                                avatar_failed_signal.set(false);
                                                 logs_task.success("Updated profile.json");
                                             }
                                             Err(err) => {
@@ -365,11 +457,78 @@ pub fn render_social_tab(
                             "data-touch-copy-success": copy_success.clone(),
                         }
                     }
+                    if !profile_response_value.is_empty() {
+                        ResponseCopyButtons { response: profile_response_value.clone() }
+                    }
                 }
 
                 section { class: "card",
                     h2 { "Posts" }
-                    p { class: "helper-text", "Compose a new post for pubky.app feeds." }
+                    p { class: "helper-text", "Compose a new post for pubky.app feeds, or load an existing one to inspect or repost it." }
+                    div { class: "form-grid",
+                        label {
+                            "Load post"
+                            input {
+                                value: post_load_uri_value,
+                                oninput: move |evt| post_load_uri_binding.set(evt.value()),
+                                placeholder: "pubky://<key>/pub/pubky.app/posts/<id>",
+                                title: "pubky:// URI of a stored post to copy into the composer",
+                                "data-touch-tooltip": touch_tooltip("pubky:// URI of a stored post to copy into the composer"),
+                            }
+                        }
+                    }
+                    div { class: "small-buttons",
+                        button {
+                            class: "action secondary",
+                            title: "Fetch the post and fill the composer with its fields",
+                            "data-touch-tooltip": touch_tooltip("Fetch the post and fill the composer with its fields"),
+                            onclick: move |_| {
+                                let uri = post_load_uri_signal.read().trim().to_string();
+                                if !uri.starts_with("pubky") {
+                                    post_load_logs.error("Enter the pubky:// URI of a post to load");
+                                    return;
+                                }
+                                let Some(pubky) = post_load_pubky.ready_or_log(&post_load_logs) else {
+                                    return;
+                                };
+                                let mut content_signal = post_load_content.clone();
+                                let mut kind_signal = post_load_kind.clone();
+                                let mut parent_signal = post_load_parent.clone();
+                                let mut embed_kind_signal = post_load_embed_kind.clone();
+                                let mut embed_uri_signal = post_load_embed_uri.clone();
+                                let mut attachments_signal = post_load_attachments.clone();
+                                let mut response_signal = post_load_response.clone();
+                                let logs_task = post_load_logs.clone();
+                                spawn(async move {
+                                    let result = async {
+                                        let resp = pubky.public_storage().get(uri.clone()).await?;
+                                        let status = resp.status();
+                                        let version = resp.version();
+                                        let headers = resp.headers().clone();
+                                        let bytes = resp.bytes().await?;
+                                        response_signal.set(format_response_parts(status, version, &headers, &bytes));
+                                        if !status.is_success() {
+                                            return Err(anyhow!("no post at {uri} (status {status})"));
+                                        }
+                                        composer_fields(&bytes).map_err(|err| anyhow!(err))
+                                    };
+                                    match result.await {
+                                        Ok(fields) => {
+                                            content_signal.set(fields.content);
+                                            kind_signal.set(fields.kind);
+                                            parent_signal.set(fields.parent);
+                                            embed_kind_signal.set(fields.embed_kind);
+                                            embed_uri_signal.set(fields.embed_uri);
+                                            attachments_signal.set(fields.attachments);
+                                            logs_task.success(format!("Loaded post {uri} into the composer"));
+                                        }
+                                        Err(err) => logs_task.error(format!("Failed to load post: {err}")),
+                                    }
+                                });
+                            },
+                            "Load post",
+                        }
+                    }
                     div { class: "form-grid",
                         label {
                             "Content"
@@ -380,6 +539,24 @@ pub fn render_social_tab(
                                 title: "Post body",
                                 "data-touch-tooltip": touch_tooltip("Post body"),
                             }
+                            span {
+                                class: if post_length.is_over() { "char-counter over" } else { "char-counter" },
+                                {post_length.label()}
+                            }
+                        }
+                        if let Some(preview_html) = post_preview_html.clone() {
+                            div { class: "markdown-preview-field",
+                                span { "Preview" }
+                                div {
+                                    class: "markdown-preview",
+                                    dangerous_inner_html: preview_html,
+                                }
+                            }
+                        } else if post_preview_enabled {
+                            div { class: "markdown-preview-field",
+                                span { "Preview" }
+                                p { class: "helper-text", "Markdown preview is available for short and long posts." }
+                            }
                         }
                         label {
                             "Kind"
@@ -444,8 +621,23 @@ pub fn render_social_tab(
                     div { class: "small-buttons",
                         button {
                             class: "action secondary",
-                            title: "Publish a new post",
-                            "data-touch-tooltip": touch_tooltip("Publish a new post"),
+                            title: "Render the post content as markdown while you type",
+                            "data-touch-tooltip": touch_tooltip("Render the post content as markdown while you type"),
+                            onclick: move |_| {
+                                let enabled = *post_preview_toggle.read();
+                                post_preview_toggle.set(!enabled);
+                            },
+                            if post_preview_enabled { "Hide preview" } else { "Show preview" }
+                        }
+                        button {
+                            class: "action secondary",
+                            title: if post_length.is_over() { "Shorten the content to the limit for this kind before publishing" } else { "Publish a new post" },
+                            "data-touch-tooltip": touch_tooltip(if post_length.is_over() {
+                                "Shorten the content to the limit for this kind before publishing"
+                            } else {
+                                "Publish a new post"
+                            }),
+                            disabled: post_length.is_over(),
                             onclick: move |_| {
                                 if let Some(session) = post_create_session.read().as_ref().cloned() {
                                     let content = post_create_content.read().clone();
@@ -524,11 +716,14 @@ pub fn render_social_tab(
                             "data-touch-copy-success": copy_success.clone(),
                         }
                     }
+                    if !post_response_value.is_empty() {
+                        ResponseCopyButtons { response: post_response_value.clone() }
+                    }
                 }
 
                 section { class: "card",
                     h2 { "Tags" }
-                    p { class: "helper-text", "Attach a tag to an existing social URI." }
+                    p { class: "helper-text", "Attach a tag to an existing social URI, remove one by its URI and label, or list the tags you have stored." }
                     div { class: "form-grid",
                         label {
                             "Target URI"
@@ -557,26 +752,11 @@ pub fn render_social_tab(
                             onclick: move |_| {
                                 if let Some(session) = tag_create_session.read().as_ref().cloned() {
                                     let uri = tag_create_uri.read().clone();
-                                    if uri.trim().is_empty() {
-                                        tag_create_logs.error("Provide a URI to tag");
-                                        return;
-                                    }
                                     let label = tag_create_label.read().clone();
-                                    if label.trim().is_empty() {
-                                        tag_create_logs.error("Provide a tag label");
-                                        return;
-                                    }
-                                    let tag = PubkyAppTag::new(uri.clone(), label.clone());
-                                    let tag_id = tag.create_id();
-                                    if let Err(err) = tag.validate(Some(&tag_id)) {
-                                        tag_create_logs.error(format!("Invalid tag: {err}"));
-                                        return;
-                                    }
-                                    let path = PubkyAppTag::create_path(&tag_id);
-                                    let body = match to_string_pretty(&tag) {
-                                        Ok(body) => body,
-                                        Err(err) => {
-                                            tag_create_logs.error(format!("Failed to serialize tag: {err}"));
+                                    let (path, body) = match prepare_tag(&uri, &label) {
+                                        Ok(prepared) => (prepared.path, prepared.body),
+                                        Err(message) => {
+                                            tag_create_logs.error(message);
                                             return;
                                         }
                                     };
@@ -605,6 +785,90 @@ pub fn render_social_tab(
                             },
                             "Create tag",
                         }
+                        ConfirmButton {
+                            label: "Remove tag",
+                            confirm_label: "Confirm remove?",
+                            danger: true,
+                            title: "Delete your tag with this target URI and label (click twice to confirm)",
+                            on_confirm: move |_| {
+                                let Some(session) = tag_remove_session.read().as_ref().cloned() else {
+                                    tag_remove_logs.error("No active session");
+                                    return;
+                                };
+                                let uri = tag_remove_uri.read().clone();
+                                let label = tag_remove_label.read().clone();
+                                let logs_task = tag_remove_logs.clone();
+                                spawn(async move {
+                                    if !mainnet_guard.allow(&format!("Remove tag '{}' from {}", label.trim(), uri.trim())).await {
+                                        logs_task.info("Tag removal cancelled");
+                                        return;
+                                    }
+                                    match remove_tag(&session, &uri, &label).await {
+                                        Ok(RemoveTagOutcome::Removed(path)) => {
+                                            logs_task.success(format!("Removed tag at {path}"));
+                                        }
+                                        Ok(RemoveTagOutcome::NotFound(path)) => {
+                                            logs_task.error(format!("Tag not found: nothing stored at {path}"));
+                                        }
+                                        Err(err) => logs_task.error(format!("Failed to remove tag: {err}")),
+                                    }
+                                });
+                            },
+                        }
+                        button {
+                            class: "action secondary",
+                            title: "List the tags stored under {TAGS_DIRECTORY}",
+                            "data-touch-tooltip": touch_tooltip("List the tags you have stored"),
+                            onclick: move |_| {
+                                let Some(session) = tag_list_session.read().as_ref().cloned() else {
+                                    tag_list_logs.error("No active session");
+                                    return;
+                                };
+                                let mut listing_signal = tag_list_signal.clone();
+                                let logs_task = tag_list_logs.clone();
+                                spawn(async move {
+                                    match list_session_tags(&session).await {
+                                        Ok(tags) => {
+                                            let count = tags.len();
+                                            listing_signal.set(tags);
+                                            logs_task.success(format!("Listed {count} tags"));
+                                        }
+                                        Err(err) => {
+                                            listing_signal.set(Vec::new());
+                                            logs_task.error(format!("Failed to list tags: {err}"));
+                                        }
+                                    }
+                                });
+                            },
+                            "List my tags",
+                        }
+                    }
+                    if !tag_listing_value.is_empty() {
+                        div { class: "table-scroll",
+                            table { class: "lookup-table",
+                                thead {
+                                    tr {
+                                        th { "Label" }
+                                        th { "Target" }
+                                    }
+                                }
+                                tbody {
+                                    for listed in tag_listing_value.iter() {
+                                        if let Ok((label, target)) = listed.tag.clone() {
+                                            tr { key: "{listed.path}",
+                                                td { "{label}" }
+                                                td { class: "mono", "{target}" }
+                                            }
+                                        } else {
+                                            tr { key: "{listed.path}", class: "tag-error",
+                                                td { class: "mono", "{listed.path}" }
+                                                td { class: "mono", {listed.tag.clone().err().unwrap_or_default()} }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
                     }
                     label {
                         "Latest response"
@@ -616,6 +880,353 @@ pub fn render_social_tab(
                             "data-touch-copy-success": copy_success,
                         }
                     }
+                    if !tag_response_value.is_empty() {
+                        ResponseCopyButtons { response: tag_response_value.clone() }
+                    }
+                }
+
+                section { class: "card",
+                    h2 { "Bulk tags" }
+                    p { class: "helper-text", "Tag many URIs at once: every label is applied to every URI, {MAX_CONCURRENT_TAG_WRITES} writes at a time." }
+                    div { class: "form-grid",
+                        label {
+                            "Target URIs"
+                            textarea {
+                                class: "tall",
+                                value: bulk_tag_uris_value,
+                                oninput: move |evt| bulk_tag_uris_binding.set(evt.value()),
+                                placeholder: "One pubky:// URI per line",
+                                title: "Newline-separated pubky:// URIs to tag",
+                                "data-touch-tooltip": touch_tooltip("Newline-separated pubky:// URIs to tag"),
+                            }
+                        }
+                        label {
+                            "Labels"
+                            textarea {
+                                value: bulk_tag_labels_value,
+                                oninput: move |evt| bulk_tag_labels_binding.set(evt.value()),
+                                placeholder: "rust, p2p, pubky",
+                                title: "Labels separated by commas or newlines",
+                                "data-touch-tooltip": touch_tooltip("Labels separated by commas or newlines"),
+                            }
+                        }
+                    }
+                    div { class: "small-buttons",
+                        button {
+                            class: "action secondary",
+                            disabled: !bulk_tag_running && bulk_tag_planned == 0,
+                            title: if bulk_tag_running { "Stop the run; writes still in flight are dropped" } else { "Create a tag for every URI and label pair" },
+                            "data-touch-tooltip": touch_tooltip(if bulk_tag_running {
+                                "Stop the run; writes still in flight are dropped"
+                            } else {
+                                "Create a tag for every URI and label pair"
+                            }),
+                            onclick: move |_| {
+                                if bulk_tag_task.cancel() {
+                                    bulk_tag_logs.info("Bulk tagging cancelled");
+                                    return;
+                                }
+                                let Some(session) = bulk_tag_session.read().as_ref().cloned() else {
+                                    bulk_tag_logs.error("No active session");
+                                    return;
+                                };
+                                let uris = parse_uri_list(&bulk_tag_uris_signal.read());
+                                let labels = parse_label_list(&bulk_tag_labels_signal.read());
+                                if uris.is_empty() || labels.is_empty() {
+                                    bulk_tag_logs.error("Provide at least one URI and one label");
+                                    return;
+                                }
+                                let pairs = bulk_pairs(&uris, &labels);
+                                let total = pairs.len();
+                                let mut rows_signal = bulk_tag_rows_signal.clone();
+                                rows_signal.set(Vec::new());
+                                let logs_task = bulk_tag_logs.clone();
+                                logs_task.info(format!("Creating {total} tags..."));
+                                bulk_tag_task.start(async move {
+                                    let rows = apply_bulk_tags(&session, pairs).await;
+                                    let created = rows
+                                        .iter()
+                                        .filter(|row| matches!(row.outcome, BulkTagOutcome::Created(_)))
+                                        .count();
+                                    rows_signal.set(rows);
+                                    let message = format!("Bulk tagging finished: {created}/{total} created");
+                                    if created == total {
+                                        logs_task.success(message);
+                                    } else {
+                                        logs_task.error(message);
+                                    }
+                                });
+                            },
+                            if bulk_tag_running {
+                                "Cancel run"
+                            } else {
+                                "Create {bulk_tag_planned} tags"
+                            }
+                        }
+                    }
+                    if let Some(summary) = bulk_tag_summary {
+                        p { class: "helper-text", "{summary}" }
+                        div { class: "table-scroll",
+                            table { class: "lookup-table",
+                                thead {
+                                    tr {
+                                        th { "URI" }
+                                        th { "Label" }
+                                        th { "Result" }
+                                    }
+                                }
+                                tbody {
+                                    for row in bulk_tag_rows_value.iter() {
+                                        tr { key: "{row.uri}:{row.label}", class: "tag-{row.outcome.status()}",
+                                            td { class: "mono", "{row.uri}" }
+                                            td { "{row.label}" }
+                                            td { class: "mono", "{row.outcome.summary()}" }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+
+                section { class: "card",
+                    h2 { "Export" }
+                    p { class: "helper-text", "Back up your profile, posts, and tags under /pub/pubky.app/ into one JSON file." }
+                    div { class: "form-grid",
+                        label {
+                            "Save to"
+                            input {
+                                value: export_path_value,
+                                oninput: move |evt| export_path_binding.set(evt.value()),
+                                placeholder: "Leave empty to choose a file",
+                            }
+                        }
+                    }
+                    div { class: "small-buttons",
+                        button {
+                            class: "action",
+                            title: if export_running { "Stop the export; nothing is written" } else { "Save your pubky.app data as a JSON bundle" },
+                            "data-touch-tooltip": touch_tooltip(if export_running {
+                                "Stop the export; nothing is written"
+                            } else {
+                                "Save your pubky.app data as a JSON bundle"
+                            }),
+                            onclick: move |_| {
+                                if export_task.cancel() {
+                                    export_logs.info("Export cancelled");
+                                    return;
+                                }
+                                let Some(session) = export_session.read().as_ref().cloned() else {
+                                    export_logs.error("No active session");
+                                    return;
+                                };
+                                let raw_path = export_path.read().clone();
+                                let chosen_path = if raw_path.trim().is_empty() {
+                                    match file_dialog::save_file() {
+                                        FileDialogResult::Selected(path) => Some(path.display().to_string()),
+                                        FileDialogResult::Unavailable => {
+                                            export_logs.info(file_dialog::MANUAL_ENTRY_HINT);
+                                            None
+                                        }
+                                        FileDialogResult::Cancelled => None,
+                                    }
+                                } else {
+                                    Some(raw_path)
+                                };
+                                let Some(selected_path) = chosen_path else {
+                                    return;
+                                };
+                                let mut path_signal = export_path.clone();
+                                let logs_task = export_logs.clone();
+                                logs_task.info("Exporting pubky.app data...");
+                                export_task.start(async move {
+                                    let progress_logs = logs_task.clone();
+                                    let result = export_pubky_app_data(&session, move |section, count| {
+                                        progress_logs.info(format!("Export: read {count} {section} entries"));
+                                    })
+                                    .await;
+                                    let export = match result {
+                                        Ok(export) => export,
+                                        Err(err) => {
+                                            logs_task.error(format!("Export failed: {err:#}"));
+                                            return;
+                                        }
+                                    };
+                                    match save_export(&export, &selected_path) {
+                                        Ok(path) => {
+                                            path_signal.set(path.display().to_string());
+                                            logs_task.success(format!(
+                                                "Exported {} posts and {} tags to {}",
+                                                export.posts.len(),
+                                                export.tags.len(),
+                                                path.display()
+                                            ));
+                                            if !export.failed.is_empty() {
+                                                logs_task.warn(format!(
+                                                    "{} entries could not be read; they are listed under \"failed\" in the export",
+                                                    export.failed.len()
+                                                ));
+                                            }
+                                        }
+                                        Err(err) => logs_task.error(format!("Failed to save export: {err:#}")),
+                                    }
+                                });
+                            },
+                            if export_running {
+                                "Cancel export"
+                            } else {
+                                "Export my pubky.app data"
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Live preview under the Links editor: each parsed link, or what is wrong
+/// with the line.
+fn render_link_checks(checks: Vec<LinkLineCheck>, logs: ActivityLog) -> Element {
+    let items: Vec<Element> = checks
+        .into_iter()
+        .map(|check| match check.result {
+            Ok(link) if link.openable => {
+                let url = link.url.clone();
+                let link_logs = logs.clone();
+                rsx! {
+                    li {
+                        a {
+                            href: "{link.url}",
+                            title: "Open {link.url} in your browser",
+                            onclick: move |evt| {
+                                evt.prevent_default();
+                                if let Err(err) = open_external_link(&url) {
+                                    link_logs.error(format!("Failed to open {url}: {err}"));
+                                }
+                            },
+                            "{link.title}"
+                        }
+                        span { class: "profile-link-url", "{link.url}" }
+                    }
+                }
+            }
+            Ok(link) => rsx! {
+                li {
+                    span { "{link.title}" }
+                    span { class: "profile-link-url", "{link.url}" }
+                }
+            },
+            Err(message) => rsx! {
+                li { class: "link-check-error", "{message}" }
+            },
+        })
+        .collect();
+
+    rsx! {
+        ul { class: "link-checks",
+            for item in items {
+                {item}
+            }
+        }
+    }
+}
+
+#[allow(clippy::clone_on_copy)]
+fn render_profile_card(
+    view: ProfileView,
+    avatar_failed: Signal<bool>,
+    logs: ActivityLog,
+) -> Element {
+    let avatar = if *avatar_failed.read() {
+        view.fallback_avatar()
+    } else {
+        view.avatar.clone()
+    };
+    let avatar_note = match &avatar {
+        ProfileAvatar::Placeholder {
+            source: Some(source),
+            ..
+        } => Some(format!("Avatar {source} cannot be displayed here.")),
+        _ => None,
+    };
+    let avatar_view = match avatar {
+        ProfileAvatar::Image(src) => {
+            let mut failed = avatar_failed.clone();
+            rsx! {
+                img {
+                    class: "profile-avatar",
+                    src,
+                    alt: "Profile avatar",
+                    onerror: move |_| failed.set(true),
+                }
+            }
+        }
+        ProfileAvatar::Placeholder { initial, .. } => rsx! {
+            div { class: "profile-avatar placeholder", "{initial}" }
+        },
+    };
+
+    let link_items: Vec<Element> = view
+        .links
+        .iter()
+        .map(|link| {
+            let title = if link.title.is_empty() {
+                link.url.clone()
+            } else {
+                link.title.clone()
+            };
+            if link.openable {
+                let url = link.url.clone();
+                let link_logs = logs.clone();
+                rsx! {
+                    li {
+                        a {
+                            href: "{link.url}",
+                            title: "Open {link.url} in your browser",
+                            onclick: move |evt| {
+                                evt.prevent_default();
+                                if let Err(err) = open_external_link(&url) {
+                                    link_logs.error(format!("Failed to open {url}: {err}"));
+                                }
+                            },
+                            "{title}"
+                        }
+                    }
+                }
+            } else {
+                rsx! {
+                    li {
+                        span { "{title}" }
+                        span { class: "profile-link-url", "{link.url}" }
+                    }
+                }
+            }
+        })
+        .collect();
+
+    rsx! {
+        div { class: "profile-card",
+            div { class: "profile-card-header",
+                {avatar_view}
+                div { class: "profile-identity",
+                    h3 { "{view.name}" }
+                    if let Some(status) = view.status.clone() {
+                        p { class: "profile-status", "{status}" }
+                    }
+                }
+            }
+            if let Some(note) = avatar_note {
+                p { class: "helper-text", "{note}" }
+            }
+            if let Some(bio) = view.bio.clone() {
+                p { class: "profile-bio", "{bio}" }
+            }
+            if !link_items.is_empty() {
+                ul { class: "profile-links",
+                    for item in link_items {
+                        {item}
+                    }
                 }
             }
         }
@@ -632,28 +1243,15 @@ fn optional_field(value: &str) -> Option<String> {
 }
 
 fn parse_links(input: &str) -> Result<Option<Vec<PubkyAppUserLink>>, String> {
-    let mut links = Vec::new();
-    for (idx, line) in input.lines().enumerate() {
-        let line = line.trim();
-        if line.is_empty() {
-            continue;
-        }
-        let mut parts = line.splitn(2, '|');
-        let title = parts
-            .next()
-            .map(str::trim)
-            .filter(|part| !part.is_empty())
-            .ok_or_else(|| format!("Link {} is missing a title", idx + 1))?;
-        let url = parts
-            .next()
-            .map(str::trim)
-            .filter(|part| !part.is_empty())
-            .ok_or_else(|| format!("Link {} is missing a URL", idx + 1))?;
-        links.push(PubkyAppUserLink {
-            title: title.to_string(),
-            url: url.to_string(),
-        });
-    }
+    let links = check_link_lines(input)
+        .into_iter()
+        .map(|check| {
+            check.result.map(|link| PubkyAppUserLink {
+                title: link.title,
+                url: link.url,
+            })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
     if links.is_empty() {
         Ok(None)
     } else {
@@ -1,18 +1,43 @@
 use dioxus::prelude::Signal;
+use pkarr::SignedPacket;
 use pubky::{Keypair, PubkyAuthFlow, PubkySession};
 
+use crate::app::NetworkMode;
+use crate::utils::accounts::AccountSwitcher;
+use crate::utils::capabilities::CapabilityPreset;
+use crate::utils::confirm::MainnetGuard;
+use crate::utils::http::{HeaderRow, HistoryEntry};
+use crate::utils::logging::LogEntry;
+use crate::utils::pkdns::BatchLookupRow;
+use crate::utils::prefs::{InputFields, PrefsHandle};
+use crate::utils::profile::ProfileView;
+use crate::utils::qr::QrSize;
+use crate::utils::session::{SessionHealth, SignupProgress};
+use crate::utils::storage::UploadFile;
+use crate::utils::tags::{BulkTagRow, ListedTag};
+use crate::utils::task::InFlight;
+
 #[derive(Clone)]
 pub struct KeysTabState {
     pub keypair: Signal<Option<Keypair>>,
     pub secret_input: Signal<String>,
+    /// BIP39 phrase editor; 24 words encode the 32-byte secret.
+    pub mnemonic_input: Signal<String>,
     pub recovery_path: Signal<String>,
     pub recovery_passphrase: Signal<String>,
+    /// Whether the secret key editor shows its contents; masked by default.
+    pub secret_visible: Signal<bool>,
+    pub prefs: PrefsHandle,
+    pub accounts: AccountSwitcher,
+    pub account_label: Signal<String>,
 }
 
 #[derive(Clone)]
 pub struct TokensTabState {
     pub keypair: Signal<Option<Keypair>>,
     pub capabilities: Signal<String>,
+    /// Last preset picked; `Custom` unlocks the capabilities field.
+    pub capability_preset: Signal<CapabilityPreset>,
     pub output: Signal<String>,
 }
 
@@ -20,9 +45,15 @@ pub struct TokensTabState {
 pub struct SessionsTabState {
     pub keypair: Signal<Option<Keypair>>,
     pub session: Signal<Option<PubkySession>>,
+    pub session_health: Signal<SessionHealth>,
     pub details: Signal<String>,
     pub homeserver: Signal<String>,
     pub signup_code: Signal<String>,
+    pub signup_progress: Signal<SignupProgress>,
+    pub invite_input: Signal<String>,
+    pub signup_task: InFlight,
+    pub prefs: PrefsHandle,
+    pub network_mode: Signal<NetworkMode>,
 }
 
 #[derive(Clone)]
@@ -31,6 +62,18 @@ pub struct PkdnsTabState {
     pub lookup_input: Signal<String>,
     pub lookup_result: Signal<String>,
     pub host_override: Signal<String>,
+    pub packet: Signal<Option<SignedPacket>>,
+    pub packet_dump: Signal<String>,
+    pub packet_path: Signal<String>,
+    pub batch_input: Signal<String>,
+    pub batch_rows: Signal<Vec<BatchLookupRow>>,
+    pub lookup_task: InFlight,
+    pub batch_task: InFlight,
+    pub batch_export_path: Signal<String>,
+    /// Background loop that republishes `_pubky` while enabled.
+    pub republish_task: InFlight,
+    pub republish_minutes: Signal<String>,
+    pub mainnet_guard: MainnetGuard,
 }
 
 #[derive(Clone)]
@@ -45,6 +88,13 @@ pub struct AuthTabState {
     pub status: Signal<String>,
     pub flow: Signal<Option<PubkyAuthFlow>>,
     pub request_body: Signal<String>,
+    /// The running "Await approval" wait, so Cancel can drop it.
+    pub await_task: InFlight,
+    /// Seconds to wait for approval before giving up.
+    pub approval_timeout: Signal<String>,
+    pub qr_size: Signal<QrSize>,
+    /// Where Save QR writes the SVG; blank opens a file dialog.
+    pub qr_save_path: Signal<String>,
 }
 
 #[derive(Clone)]
@@ -53,8 +103,18 @@ pub struct StorageTabState {
     pub path: Signal<String>,
     pub body: Signal<String>,
     pub response: Signal<String>,
+    pub response_headers: Signal<Vec<HeaderRow>>,
+    pub range_start: Signal<String>,
+    pub range_end: Signal<String>,
+    pub listing: Signal<Vec<String>>,
+    pub public_owner: Signal<String>,
     pub public_resource: Signal<String>,
     pub public_response: Signal<String>,
+    pub public_headers: Signal<Vec<HeaderRow>>,
+    pub public_preview: Signal<Option<String>>,
+    /// File PUT instead of the text body while one is chosen.
+    pub upload_file: Signal<Option<UploadFile>>,
+    pub mainnet_guard: MainnetGuard,
 }
 
 #[derive(Clone)]
@@ -64,11 +124,22 @@ pub struct HttpTabState {
     pub headers: Signal<String>,
     pub body: Signal<String>,
     pub response: Signal<String>,
+    pub response_headers: Signal<Vec<HeaderRow>>,
+    pub repeat_count: Signal<String>,
+    pub send_task: InFlight,
+    pub repeat_task: InFlight,
+    pub prefs: PrefsHandle,
+    /// Recently sent requests, newest first, without response bodies.
+    pub history: Signal<Vec<HistoryEntry>>,
+    /// File sent as `multipart/form-data` instead of the text body.
+    pub attachment_path: Signal<String>,
+    pub attachment_field: Signal<String>,
 }
 
 #[derive(Clone)]
 pub struct SocialTabState {
     pub session: Signal<Option<PubkySession>>,
+    pub session_health: Signal<SessionHealth>,
     pub profile_name: Signal<String>,
     pub profile_bio: Signal<String>,
     pub profile_image: Signal<String>,
@@ -76,14 +147,46 @@ pub struct SocialTabState {
     pub profile_links: Signal<String>,
     pub profile_error: Signal<String>,
     pub profile_response: Signal<String>,
+    pub profile_view: Signal<Option<ProfileView>>,
+    pub profile_avatar_failed: Signal<bool>,
     pub post_content: Signal<String>,
+    pub post_preview: Signal<bool>,
     pub post_kind: Signal<String>,
     pub post_parent: Signal<String>,
     pub post_embed_kind: Signal<String>,
     pub post_embed_uri: Signal<String>,
     pub post_attachments: Signal<String>,
     pub post_response: Signal<String>,
+    /// `pubky://` URI of a stored post to load into the composer.
+    pub post_load_uri: Signal<String>,
     pub tag_uri: Signal<String>,
     pub tag_label: Signal<String>,
     pub tag_response: Signal<String>,
+    pub bulk_tag_uris: Signal<String>,
+    pub bulk_tag_labels: Signal<String>,
+    pub bulk_tag_rows: Signal<Vec<BulkTagRow>>,
+    pub bulk_tag_task: InFlight,
+    /// The session user's tags from the last "List my tags".
+    pub tag_listing: Signal<Vec<ListedTag>>,
+    /// Where "Export my pubky.app data" writes; empty opens a save dialog.
+    pub export_path: Signal<String>,
+    pub export_task: InFlight,
+    pub mainnet_guard: MainnetGuard,
+}
+
+#[derive(Clone)]
+pub struct SettingsTabState {
+    pub prefs: PrefsHandle,
+    pub mainnet_capabilities: Signal<String>,
+    pub mainnet_storage_path: Signal<String>,
+    pub testnet_capabilities: Signal<String>,
+    pub testnet_storage_path: Signal<String>,
+    pub network_mode: Signal<NetworkMode>,
+    pub session: Signal<Option<PubkySession>>,
+    pub session_health: Signal<SessionHealth>,
+    pub log_entries: Signal<Vec<LogEntry>>,
+    pub diagnostics_path: Signal<String>,
+    pub input_fields: InputFields,
+    pub environment_name: Signal<String>,
+    pub environment_variables: Signal<String>,
 }
@@ -1,10 +1,77 @@
 use dioxus::prelude::*;
+use pubky::PubkySession;
+use reqwest::Method;
+use reqwest::header::{CONTENT_RANGE, CONTENT_TYPE, RANGE};
+use url::Url;
 
+use crate::components::{ConfirmButton, HeaderTable, QrToggle, ResponseCopyButtons};
 use crate::tabs::StorageTabState;
-use crate::utils::http::format_response;
+use crate::utils::documents::check_app_document;
+use crate::utils::file_dialog::{self, FileDialogResult};
+use crate::utils::http::{
+    format_response_parts, format_response_with_headers, header_rows, image_preview_data_url,
+};
 use crate::utils::logging::ActivityLog;
 use crate::utils::mobile::{is_android_touch, touch_copy_option, touch_tooltip};
 use crate::utils::pubky::PubkyFacadeHandle;
+use crate::utils::storage::{
+    ByteRange, UploadFile, describe_range_response, directory_of, entry_exists, entry_path,
+    parent_directory, path_breadcrumbs, public_resource_target, put_file,
+};
+
+/// Maximum number of entries requested per directory listing.
+const LIST_LIMIT: u16 = 100;
+
+fn list_directory(
+    session: PubkySession,
+    directory: String,
+    mut listing: Signal<Vec<String>>,
+    logs: ActivityLog,
+) {
+    spawn(async move {
+        let result = async {
+            let entries = session
+                .storage()
+                .list(directory.clone())?
+                .limit(LIST_LIMIT)
+                .send()
+                .await?;
+            Ok::<_, anyhow::Error>(
+                entries
+                    .into_iter()
+                    .map(|entry| entry.to_string())
+                    .collect::<Vec<_>>(),
+            )
+        };
+        match result.await {
+            Ok(entries) => {
+                let count = entries.len();
+                listing.set(entries);
+                logs.success(format!("Listed {count} entries under {directory}"));
+            }
+            Err(err) => logs.error(format!("LIST failed: {err}")),
+        }
+    });
+}
+
+/// Point the path editor at `target` and re-list it when it names a directory.
+fn navigate_to(
+    target: String,
+    session: Signal<Option<PubkySession>>,
+    mut path: Signal<String>,
+    listing: Signal<Vec<String>>,
+    logs: &ActivityLog,
+) {
+    path.set(target.clone());
+    if !target.ends_with('/') {
+        return;
+    }
+    if let Some(active) = session.read().as_ref().cloned() {
+        list_directory(active, target, listing, logs.clone());
+    } else {
+        logs.error("No active session");
+    }
+}
 
 #[allow(clippy::too_many_arguments, clippy::clone_on_copy)]
 pub fn render_storage_tab(
@@ -17,15 +84,39 @@ pub fn render_storage_tab(
         path,
         body,
         response,
+        response_headers,
+        range_start,
+        range_end,
+        listing,
+        public_owner,
         public_resource,
         public_response,
+        public_headers,
+        public_preview,
+        upload_file,
+        mainnet_guard,
     } = state;
 
     let path_value = { path.read().clone() };
     let body_value = { body.read().clone() };
+    let upload_value = { upload_file.read().clone() };
+    let upload_summary = upload_value.as_ref().map(UploadFile::summary);
+    let document_check = match upload_value.as_ref() {
+        Some(file) => check_app_document(&path_value, &file.bytes),
+        None => check_app_document(&path_value, body_value.as_bytes()),
+    };
+    let range_start_value = { range_start.read().clone() };
+    let range_end_value = { range_end.read().clone() };
+    let listing_value = { listing.read().clone() };
+    let crumbs = path_breadcrumbs(&path_value);
     let session_response = { response.read().clone() };
+    let session_header_rows = { response_headers.read().clone() };
+    let public_owner_value = { public_owner.read().clone() };
     let public_value = { public_resource.read().clone() };
+    let public_link = public_resource_target(&public_owner_value, &public_value).ok();
     let public_resp = { public_response.read().clone() };
+    let public_header_rows = { public_headers.read().clone() };
+    let public_preview_value = { public_preview.read().clone() };
     let session_copy_value = if session_response.trim().is_empty() {
         None
     } else {
@@ -55,9 +146,24 @@ pub fn render_storage_tab(
     let storage_response_get = response.clone();
     let storage_logs_get = logs.clone();
 
+    let mut range_start_binding = range_start.clone();
+    let mut range_end_binding = range_end.clone();
+    let range_pubky = pubky.clone();
+    let range_session = session.clone();
+    let range_path = path.clone();
+    let range_start_get = range_start.clone();
+    let range_end_get = range_end.clone();
+    let range_response = response.clone();
+    let range_logs = logs.clone();
+
+    let storage_pubky_put = pubky.clone();
     let storage_session_put = session.clone();
     let storage_path_put = path.clone();
     let storage_body_put = body.clone();
+    let storage_upload_put = upload_file.clone();
+    let mut choose_upload_signal = upload_file.clone();
+    let mut clear_upload_signal = upload_file.clone();
+    let choose_upload_logs = logs.clone();
     let storage_response_put = response.clone();
     let storage_logs_put = logs.clone();
 
@@ -66,9 +172,22 @@ pub fn render_storage_tab(
     let storage_response_delete = response.clone();
     let storage_logs_delete = logs.clone();
 
+    let list_session = session.clone();
+    let list_path = path.clone();
+    let list_listing = listing.clone();
+    let list_logs = logs.clone();
+
+    let up_session = session.clone();
+    let up_path = path.clone();
+    let up_listing = listing.clone();
+    let up_logs = logs.clone();
+
+    let mut public_owner_binding = public_owner.clone();
     let mut public_resource_binding = public_resource.clone();
+    let public_owner_signal = public_owner.clone();
     let public_resource_signal = public_resource.clone();
     let public_response_signal = public_response.clone();
+    let public_preview_signal = public_preview.clone();
     let public_logs = logs.clone();
 
     rsx! {
@@ -76,6 +195,35 @@ pub fn render_storage_tab(
             section { class: "card",
                 h2 { "Session storage" }
                 p { class: "helper-text", "Operate on authenticated storage using the active session." }
+                div { class: "breadcrumbs",
+                    button {
+                        class: "action secondary",
+                        title: "Go up one directory and list it",
+                        "data-touch-tooltip": touch_tooltip(
+                            "Go up one directory and list it",
+                        ),
+                        onclick: move |_| {
+                            let current = up_path.read().clone();
+                            navigate_to(parent_directory(&current), up_session, up_path, up_listing, &up_logs);
+                        },
+                        "Up"
+                    }
+                    for (index, crumb) in crumbs.into_iter().enumerate() {
+                        if index > 0 {
+                            span { class: "crumb-separator", "›" }
+                        }
+                        button {
+                            class: "crumb",
+                            title: format!("Jump to {}", crumb.prefix),
+                            onclick: {
+                                let prefix = crumb.prefix.clone();
+                                let logs_crumb = logs.clone();
+                                move |_| navigate_to(prefix.clone(), session, path, listing, &logs_crumb)
+                            },
+                            {crumb.label}
+                        }
+                    }
+                }
                 div { class: "form-grid",
                     label {
                         "Absolute path"
@@ -88,12 +236,37 @@ pub fn render_storage_tab(
                             ),
                         }
                     }
+                    label {
+                        "Range start (bytes)"
+                        input {
+                            value: range_start_value,
+                            oninput: move |evt| range_start_binding.set(evt.value()),
+                            placeholder: "0",
+                            title: "First byte to fetch with GET range",
+                            "data-touch-tooltip": touch_tooltip(
+                                "First byte to fetch with GET range",
+                            ),
+                        }
+                    }
+                    label {
+                        "Range end (bytes)"
+                        input {
+                            value: range_end_value,
+                            oninput: move |evt| range_end_binding.set(evt.value()),
+                            placeholder: "End of entry",
+                            title: "Last byte to fetch (inclusive); leave empty to read to the end",
+                            "data-touch-tooltip": touch_tooltip(
+                                "Last byte to fetch (inclusive); leave empty to read to the end",
+                            ),
+                        }
+                    }
                     label {
                         "Body"
                         textarea {
                             class: "tall",
                             value: body_value.clone(),
                             oninput: move |evt| storage_body_binding.set(evt.value()),
+                            disabled: upload_summary.is_some(),
                             title: "Content to upload when storing data",
                             "data-touch-tooltip": touch_tooltip(
                                 "Content to upload when storing data",
@@ -101,6 +274,55 @@ pub fn render_storage_tab(
                         }
                     }
                 }
+                div { class: "upload-mode",
+                    if let Some(summary) = upload_summary.clone() {
+                        span { class: "upload-mode-label", "PUT sends file" }
+                        span { class: "mono", {summary} }
+                        button {
+                            class: "action secondary",
+                            title: "Forget the chosen file and send the text body again",
+                            "data-touch-tooltip": touch_tooltip(
+                                "Forget the chosen file and send the text body again",
+                            ),
+                            onclick: move |_| clear_upload_signal.set(None),
+                            "Use text body"
+                        }
+                    } else {
+                        span { class: "upload-mode-label", "PUT sends the text body" }
+                    }
+                    if let Some(check) = document_check.as_ref() {
+                        span {
+                            class: if check.outcome.is_ok() { "spec-badge spec-badge-valid" } else { "spec-badge spec-badge-invalid" },
+                            title: "Checked against the pubky.app type this path holds",
+                            {check.summary()}
+                        }
+                    }
+                    button {
+                        class: "action secondary",
+                        title: "Load a file from disk to PUT as raw bytes instead of the text body",
+                        "data-touch-tooltip": touch_tooltip(
+                            "Load a file from disk to PUT as raw bytes instead of the text body",
+                        ),
+                        onclick: move |_| {
+                            let chosen = match file_dialog::pick_file() {
+                                FileDialogResult::Selected(path) => path,
+                                FileDialogResult::Unavailable => {
+                                    choose_upload_logs.info(file_dialog::MANUAL_ENTRY_HINT);
+                                    return;
+                                }
+                                FileDialogResult::Cancelled => return,
+                            };
+                            match UploadFile::load(&chosen) {
+                                Ok(file) => {
+                                    choose_upload_logs.info(format!("Selected {} for upload", file.summary()));
+                                    choose_upload_signal.set(Some(file));
+                                }
+                                Err(err) => choose_upload_logs.error(format!("Failed to load file: {err:#}")),
+                            }
+                        },
+                        "Choose file"
+                    }
+                }
                 div { class: "small-buttons",
                     button {
                         class: "action",
@@ -116,12 +338,14 @@ pub fn render_storage_tab(
                                     return;
                                 }
                                 let mut response_signal = storage_response_get.clone();
+                                let mut header_rows_signal = response_headers;
                                 let logs_task = storage_logs_get.clone();
                                 spawn(async move {
                                     let result = async move {
                                         let resp = session.storage().get(path.clone()).await?;
-                                        let formatted = format_response(resp).await?;
-                                        response_signal.set(formatted.clone());
+                                        let (formatted, rows) = format_response_with_headers(resp).await?;
+                                        response_signal.set(formatted);
+                                        header_rows_signal.set(rows);
                                         Ok::<_, anyhow::Error>(format!("Fetched {path}"))
                                     };
                                     match result.await {
@@ -135,6 +359,73 @@ pub fn render_storage_tab(
                         },
                         "GET",
                     }
+                    button {
+                        class: "action secondary",
+                        title: "Fetch only the byte range above from this path",
+                        "data-touch-tooltip": touch_tooltip(
+                            "Fetch only the byte range above from this path",
+                        ),
+                        onclick: move |_| {
+                            let Some(session) = range_session.read().as_ref().cloned() else {
+                                range_logs.error("No active session");
+                                return;
+                            };
+                            let path = range_path.read().clone();
+                            if path.trim().is_empty() {
+                                range_logs.error("Provide a path to GET");
+                                return;
+                            }
+                            let range = match ByteRange::parse(
+                                &range_start_get.read(),
+                                &range_end_get.read(),
+                            ) {
+                                Ok(range) => range,
+                                Err(err) => {
+                                    range_logs.error(format!("Invalid range: {err}"));
+                                    return;
+                                }
+                            };
+                            let Some(pubky) = range_pubky.ready_or_log(&range_logs) else {
+                                return;
+                            };
+                            let mut response_signal = range_response.clone();
+                            let mut header_rows_signal = response_headers;
+                            let logs_task = range_logs.clone();
+                            spawn(async move {
+                                let result = async move {
+                                    let url = Url::parse(&format!(
+                                        "pubky://{}{}",
+                                        session.info().public_key(),
+                                        path.trim()
+                                    ))?;
+                                    let resp = pubky
+                                        .client()
+                                        .request(Method::GET, url)
+                                        .header(RANGE, range.header_value())
+                                        .send()
+                                        .await?;
+                                    let status = resp.status();
+                                    let version = resp.version();
+                                    let headers = resp.headers().clone();
+                                    let bytes = resp.bytes().await?;
+                                    let note = describe_range_response(
+                                        status.as_u16(),
+                                        headers.get(CONTENT_RANGE).and_then(|value| value.to_str().ok()),
+                                        bytes.len(),
+                                    );
+                                    let formatted = format_response_parts(status, version, &headers, &bytes);
+                                    response_signal.set(format!("{note}\n\n{formatted}"));
+                                    header_rows_signal.set(header_rows(&headers));
+                                    Ok::<_, anyhow::Error>(format!("Fetched {path} ({note})"))
+                                };
+                                match result.await {
+                                    Ok(msg) => logs_task.success(msg),
+                                    Err(err) => logs_task.error(format!("Range GET failed: {err}")),
+                                }
+                            });
+                        },
+                        "GET range",
+                    }
                     button {
                         class: "action secondary",
                         title: "Write the body above to this storage path",
@@ -149,14 +440,62 @@ pub fn render_storage_tab(
                                     return;
                                 }
                                 let body = storage_body_put.read().clone();
+                                let upload = storage_upload_put.read().clone();
                                 let mut response_signal = storage_response_put.clone();
+                                let mut header_rows_signal = response_headers;
                                 let logs_task = storage_logs_put.clone();
+                                let facade = storage_pubky_put.ensure_ready().ok();
                                 spawn(async move {
+                                    let existing = if mainnet_guard.checks_overwrite() {
+                                        let checked = match facade.as_deref() {
+                                            Some(pubky) => entry_exists(pubky, &session, &path).await,
+                                            None => Err(anyhow::anyhow!("the Pubky client is not ready")),
+                                        };
+                                        match checked {
+                                            Ok(exists) => Some(exists),
+                                            Err(err) => {
+                                                logs_task.info(format!(
+                                                    "Could not check whether {path} exists: {err}"
+                                                ));
+                                                None
+                                            }
+                                        }
+                                    } else {
+                                        None
+                                    };
+                                    let allowed = match existing {
+                                        // The overwrite prompt stands in for the mainnet one.
+                                        Some(true) => mainnet_guard.confirm_overwrite(&path).await,
+                                        Some(false) => mainnet_guard.allow(&format!("Create {path}")).await,
+                                        None => {
+                                            let action = format!("Write {path}, replacing any existing content");
+                                            mainnet_guard.allow(&action).await
+                                        }
+                                    };
+                                    if !allowed {
+                                        logs_task.info(format!("PUT {path} cancelled"));
+                                        return;
+                                    }
                                     let result = async move {
-                                        let resp = session.storage().put(path.clone(), body.clone()).await?;
-                                        let formatted = format_response(resp).await?;
-                                        response_signal.set(formatted.clone());
-                                        Ok::<_, anyhow::Error>(format!("Stored {path}"))
+                                        let (resp, stored) = match upload {
+                                            Some(file) => {
+                                                let pubky = facade
+                                                    .as_deref()
+                                                    .ok_or_else(|| anyhow::anyhow!("the Pubky client is not ready"))?;
+                                                (
+                                                    put_file(pubky, &session, &path, &file).await?,
+                                                    format!("Stored {path} from {}", file.summary()),
+                                                )
+                                            }
+                                            None => (
+                                                session.storage().put(path.clone(), body.clone()).await?,
+                                                format!("Stored {path}"),
+                                            ),
+                                        };
+                                        let (formatted, rows) = format_response_with_headers(resp).await?;
+                                        response_signal.set(formatted);
+                                        header_rows_signal.set(rows);
+                                        Ok::<_, anyhow::Error>(stored)
                                     };
                                     match result.await {
                                         Ok(msg) => logs_task.success(msg),
@@ -169,13 +508,12 @@ pub fn render_storage_tab(
                         },
                         "PUT",
                     }
-                    button {
-                        class: "action secondary",
-                        title: "Delete the resource stored at this path",
-                        "data-touch-tooltip": touch_tooltip(
-                            "Delete the resource stored at this path",
-                        ),
-                        onclick: move |_| {
+                    ConfirmButton {
+                        label: "DELETE",
+                        confirm_label: "Confirm DELETE?",
+                        danger: true,
+                        title: "Delete the resource stored at this path (click twice to confirm)",
+                        on_confirm: move |_| {
                             if let Some(session) = storage_session_delete.read().as_ref().cloned() {
                                 let path = storage_path_delete.read().clone();
                                 if path.trim().is_empty() {
@@ -183,12 +521,18 @@ pub fn render_storage_tab(
                                     return;
                                 }
                                 let mut response_signal = storage_response_delete.clone();
+                                let mut header_rows_signal = response_headers;
                                 let logs_task = storage_logs_delete.clone();
                                 spawn(async move {
+                                    if !mainnet_guard.allow(&format!("Delete {path}")).await {
+                                        logs_task.info(format!("DELETE {path} cancelled"));
+                                        return;
+                                    }
                                     let result = async move {
                                         let resp = session.storage().delete(path.clone()).await?;
-                                        let formatted = format_response(resp).await?;
-                                        response_signal.set(formatted.clone());
+                                        let (formatted, rows) = format_response_with_headers(resp).await?;
+                                        response_signal.set(formatted);
+                                        header_rows_signal.set(rows);
                                         Ok::<_, anyhow::Error>(format!("Deleted {path}"))
                                     };
                                     match result.await {
@@ -200,7 +544,36 @@ pub fn render_storage_tab(
                                 storage_logs_delete.error("No active session");
                             }
                         },
-                        "DELETE",
+                    }
+                    button {
+                        class: "action secondary",
+                        title: "List the entries of the directory containing this path",
+                        "data-touch-tooltip": touch_tooltip(
+                            "List the entries of the directory containing this path",
+                        ),
+                        onclick: move |_| {
+                            let current = list_path.read().clone();
+                            navigate_to(directory_of(&current), list_session, list_path, list_listing, &list_logs);
+                        },
+                        "LIST",
+                    }
+                }
+                if !listing_value.is_empty() {
+                    ul { class: "listing",
+                        for entry in listing_value {
+                            li {
+                                button {
+                                    class: "crumb",
+                                    title: "Open {entry}",
+                                    onclick: {
+                                        let target = entry_path(&entry);
+                                        let logs_entry = logs.clone();
+                                        move |_| navigate_to(target.clone(), session, path, listing, &logs_entry)
+                                    },
+                                    {entry_path(&entry)}
+                                }
+                            }
+                        }
                     }
                 }
                 if !session_response.is_empty() {
@@ -211,14 +584,30 @@ pub fn render_storage_tab(
                         ),
                         "data-touch-copy": touch_copy_option(session_copy_value.clone()),
                         "data-copy-success": session_copy_success.clone(),
-                        {session_response}
+                        {session_response.clone()}
                     }
+                    ResponseCopyButtons { response: session_response }
+                    HeaderTable { headers: session_header_rows }
                 }
             }
             section { class: "card",
                 h2 { "Public storage" }
-                p { class: "helper-text", "Fetch any public resource (pubky<pk>/path or pubky://...)." }
+                p { class: "helper-text",
+                    "Fetch any public resource: paste a pubky<pk>/path or pubky://... link, or enter a user key and a /pub/ path. JSON is pretty-printed, text is shown as-is, and small images are previewed."
+                }
                 div { class: "form-grid",
+                    label {
+                        "User key (optional)"
+                        input {
+                            value: public_owner_value,
+                            oninput: move |evt| public_owner_binding.set(evt.value()),
+                            placeholder: "Leave empty when the resource is a full link",
+                            title: "Public key of the user whose public data you want to read",
+                            "data-touch-tooltip": touch_tooltip(
+                                "Public key of the user whose public data you want to read",
+                            ),
+                        }
+                    }
                     label {
                         "Resource"
                         input {
@@ -239,22 +628,37 @@ pub fn render_storage_tab(
                             "Fetch the public resource using the Pubky client",
                         ),
                         onclick: move |_| {
+                            let owner = public_owner_signal.read().clone();
                             let resource = public_resource_signal.read().clone();
-                            if resource.trim().is_empty() {
-                                public_logs.error("Provide a resource to fetch");
-                                return;
-                            }
+                            let target = match public_resource_target(&owner, &resource) {
+                                Ok(target) => target,
+                                Err(err) => {
+                                    public_logs.error(err.to_string());
+                                    return;
+                                }
+                            };
                             let Some(pubky) = pubky.ready_or_log(&public_logs) else {
                                 return;
                             };
                             let mut response_signal = public_response_signal.clone();
+                            let mut preview_signal = public_preview_signal.clone();
+                            let mut header_rows_signal = public_headers;
                             let logs_task = public_logs.clone();
                             spawn(async move {
                                 let result = async move {
-                                    let resp = pubky.public_storage().get(resource.clone()).await?;
-                                    let formatted = format_response(resp).await?;
-                                    response_signal.set(formatted.clone());
-                                    Ok::<_, anyhow::Error>(format!("Fetched public resource {resource}"))
+                                    let resp = pubky.public_storage().get(target.clone()).await?;
+                                    let status = resp.status();
+                                    let version = resp.version();
+                                    let headers = resp.headers().clone();
+                                    let bytes = resp.bytes().await?;
+                                    let content_type = headers
+                                        .get(CONTENT_TYPE)
+                                        .and_then(|value| value.to_str().ok());
+                                    preview_signal.set(image_preview_data_url(content_type, &bytes));
+                                    response_signal
+                                        .set(format_response_parts(status, version, &headers, &bytes));
+                                    header_rows_signal.set(header_rows(&headers));
+                                    Ok::<_, anyhow::Error>(format!("Fetched public resource {target}"))
                                 };
                                 match result.await {
                                     Ok(msg) => logs_task.success(msg),
@@ -265,6 +669,12 @@ pub fn render_storage_tab(
                         "GET",
                     }
                 }
+                if let Some(link) = public_link {
+                    QrToggle { value: link }
+                }
+                if let Some(src) = public_preview_value {
+                    img { class: "public-preview", src, alt: "Image preview of the public resource" }
+                }
                 if !public_resp.is_empty() {
                     div {
                         class: "outputs copyable",
@@ -273,8 +683,10 @@ pub fn render_storage_tab(
                         ),
                         "data-touch-copy": touch_copy_option(public_copy_value.clone()),
                         "data-copy-success": public_copy_success.clone(),
-                        {public_resp}
+                        {public_resp.clone()}
                     }
+                    ResponseCopyButtons { response: public_resp }
+                    HeaderTable { headers: public_header_rows }
                 }
             }
         }
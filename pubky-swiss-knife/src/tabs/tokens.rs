@@ -1,25 +1,66 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
 use base64::{Engine as _, engine::general_purpose::STANDARD};
 use dioxus::prelude::*;
 use pubky::{AuthToken, Capabilities};
 
+use crate::components::QrToggle;
 use crate::tabs::TokensTabState;
+use crate::utils::capabilities::CapabilityPreset;
 use crate::utils::logging::ActivityLog;
 use crate::utils::mobile::{is_android_touch, touch_copy_option, touch_tooltip};
 
+/// Fields read back from a signed token, for display only.
+struct DecodedToken {
+    public_key: String,
+    capabilities: Vec<String>,
+    signed_at: String,
+}
+
+/// Parse the base64 token shown in the tab. `None` when it cannot be read,
+/// for instance if the token format changes.
+fn decode_token(encoded: &str) -> Option<DecodedToken> {
+    let bytes = STANDARD.decode(encoded.trim()).ok()?;
+    let token = AuthToken::deserialize(&bytes).ok()?;
+    let micros = token.timestamp().as_u64();
+    let now_micros = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_micros() as u64)
+        .unwrap_or(micros);
+    let age_secs = now_micros.saturating_sub(micros) / 1_000_000;
+    Some(DecodedToken {
+        public_key: token.public_key().to_string(),
+        capabilities: token
+            .capabilities()
+            .iter()
+            .map(ToString::to_string)
+            .collect(),
+        signed_at: format!("{micros} µs ({age_secs} s ago)"),
+    })
+}
+
 pub fn render_tokens_tab(state: TokensTabState, logs: ActivityLog) -> Element {
     let TokensTabState {
         keypair,
         capabilities,
+        capability_preset,
         output,
     } = state;
 
     let caps_value = { capabilities.read().clone() };
+    let preset = capability_preset.read().resolve(&caps_value);
+    let caps_locked = preset != CapabilityPreset::Custom;
     let token_value = { output.read().clone() };
     let token_copy_value = if token_value.trim().is_empty() {
         None
     } else {
         Some(token_value.clone())
     };
+    let decoded_token = if token_value.trim().is_empty() {
+        None
+    } else {
+        Some(decode_token(&token_value))
+    };
     let token_copy_success = if is_android_touch() {
         Some(String::from("Copied auth token to clipboard"))
     } else {
@@ -27,6 +68,8 @@ pub fn render_tokens_tab(state: TokensTabState, logs: ActivityLog) -> Element {
     };
 
     let mut token_caps_binding = capabilities;
+    let mut preset_binding = capability_preset;
+    let mut preset_caps = capabilities;
 
     let sign_keypair = keypair;
     let sign_caps = capabilities;
@@ -39,10 +82,35 @@ pub fn render_tokens_tab(state: TokensTabState, logs: ActivityLog) -> Element {
                 h2 { "Sign capability tokens" }
                 p { class: "helper-text", "Compose a capability string (e.g. '/:rw,/pub/app/:r') and sign using the active key." }
                 div { class: "form-grid",
+                    label {
+                        "Preset"
+                        select {
+                            value: preset.key(),
+                            title: "Fill the capabilities from a common scope, or choose Custom to type your own",
+                            "data-touch-tooltip": touch_tooltip(
+                                "Fill the capabilities from a common scope, or choose Custom to type your own",
+                            ),
+                            onchange: move |evt| {
+                                let selected = CapabilityPreset::from_key(&evt.value());
+                                if let Some(caps) = selected.capabilities() {
+                                    preset_caps.set(caps.to_string());
+                                }
+                                preset_binding.set(selected);
+                            },
+                            for option in CapabilityPreset::ALL {
+                                option {
+                                    value: option.key(),
+                                    selected: option == preset,
+                                    {option.label()}
+                                }
+                            }
+                        }
+                    }
                     label {
                         "Capabilities"
                         input {
                             value: caps_value,
+                            readonly: caps_locked,
                             oninput: move |evt| token_caps_binding.set(evt.value()),
                             title: "Enter the capabilities you want to grant, separated by commas",
                             "data-touch-tooltip": touch_tooltip(
@@ -90,6 +158,36 @@ pub fn render_tokens_tab(state: TokensTabState, logs: ActivityLog) -> Element {
                         "data-copy-success": token_copy_success.clone(),
                         {token_value}
                     }
+                    if let Some(value) = token_copy_value.clone() {
+                        QrToggle { value }
+                    }
+                }
+                if let Some(decoded) = decoded_token {
+                    h3 { "Decoded token" }
+                    if let Some(token) = decoded {
+                        table { class: "lookup-table token-fields",
+                            tbody {
+                                tr {
+                                    th { "Public key" }
+                                    td { class: "mono", "{token.public_key}" }
+                                }
+                                tr {
+                                    th { "Signed at" }
+                                    td { class: "mono", "{token.signed_at}" }
+                                }
+                                tr {
+                                    th { "Capabilities" }
+                                    td { class: "mono",
+                                        for capability in token.capabilities {
+                                            div { "{capability}" }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    } else {
+                        p { class: "helper-text", "Token is unparseable." }
+                    }
                 }
             }
         }
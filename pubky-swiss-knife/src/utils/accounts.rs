@@ -0,0 +1,391 @@
+use anyhow::{Context, Result, anyhow};
+use base64::{Engine as _, engine::general_purpose::STANDARD};
+use dioxus::prelude::{ReadableExt, Signal, WritableExt};
+use pubky::{Keypair, PubkySession, recovery_file};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::tabs::format_session_info;
+use crate::utils::logging::ActivityLog;
+use crate::utils::prefs::prefs_path;
+
+const ACCOUNTS_FILE_NAME: &str = "accounts.json";
+
+/// A saved identity. The secret key is stored as an encrypted recovery file
+/// (the same format as `.pkarr` exports), never in plain text.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StoredAccount {
+    pub label: String,
+    pub public_key: String,
+    /// Homeserver last used for sign-in with this account.
+    #[serde(default)]
+    pub homeserver: String,
+    /// Base64 of the recovery file bytes.
+    pub encrypted_secret: String,
+}
+
+impl StoredAccount {
+    pub fn seal(
+        label: &str,
+        keypair: &Keypair,
+        homeserver: &str,
+        passphrase: &str,
+    ) -> Result<Self> {
+        if passphrase.is_empty() {
+            return Err(anyhow!("Set an accounts passphrase first"));
+        }
+        let public_key = keypair.public_key().to_string();
+        let label = match label.trim() {
+            "" => short_key(&public_key),
+            trimmed => trimmed.to_string(),
+        };
+        Ok(Self {
+            label,
+            public_key,
+            homeserver: homeserver.trim().to_string(),
+            encrypted_secret: STANDARD
+                .encode(recovery_file::create_recovery_file(keypair, passphrase)),
+        })
+    }
+
+    pub fn unlock(&self, passphrase: &str) -> Result<Keypair> {
+        let bytes = STANDARD
+            .decode(&self.encrypted_secret)
+            .context("stored account secret is not valid base64")?;
+        let keypair = recovery_file::decrypt_recovery_file(&bytes, passphrase)
+            .map_err(|_| anyhow!("wrong accounts passphrase for {}", self.label))?;
+        if keypair.public_key().to_string() != self.public_key {
+            return Err(anyhow!(
+                "stored secret for {} does not match its public key",
+                self.label
+            ));
+        }
+        Ok(keypair)
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AccountStore {
+    pub accounts: Vec<StoredAccount>,
+}
+
+impl AccountStore {
+    /// Add `account`, replacing any saved account with the same public key.
+    pub fn upsert(&mut self, account: StoredAccount) {
+        match self
+            .accounts
+            .iter_mut()
+            .find(|existing| existing.public_key == account.public_key)
+        {
+            Some(existing) => *existing = account,
+            None => self.accounts.push(account),
+        }
+    }
+
+    pub fn remove(&mut self, public_key: &str) -> bool {
+        let before = self.accounts.len();
+        self.accounts
+            .retain(|account| account.public_key != public_key);
+        self.accounts.len() != before
+    }
+
+    pub fn find(&self, public_key: &str) -> Option<&StoredAccount> {
+        self.accounts
+            .iter()
+            .find(|account| account.public_key == public_key)
+    }
+
+    /// Remember the homeserver for a saved account. Returns `true` on change.
+    pub fn set_homeserver(&mut self, public_key: &str, homeserver: &str) -> bool {
+        let homeserver = homeserver.trim();
+        match self
+            .accounts
+            .iter_mut()
+            .find(|account| account.public_key == public_key)
+        {
+            Some(account) if account.homeserver != homeserver => {
+                account.homeserver = homeserver.to_string();
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Saved accounts live next to `prefs.json`, in their own file.
+pub fn accounts_path() -> Option<PathBuf> {
+    prefs_path().and_then(|path| path.parent().map(|dir| dir.join(ACCOUNTS_FILE_NAME)))
+}
+
+pub fn load_accounts_from(path: &Path) -> Result<AccountStore> {
+    if !path.is_file() {
+        return Ok(AccountStore::default());
+    }
+    let bytes = fs::read(path).with_context(|| format!("failed to read {}", path.display()))?;
+    serde_json::from_slice(&bytes).with_context(|| format!("failed to parse {}", path.display()))
+}
+
+/// Write `store` to `path`. An existing file that no longer parses is moved
+/// to `<path>.bak` first, so a save can never destroy identities the app
+/// failed to load; if it cannot be moved, nothing is written.
+pub fn save_accounts_to(path: &Path, store: &AccountStore) -> Result<()> {
+    if path.is_file() && load_accounts_from(path).is_err() {
+        let backup = backup_path(path);
+        fs::rename(path, &backup).with_context(|| {
+            format!(
+                "refusing to overwrite unreadable {}; failed to move it to {}",
+                path.display(),
+                backup.display()
+            )
+        })?;
+    }
+    if let Some(parent) = path.parent()
+        && !parent.as_os_str().is_empty()
+    {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("failed to create directory {}", parent.display()))?;
+    }
+    let rendered = serde_json::to_vec_pretty(store)?;
+    fs::write(path, rendered).with_context(|| format!("failed to write {}", path.display()))
+}
+
+/// Load the saved accounts; none when there is no accounts file yet.
+pub fn load_accounts() -> Result<AccountStore> {
+    match accounts_path() {
+        Some(path) => load_accounts_from(&path),
+        None => Ok(AccountStore::default()),
+    }
+}
+
+pub fn save_accounts(store: &AccountStore) -> Result<()> {
+    match accounts_path() {
+        Some(path) => save_accounts_to(&path, store),
+        None => Ok(()),
+    }
+}
+
+fn backup_path(path: &Path) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(".bak");
+    PathBuf::from(name)
+}
+
+fn short_key(public_key: &str) -> String {
+    let key = public_key.strip_prefix("pubky").unwrap_or(public_key);
+    format!("{}…", key.chars().take(8).collect::<String>())
+}
+
+/// Everything an account switch touches. Sessions are kept per account for
+/// the lifetime of the app so switching back does not require a new sign-in.
+#[derive(Clone, Copy)]
+pub struct AccountSwitcher {
+    pub accounts: Signal<AccountStore>,
+    /// Unlocks every saved account; held in memory only.
+    pub passphrase: Signal<String>,
+    keypair: Signal<Option<Keypair>>,
+    session: Signal<Option<PubkySession>>,
+    session_details: Signal<String>,
+    homeserver: Signal<String>,
+    parked_sessions: Signal<HashMap<String, PubkySession>>,
+}
+
+impl AccountSwitcher {
+    pub fn new(
+        accounts: Signal<AccountStore>,
+        passphrase: Signal<String>,
+        keypair: Signal<Option<Keypair>>,
+        session: Signal<Option<PubkySession>>,
+        session_details: Signal<String>,
+        homeserver: Signal<String>,
+        parked_sessions: Signal<HashMap<String, PubkySession>>,
+    ) -> Self {
+        Self {
+            accounts,
+            passphrase,
+            keypair,
+            session,
+            session_details,
+            homeserver,
+            parked_sessions,
+        }
+    }
+
+    /// Public key of the active keypair, if any.
+    pub fn active_key(&self) -> Option<String> {
+        self.keypair
+            .read()
+            .as_ref()
+            .map(|kp| kp.public_key().to_string())
+    }
+
+    /// Save the active keypair (and the homeserver field) as an account.
+    pub fn save_current(&self, label: &str, logs: &ActivityLog) {
+        let Some(keypair) = self.keypair.read().clone() else {
+            logs.error("Load or generate a key before saving it as an account");
+            return;
+        };
+        let passphrase = self.passphrase.read().clone();
+        let homeserver = self.homeserver.read().clone();
+        match StoredAccount::seal(label, &keypair, &homeserver, &passphrase) {
+            Ok(account) => {
+                let name = account.label.clone();
+                self.update(logs, |store| store.upsert(account));
+                logs.success(format!("Saved account {name}"));
+            }
+            Err(err) => logs.error(format!("Account not saved: {err}")),
+        }
+    }
+
+    pub fn remove(&self, public_key: &str, logs: &ActivityLog) {
+        let mut parked = self.parked_sessions;
+        parked.write().remove(public_key);
+        let mut removed = false;
+        self.update(logs, |store| removed = store.remove(public_key));
+        if removed {
+            logs.info("Removed saved account");
+        }
+    }
+
+    /// Make the saved account with `public_key` the active identity. The
+    /// current session is parked under its own key and the target account's
+    /// parked session (if any) is restored.
+    pub fn switch_to(&self, public_key: &str, logs: &ActivityLog) {
+        if self.active_key().as_deref() == Some(public_key) {
+            return;
+        }
+        let Some(account) = self.accounts.read().find(public_key).cloned() else {
+            logs.error("That account is no longer saved");
+            return;
+        };
+        let passphrase = self.passphrase.read().clone();
+        if passphrase.is_empty() {
+            logs.error("Enter the accounts passphrase in the Keys tab to switch accounts");
+            return;
+        }
+        let keypair = match account.unlock(&passphrase) {
+            Ok(keypair) => keypair,
+            Err(err) => {
+                logs.error(format!("Could not switch accounts: {err}"));
+                return;
+            }
+        };
+
+        let (mut keypair_signal, mut session, mut details, mut homeserver, mut parked) = (
+            self.keypair,
+            self.session,
+            self.session_details,
+            self.homeserver,
+            self.parked_sessions,
+        );
+        if let Some(previous) = self.active_key() {
+            let previous_homeserver = homeserver.read().clone();
+            self.update(logs, |store| {
+                store.set_homeserver(&previous, &previous_homeserver);
+            });
+        }
+        // Park under the session's own key: an approved auth flow can leave a
+        // session for a different identity than the loaded keypair.
+        if let Some(current) = session.write().take() {
+            parked
+                .write()
+                .insert(current.info().public_key().to_string(), current);
+        }
+
+        let restored = parked.write().remove(public_key);
+        details.set(
+            restored
+                .as_ref()
+                .map(|session| format_session_info(session.info()))
+                .unwrap_or_default(),
+        );
+        session.set(restored);
+        keypair_signal.set(Some(keypair));
+        if !account.homeserver.is_empty() {
+            homeserver.set(account.homeserver.clone());
+        }
+        logs.success(format!("Switched to account {}", account.label));
+    }
+
+    fn update(&self, logs: &ActivityLog, apply: impl FnOnce(&mut AccountStore)) {
+        let mut accounts = self.accounts;
+        let updated = {
+            let mut guard = accounts.write();
+            apply(&mut guard);
+            guard.clone()
+        };
+        if let Err(err) = save_accounts(&updated) {
+            logs.error(format!("Failed to save accounts: {err}"));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn sealed_accounts_unlock_with_the_right_passphrase() -> Result<()> {
+        let keypair = Keypair::random();
+        let account = StoredAccount::seal("alice", &keypair, " hs1 ", "correct horse")?;
+        assert_eq!(account.homeserver, "hs1");
+        assert!(!account.encrypted_secret.is_empty());
+
+        let unlocked = account.unlock("correct horse")?;
+        assert_eq!(unlocked.public_key(), keypair.public_key());
+        assert!(account.unlock("wrong").is_err());
+        assert!(StoredAccount::seal("bob", &keypair, "", "").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn store_upserts_by_public_key() -> Result<()> {
+        let keypair = Keypair::random();
+        let mut store = AccountStore::default();
+        store.upsert(StoredAccount::seal("first", &keypair, "", "pw")?);
+        store.upsert(StoredAccount::seal("renamed", &keypair, "", "pw")?);
+        assert_eq!(store.accounts.len(), 1);
+        assert_eq!(store.accounts[0].label, "renamed");
+
+        let key = keypair.public_key().to_string();
+        assert!(store.set_homeserver(&key, "hs2"));
+        assert!(!store.set_homeserver(&key, "hs2"));
+        assert_eq!(store.find(&key).map(|a| a.homeserver.as_str()), Some("hs2"));
+        assert!(store.remove(&key));
+        assert!(!store.remove(&key));
+        Ok(())
+    }
+
+    #[test]
+    fn accounts_roundtrip_through_disk() -> Result<()> {
+        let dir = TempDir::new()?;
+        let path = dir.path().join("nested").join(ACCOUNTS_FILE_NAME);
+        assert_eq!(load_accounts_from(&path)?, AccountStore::default());
+
+        let mut store = AccountStore::default();
+        store.upsert(StoredAccount::seal("", &Keypair::random(), "", "pw")?);
+        save_accounts_to(&path, &store)?;
+        let loaded = load_accounts_from(&path)?;
+        assert_eq!(loaded, store);
+        assert!(loaded.accounts[0].label.ends_with('…'));
+        Ok(())
+    }
+
+    #[test]
+    fn corrupt_accounts_file_is_set_aside_before_saving() -> Result<()> {
+        let dir = TempDir::new()?;
+        let path = dir.path().join(ACCOUNTS_FILE_NAME);
+        fs::write(&path, b"{ not json")?;
+        assert!(load_accounts_from(&path).is_err());
+
+        let mut store = AccountStore::default();
+        store.upsert(StoredAccount::seal("new", &Keypair::random(), "", "pw")?);
+        save_accounts_to(&path, &store)?;
+        assert_eq!(load_accounts_from(&path)?, store);
+        assert_eq!(fs::read(backup_path(&path))?, b"{ not json");
+        Ok(())
+    }
+}
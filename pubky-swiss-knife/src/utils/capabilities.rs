@@ -0,0 +1,118 @@
+use pubky::Capabilities;
+
+/// Common scopes for the Tokens tab, plus free-form entry.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CapabilityPreset {
+    #[default]
+    FullAccess,
+    ReadOnly,
+    SocialApp,
+    Custom,
+}
+
+impl CapabilityPreset {
+    pub const ALL: [CapabilityPreset; 4] = [
+        CapabilityPreset::FullAccess,
+        CapabilityPreset::ReadOnly,
+        CapabilityPreset::SocialApp,
+        CapabilityPreset::Custom,
+    ];
+
+    pub fn key(self) -> &'static str {
+        match self {
+            CapabilityPreset::FullAccess => "full",
+            CapabilityPreset::ReadOnly => "read-only",
+            CapabilityPreset::SocialApp => "social",
+            CapabilityPreset::Custom => "custom",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            CapabilityPreset::FullAccess => "Full access (/:rw)",
+            CapabilityPreset::ReadOnly => "Read only (/:r)",
+            CapabilityPreset::SocialApp => "pubky.app social (/pub/pubky.app/:rw)",
+            CapabilityPreset::Custom => "Custom",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Self {
+        Self::ALL
+            .into_iter()
+            .find(|preset| preset.key() == key)
+            .unwrap_or(CapabilityPreset::Custom)
+    }
+
+    /// The parsed scopes, or `None` for [`CapabilityPreset::Custom`].
+    pub fn capabilities(self) -> Option<Capabilities> {
+        let scopes = match self {
+            CapabilityPreset::FullAccess => "/:rw",
+            CapabilityPreset::ReadOnly => "/:r",
+            CapabilityPreset::SocialApp => "/pub/pubky.app/:rw",
+            CapabilityPreset::Custom => return None,
+        };
+        Capabilities::try_from(scopes).ok()
+    }
+
+    /// The preset whose scopes equal `value`, or `Custom`.
+    pub fn matching(value: &str) -> Self {
+        let Ok(parsed) = Capabilities::try_from(value.trim()) else {
+            return CapabilityPreset::Custom;
+        };
+        Self::ALL
+            .into_iter()
+            .find(|preset| preset.capabilities().is_some_and(|caps| caps == parsed))
+            .unwrap_or(CapabilityPreset::Custom)
+    }
+
+    /// What the picker shows for `value`: a chosen `Custom` stays custom,
+    /// otherwise the preset follows the field, which can also change when the
+    /// network defaults or saved inputs are applied.
+    pub fn resolve(self, value: &str) -> Self {
+        match self {
+            CapabilityPreset::Custom => CapabilityPreset::Custom,
+            _ => Self::matching(value),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_preset_but_custom_parses() {
+        for preset in CapabilityPreset::ALL {
+            assert_eq!(
+                preset.capabilities().is_some(),
+                preset != CapabilityPreset::Custom,
+                "{preset:?}"
+            );
+            assert_eq!(CapabilityPreset::from_key(preset.key()), preset);
+        }
+    }
+
+    #[test]
+    fn presets_follow_the_field() {
+        assert_eq!(
+            CapabilityPreset::matching(" /pub/pubky.app/:rw "),
+            CapabilityPreset::SocialApp
+        );
+        assert_eq!(
+            CapabilityPreset::matching("/pub/demo/:r"),
+            CapabilityPreset::Custom
+        );
+        assert_eq!(
+            CapabilityPreset::matching("not a scope"),
+            CapabilityPreset::Custom
+        );
+        assert_eq!(
+            CapabilityPreset::FullAccess.resolve("/:r"),
+            CapabilityPreset::ReadOnly
+        );
+        assert_eq!(
+            CapabilityPreset::Custom.resolve("/:rw"),
+            CapabilityPreset::Custom
+        );
+    }
+}
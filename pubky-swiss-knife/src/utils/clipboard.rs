@@ -0,0 +1,17 @@
+use dioxus::prelude::document;
+
+/// Write `text` to the system clipboard through the WebView.
+pub fn copy_to_clipboard(text: &str) {
+    let literal = serde_json::to_string(text).unwrap_or_else(|_| String::from("\"\""));
+    let _ = document::eval(&format!("navigator.clipboard.writeText({literal});"));
+}
+
+/// Read text from the system clipboard through the WebView. `None` when the
+/// clipboard is empty, holds no text, or cannot be read.
+pub async fn read_clipboard() -> Option<String> {
+    document::eval("return await navigator.clipboard.readText();")
+        .join::<String>()
+        .await
+        .ok()
+        .filter(|text| !text.trim().is_empty())
+}
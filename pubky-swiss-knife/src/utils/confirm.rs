@@ -0,0 +1,75 @@
+use dioxus::prelude::{ReadableExt, Signal};
+
+use crate::app::NetworkMode;
+use crate::utils::prefs::PrefsHandle;
+
+/// Asks before deletes, overwrites, and record publishes when they would hit
+/// mainnet and the "confirm destructive mainnet actions" setting is on.
+#[derive(Clone, Copy)]
+pub struct MainnetGuard {
+    prefs: PrefsHandle,
+    network_mode: Signal<NetworkMode>,
+}
+
+impl MainnetGuard {
+    pub fn new(prefs: PrefsHandle, network_mode: Signal<NetworkMode>) -> Self {
+        Self {
+            prefs,
+            network_mode,
+        }
+    }
+
+    /// Returns `true` when `action` may go ahead, prompting first if needed.
+    pub async fn allow(self, action: &str) -> bool {
+        let network = *self.network_mode.peek();
+        if !self.prefs.snapshot().confirms_destructive(network) {
+            return true;
+        }
+        confirm_dialog(
+            "Confirm mainnet action",
+            &format!(
+                "{action} on Mainnet?\n\nThis changes live data on a real homeserver and cannot be undone."
+            ),
+        )
+        .await
+    }
+
+    /// Whether storage writes should look for an entry they would replace.
+    pub fn checks_overwrite(self) -> bool {
+        self.prefs
+            .snapshot()
+            .checks_overwrite(*self.network_mode.peek())
+    }
+
+    /// Ask before replacing the entry that already exists at `path`.
+    pub async fn confirm_overwrite(self, path: &str) -> bool {
+        confirm_dialog(
+            "Confirm overwrite",
+            &format!(
+                "This will overwrite an existing entry at {path}.\n\nIts current content will be lost."
+            ),
+        )
+        .await
+    }
+}
+
+#[cfg(target_os = "android")]
+async fn confirm_dialog(_title: &str, message: &str) -> bool {
+    let literal = serde_json::to_string(message).unwrap_or_else(|_| String::from("\"\""));
+    dioxus::prelude::document::eval(&format!("return window.confirm({literal});"))
+        .join::<bool>()
+        .await
+        .unwrap_or(false)
+}
+
+#[cfg(not(target_os = "android"))]
+async fn confirm_dialog(title: &str, message: &str) -> bool {
+    let result = rfd::AsyncMessageDialog::new()
+        .set_level(rfd::MessageLevel::Warning)
+        .set_title(title)
+        .set_description(message)
+        .set_buttons(rfd::MessageButtons::OkCancel)
+        .show()
+        .await;
+    result == rfd::MessageDialogResult::Ok
+}
@@ -0,0 +1,187 @@
+use anyhow::{Context, Result};
+use std::fmt::Write as _;
+use std::fs;
+use std::path::PathBuf;
+
+use crate::app::NetworkMode;
+use crate::utils::logging::LogEntry;
+use crate::utils::prefs::AppPrefs;
+use crate::utils::pubky::{PubkyFacadeState, PubkyFacadeStatus};
+use crate::utils::session::SessionHealth;
+
+/// Query parameters whose values are masked if they ever show up in a log line.
+const SENSITIVE_PARAMS: [&str; 3] = ["secret=", "token=", "passphrase="];
+
+const REDACTED: &str = "[redacted]";
+
+/// Everything that goes into a diagnostics report. Key material, session
+/// tokens, and passphrases are deliberately not part of it.
+pub struct DiagnosticsSnapshot<'a> {
+    pub network: NetworkMode,
+    pub facade: &'a PubkyFacadeState,
+    pub session_present: bool,
+    pub session_health: SessionHealth,
+    pub prefs: &'a AppPrefs,
+    pub logs: &'a [LogEntry],
+}
+
+/// Render a plain-text report suitable for attaching to a bug report.
+pub fn format_diagnostics(snapshot: &DiagnosticsSnapshot<'_>) -> String {
+    let mut report = String::new();
+    let _ = writeln!(report, "Pubky Swiss Knife diagnostics");
+    let _ = writeln!(report, "=============================");
+    let _ = writeln!(report, "App version: {}", env!("CARGO_PKG_VERSION"));
+    let _ = writeln!(
+        report,
+        "Platform: {} ({})",
+        std::env::consts::OS,
+        std::env::consts::ARCH
+    );
+    let _ = writeln!(report, "Network: {}", snapshot.network.label());
+
+    let facade_status = match &snapshot.facade.status {
+        PubkyFacadeStatus::Loading => String::from("loading"),
+        PubkyFacadeStatus::Ready(_) => String::from("ready"),
+        PubkyFacadeStatus::Error(message) => format!("error: {}", redact_secrets(message)),
+    };
+    let _ = writeln!(
+        report,
+        "Pubky facade ({}): {facade_status}",
+        snapshot.facade.network.label()
+    );
+    let session = if snapshot.session_present {
+        format!("{:?}", snapshot.session_health)
+    } else {
+        String::from("none")
+    };
+    let _ = writeln!(report, "Session: {session}");
+
+    let _ = writeln!(report);
+    let _ = writeln!(report, "Settings");
+    let _ = writeln!(report, "--------");
+    for mode in NetworkMode::ALL {
+        let defaults = snapshot.prefs.defaults.for_network(mode);
+        let _ = writeln!(
+            report,
+            "{} defaults: capabilities {}, storage path {}",
+            mode.label(),
+            defaults.capabilities,
+            defaults.storage_path
+        );
+    }
+    let _ = writeln!(
+        report,
+        "Remembered recovery files: {}",
+        snapshot.prefs.recovery.recent_files.len()
+    );
+
+    let _ = writeln!(report);
+    let _ = writeln!(report, "Activity log ({} entries)", snapshot.logs.len());
+    let _ = writeln!(report, "------------");
+    for entry in snapshot.logs {
+        let _ = writeln!(
+            report,
+            "[{}] {}",
+            entry.level_label(),
+            redact_secrets(entry.message())
+        );
+    }
+
+    report
+}
+
+/// Write the report, adding a `.txt` extension when none is given.
+pub fn save_diagnostics(report: &str, path: &str) -> Result<PathBuf> {
+    let mut target = PathBuf::from(path.trim());
+    if target.extension().is_none() {
+        target.set_extension("txt");
+    }
+    if let Some(parent) = target
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+    {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("failed to create directory {}", parent.display()))?;
+    }
+    fs::write(&target, report).with_context(|| format!("failed to write {}", target.display()))?;
+    Ok(target)
+}
+
+/// Mask the values of sensitive query parameters, such as the `secret` in a
+/// `pubkyauth://` URL that ended up in an error message.
+fn redact_secrets(message: &str) -> String {
+    let mut redacted = String::with_capacity(message.len());
+    let mut rest = message;
+    while let Some((index, param)) = SENSITIVE_PARAMS
+        .iter()
+        .filter_map(|param| rest.find(param).map(|index| (index, *param)))
+        .min_by_key(|(index, _)| *index)
+    {
+        let value_start = index + param.len();
+        redacted.push_str(&rest[..value_start]);
+        let value_len = rest[value_start..]
+            .find(|ch: char| ch == '&' || ch == '#' || ch.is_whitespace())
+            .unwrap_or(rest.len() - value_start);
+        if value_len > 0 {
+            redacted.push_str(REDACTED);
+        }
+        rest = &rest[value_start + value_len..];
+    }
+    redacted.push_str(rest);
+    redacted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::logging::LogLevel;
+    use tempfile::TempDir;
+
+    #[test]
+    fn report_includes_context_but_not_recovery_paths() {
+        let mut prefs = AppPrefs::default();
+        prefs
+            .recovery
+            .remember(std::path::Path::new("/home/alice/keys/me.pkarr"));
+        let facade = PubkyFacadeState::error(NetworkMode::Testnet, "relay down");
+        let logs = vec![LogEntry::new(LogLevel::Error, "Request failed")];
+
+        let report = format_diagnostics(&DiagnosticsSnapshot {
+            network: NetworkMode::Testnet,
+            facade: &facade,
+            session_present: true,
+            session_health: SessionHealth::Expired,
+            prefs: &prefs,
+            logs: &logs,
+        });
+
+        assert!(report.contains("Network: Testnet"));
+        assert!(report.contains("Pubky facade (Testnet): error: relay down"));
+        assert!(report.contains("Session: Expired"));
+        assert!(report.contains("Remembered recovery files: 1"));
+        assert!(report.contains("[error] Request failed"));
+        assert!(!report.contains("/home/alice"));
+    }
+
+    #[test]
+    fn redacts_sensitive_parameters() {
+        assert_eq!(
+            redact_secrets("bad url pubkyauth:///?caps=/:rw&secret=abc123&relay=x failed"),
+            "bad url pubkyauth:///?caps=/:rw&secret=[redacted]&relay=x failed"
+        );
+        assert_eq!(
+            redact_secrets("token=t1 then passphrase=p2"),
+            "token=[redacted] then passphrase=[redacted]"
+        );
+        assert_eq!(redact_secrets("nothing to hide"), "nothing to hide");
+    }
+
+    #[test]
+    fn save_diagnostics_adds_extension() -> Result<()> {
+        let dir = TempDir::new()?;
+        let saved = save_diagnostics("report", dir.path().join("bug").to_str().unwrap())?;
+        assert_eq!(saved.extension().and_then(|ext| ext.to_str()), Some("txt"));
+        assert_eq!(fs::read_to_string(saved)?, "report");
+        Ok(())
+    }
+}
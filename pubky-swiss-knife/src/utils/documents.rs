@@ -0,0 +1,124 @@
+use pubky_app_specs::{
+    PubkyAppPost, PubkyAppTag, PubkyAppUser,
+    traits::{HasPath, Validatable},
+};
+
+use crate::utils::export::POSTS_DIRECTORY;
+use crate::utils::tags::TAGS_DIRECTORY;
+
+/// Result of checking a body against the pubky.app type its path implies.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DocumentCheck {
+    /// `"profile"`, `"post"`, or `"tag"`.
+    pub kind: &'static str,
+    pub outcome: Result<(), String>,
+}
+
+impl DocumentCheck {
+    /// Badge text, e.g. `"valid post"` or `"invalid tag: …"`.
+    pub fn summary(&self) -> String {
+        match &self.outcome {
+            Ok(()) => format!("valid {}", self.kind),
+            Err(err) => format!("invalid {}: {err}", self.kind),
+        }
+    }
+}
+
+/// Deserialize and validate `body` when `path` is a pubky.app profile, post,
+/// or tag path, using the id in the path. Other paths, and empty bodies, are
+/// not checked.
+pub fn check_app_document(path: &str, body: &[u8]) -> Option<DocumentCheck> {
+    if body.iter().all(u8::is_ascii_whitespace) {
+        return None;
+    }
+    let path = path.trim();
+    let path = if path.starts_with('/') {
+        path.to_string()
+    } else {
+        format!("/{path}")
+    };
+
+    let (kind, outcome) = if path == PubkyAppUser::create_path() {
+        ("profile", validate::<PubkyAppUser>(body, ""))
+    } else if let Some(id) = document_id(&path, POSTS_DIRECTORY) {
+        ("post", validate::<PubkyAppPost>(body, id))
+    } else if let Some(id) = document_id(&path, TAGS_DIRECTORY) {
+        ("tag", validate::<PubkyAppTag>(body, id))
+    } else {
+        return None;
+    };
+    Some(DocumentCheck { kind, outcome })
+}
+
+fn document_id<'a>(path: &'a str, prefix: &str) -> Option<&'a str> {
+    path.strip_prefix(prefix)
+        .filter(|id| !id.is_empty() && !id.contains('/'))
+}
+
+fn validate<T: Validatable>(body: &[u8], id: &str) -> Result<(), String> {
+    T::try_from(body, id).map(|_| ())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::tags::prepare_tag;
+    use pubky_app_specs::{
+        PubkyAppPostKind,
+        traits::{HasIdPath, TimestampId},
+    };
+
+    #[test]
+    fn known_paths_are_checked() {
+        let profile = check_app_document(&PubkyAppUser::create_path(), br#"{"name":"alice"}"#)
+            .expect("profile");
+        assert_eq!(profile.summary(), "valid profile");
+        let nameless = check_app_document("pub/pubky.app/profile.json", br#"{"bio":"hi"}"#)
+            .expect("profile without a leading slash");
+        assert!(nameless.outcome.is_err());
+
+        let post = PubkyAppPost::new(
+            String::from("hello"),
+            PubkyAppPostKind::Short,
+            None,
+            None,
+            None,
+        );
+        let post_path = PubkyAppPost::create_path(&post.create_id());
+        let body = serde_json::to_vec(&post).expect("post serializes");
+        assert_eq!(
+            check_app_document(&post_path, &body).map(|check| check.outcome),
+            Some(Ok(()))
+        );
+        let bad_id = check_app_document("/pub/pubky.app/posts/not-an-id", &body).expect("post");
+        assert!(bad_id.summary().starts_with("invalid post: "));
+
+        let display = pubky::Keypair::random().public_key().to_string();
+        let key = display.strip_prefix("pubky").unwrap_or(&display);
+        let tag = prepare_tag(
+            &format!("pubky://{key}/pub/pubky.app/posts/0033SSE3B1FQ0"),
+            "rust",
+        )
+        .expect("valid tag");
+        assert_eq!(
+            check_app_document(&tag.path, tag.body.as_bytes()).map(|check| check.kind),
+            Some("tag")
+        );
+        assert!(
+            check_app_document(&tag.path, b"not json")
+                .expect("tag")
+                .outcome
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn other_paths_and_empty_bodies_are_skipped() {
+        assert_eq!(check_app_document("/pub/notes.txt", b"{}"), None);
+        assert_eq!(check_app_document("/pub/pubky.app/posts/", b"{}"), None);
+        assert_eq!(
+            check_app_document(&PubkyAppUser::create_path(), b"  \n"),
+            None
+        );
+    }
+}
@@ -0,0 +1,234 @@
+use base64::{
+    Engine as _,
+    engine::general_purpose::{STANDARD, STANDARD_NO_PAD, URL_SAFE, URL_SAFE_NO_PAD},
+};
+
+/// Shortest token [`find_encoded_blobs`] considers, so ordinary words are not
+/// mistaken for base64.
+pub const MIN_BLOB_LEN: usize = 16;
+
+/// Most blobs listed when scanning a whole response.
+pub const MAX_BLOBS: usize = 5;
+
+/// Bytes of binary output shown as hex.
+const BINARY_PREVIEW_BYTES: usize = 32;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Encoding {
+    Hex,
+    Base64,
+    Base64Url,
+}
+
+impl Encoding {
+    pub fn label(self) -> &'static str {
+        match self {
+            Encoding::Hex => "hex",
+            Encoding::Base64 => "base64",
+            Encoding::Base64Url => "base64url",
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DecodedContent {
+    Text(String),
+    /// Bytes that are not printable UTF-8, with a hex preview of the start.
+    Binary {
+        len: usize,
+        preview: String,
+    },
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Decoded {
+    pub encoding: Encoding,
+    pub content: DecodedContent,
+}
+
+impl Decoded {
+    /// One-line description for the response view.
+    pub fn describe(&self) -> String {
+        match &self.content {
+            DecodedContent::Text(text) => format!("{} → {text}", self.encoding.label()),
+            DecodedContent::Binary { len, preview } => {
+                let ellipsis = if *len > BINARY_PREVIEW_BYTES {
+                    "…"
+                } else {
+                    ""
+                };
+                format!(
+                    "{} → {len} bytes of binary data: {preview}{ellipsis}",
+                    self.encoding.label()
+                )
+            }
+        }
+    }
+}
+
+/// Decode `input` as hex or base64 (standard or URL-safe, padded or not).
+/// Hex wins when both would match, since every even-length hex string is
+/// also valid base64.
+pub fn decode_blob(input: &str) -> Result<Decoded, String> {
+    let trimmed = input.trim().trim_matches(|ch| ch == '"' || ch == '\'');
+    let compact: String = trimmed.chars().filter(|ch| !ch.is_whitespace()).collect();
+    if compact.is_empty() {
+        return Err(String::from("Nothing to decode"));
+    }
+
+    let hex_digits = compact
+        .strip_prefix("0x")
+        .or_else(|| compact.strip_prefix("0X"))
+        .unwrap_or(&compact);
+    if let Some(bytes) = decode_hex(hex_digits) {
+        return Ok(Decoded {
+            encoding: Encoding::Hex,
+            content: classify(bytes),
+        });
+    }
+
+    for (encoding, engine) in [
+        (Encoding::Base64, &STANDARD),
+        (Encoding::Base64, &STANDARD_NO_PAD),
+        (Encoding::Base64Url, &URL_SAFE),
+        (Encoding::Base64Url, &URL_SAFE_NO_PAD),
+    ] {
+        if let Ok(bytes) = engine.decode(&compact) {
+            return Ok(Decoded {
+                encoding,
+                content: classify(bytes),
+            });
+        }
+    }
+
+    Err(format!(
+        "'{}' is not valid base64 or hex",
+        shorten(trimmed, 40)
+    ))
+}
+
+/// Base64 or hex tokens of at least [`MIN_BLOB_LEN`] characters in `text`,
+/// decoded, in order of appearance and capped at [`MAX_BLOBS`].
+pub fn find_encoded_blobs(text: &str) -> Vec<(String, Decoded)> {
+    let mut seen: Vec<&str> = Vec::new();
+    text.split(|ch: char| {
+        !(ch.is_ascii_alphanumeric() || matches!(ch, '+' | '/' | '=' | '-' | '_'))
+    })
+    .filter(|token| token.len() >= MIN_BLOB_LEN)
+    .filter(|token| token.chars().any(|ch| ch.is_ascii_digit()))
+    .filter(|token| {
+        let fresh = !seen.contains(token);
+        seen.push(token);
+        fresh
+    })
+    .filter_map(|token| {
+        decode_blob(token)
+            .ok()
+            .map(|decoded| (token.to_string(), decoded))
+    })
+    .take(MAX_BLOBS)
+    .collect()
+}
+
+fn decode_hex(digits: &str) -> Option<Vec<u8>> {
+    if digits.is_empty() || !digits.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..digits.len())
+        .step_by(2)
+        .map(|index| u8::from_str_radix(digits.get(index..index + 2)?, 16).ok())
+        .collect()
+}
+
+fn classify(bytes: Vec<u8>) -> DecodedContent {
+    match String::from_utf8(bytes) {
+        Ok(text)
+            if !text
+                .chars()
+                .any(|ch| ch.is_control() && !matches!(ch, '\n' | '\r' | '\t')) =>
+        {
+            DecodedContent::Text(text)
+        }
+        Ok(text) => binary(text.into_bytes()),
+        Err(err) => binary(err.into_bytes()),
+    }
+}
+
+fn binary(bytes: Vec<u8>) -> DecodedContent {
+    let preview = bytes
+        .iter()
+        .take(BINARY_PREVIEW_BYTES)
+        .map(|byte| format!("{byte:02x}"))
+        .collect();
+    DecodedContent::Binary {
+        len: bytes.len(),
+        preview,
+    }
+}
+
+fn shorten(value: &str, max_chars: usize) -> String {
+    if value.chars().count() <= max_chars {
+        value.to_string()
+    } else {
+        let head: String = value.chars().take(max_chars).collect();
+        format!("{head}…")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decodes_hex_and_base64_variants() {
+        let hex = decode_blob("0x68656c6c6f").expect("hex");
+        assert_eq!(hex.encoding, Encoding::Hex);
+        assert_eq!(hex.content, DecodedContent::Text(String::from("hello")));
+
+        let padded = decode_blob(" \"aGVsbG8gd29ybGQ=\" ").expect("base64");
+        assert_eq!(padded.encoding, Encoding::Base64);
+        assert_eq!(padded.describe(), "base64 → hello world");
+
+        let unpadded = decode_blob("aGVsbG8gd29ybGQ").expect("unpadded base64");
+        assert_eq!(
+            unpadded.content,
+            DecodedContent::Text(String::from("hello world"))
+        );
+
+        let url_safe = decode_blob("-_8").expect("base64url");
+        assert_eq!(url_safe.encoding, Encoding::Base64Url);
+        assert_eq!(
+            url_safe.content,
+            DecodedContent::Binary {
+                len: 2,
+                preview: String::from("fbff"),
+            }
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_input() {
+        assert!(decode_blob("   ").is_err());
+        assert!(decode_blob("not base64!").is_err());
+        assert!(decode_blob("abc$").is_err());
+    }
+
+    #[test]
+    fn binary_output_is_summarised() {
+        let decoded = decode_blob("00ff10").expect("hex");
+        assert_eq!(decoded.describe(), "hex → 3 bytes of binary data: 00ff10");
+    }
+
+    #[test]
+    fn finds_blobs_in_json() {
+        let body = r#"{"name":"alice","data":"aGVsbG8gZnJvbSBwdWJreSE=","id":"0123456789abcdef0123","again":"aGVsbG8gZnJvbSBwdWJreSE="}"#;
+        let blobs = find_encoded_blobs(body);
+        let described: Vec<String> = blobs
+            .iter()
+            .map(|(_, decoded)| decoded.describe())
+            .collect();
+        assert_eq!(described.len(), 2);
+        assert_eq!(described[0], "base64 → hello from pubky!");
+        assert!(described[1].starts_with("hex → 10 bytes of binary data"));
+    }
+}
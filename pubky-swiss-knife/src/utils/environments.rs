@@ -0,0 +1,236 @@
+use serde::{Deserialize, Serialize};
+
+/// Named sets of `{{variable}}` values for the Raw Requests tab, saved with the
+/// other preferences.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EnvironmentPrefs {
+    /// Name of the environment substituted at send time, if any.
+    pub active: Option<String>,
+    pub environments: Vec<HttpEnvironment>,
+}
+
+impl EnvironmentPrefs {
+    pub fn active_environment(&self) -> Option<&HttpEnvironment> {
+        let active = self.active.as_deref()?;
+        self.environments.iter().find(|env| env.name == active)
+    }
+
+    /// Insert `environment`, replacing one with the same name.
+    pub fn upsert(&mut self, environment: HttpEnvironment) {
+        match self
+            .environments
+            .iter_mut()
+            .find(|existing| existing.name == environment.name)
+        {
+            Some(existing) => *existing = environment,
+            None => self.environments.push(environment),
+        }
+    }
+
+    /// Remove the environment called `name`, deactivating it if needed.
+    pub fn remove(&mut self, name: &str) {
+        self.environments.retain(|env| env.name != name);
+        if self.active.as_deref() == Some(name) {
+            self.active = None;
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HttpEnvironment {
+    pub name: String,
+    pub variables: Vec<EnvironmentVariable>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EnvironmentVariable {
+    pub name: String,
+    pub value: String,
+}
+
+impl HttpEnvironment {
+    /// Build an environment from the settings editor, where variables are
+    /// written one `name = value` per line.
+    pub fn parse(name: &str, variables: &str) -> Result<Self, String> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err(String::from("Give the environment a name"));
+        }
+        let mut parsed: Vec<EnvironmentVariable> = Vec::new();
+        for (index, line) in variables.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (key, value) = line
+                .split_once('=')
+                .ok_or_else(|| format!("Line {} must use name = value", index + 1))?;
+            let key = key.trim();
+            if !is_variable_name(key) {
+                return Err(format!(
+                    "Line {}: '{key}' is not a valid variable name (letters, digits, _ and -)",
+                    index + 1
+                ));
+            }
+            if parsed.iter().any(|existing| existing.name == key) {
+                return Err(format!("Variable '{key}' is defined twice"));
+            }
+            parsed.push(EnvironmentVariable {
+                name: key.to_string(),
+                value: value.trim().to_string(),
+            });
+        }
+        Ok(Self {
+            name: name.to_string(),
+            variables: parsed,
+        })
+    }
+
+    /// The variables in the editor's `name = value` form.
+    pub fn variables_text(&self) -> String {
+        self.variables
+            .iter()
+            .map(|variable| format!("{} = {}", variable.name, variable.value))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    fn lookup(&self, name: &str) -> Option<&str> {
+        self.variables
+            .iter()
+            .find(|variable| variable.name == name)
+            .map(|variable| variable.value.as_str())
+    }
+}
+
+fn is_variable_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || ch == '_' || ch == '-')
+}
+
+/// Whether `text` references any `{{variable}}`.
+pub fn has_placeholders(text: &str) -> bool {
+    text.split("{{")
+        .skip(1)
+        .any(|rest| rest.split_once("}}").is_some())
+}
+
+/// Replace every `{{name}}` in `template` with its value from `environment`.
+/// Fails with the names that are not defined, so nothing is sent half
+/// substituted.
+pub fn substitute(
+    template: &str,
+    environment: Option<&HttpEnvironment>,
+) -> Result<String, Vec<String>> {
+    let mut output = String::with_capacity(template.len());
+    let mut missing: Vec<String> = Vec::new();
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        let Some(end) = rest[start + 2..].find("}}") else {
+            break;
+        };
+        output.push_str(&rest[..start]);
+        let name = rest[start + 2..start + 2 + end].trim();
+        match environment.and_then(|env| env.lookup(name)) {
+            Some(value) => output.push_str(value),
+            None => {
+                if !missing.iter().any(|existing| existing == name) {
+                    missing.push(name.to_string());
+                }
+            }
+        }
+        rest = &rest[start + 2 + end + 2..];
+    }
+    output.push_str(rest);
+
+    if missing.is_empty() {
+        Ok(output)
+    } else {
+        Err(missing)
+    }
+}
+
+/// `"Undefined variables in the URL: {{a}}, {{b}}"`.
+pub fn describe_missing(field: &str, missing: &[String]) -> String {
+    let names = missing
+        .iter()
+        .map(|name| format!("{{{{{name}}}}}"))
+        .collect::<Vec<_>>()
+        .join(", ");
+    format!("Undefined variables in the {field}: {names}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn environment() -> HttpEnvironment {
+        HttpEnvironment::parse(
+            "testnet",
+            "# local homeserver\nbase = http://localhost:6286\nkey=abc\n",
+        )
+        .expect("valid environment")
+    }
+
+    #[test]
+    fn parses_and_formats_variables() {
+        let env = environment();
+        assert_eq!(env.variables.len(), 2);
+        assert_eq!(
+            env.variables_text(),
+            "base = http://localhost:6286\nkey = abc"
+        );
+
+        assert!(HttpEnvironment::parse(" ", "a = 1").is_err());
+        assert!(HttpEnvironment::parse("x", "no equals sign").is_err());
+        assert!(HttpEnvironment::parse("x", "bad name = 1").is_err());
+        assert!(HttpEnvironment::parse("x", "a = 1\na = 2").is_err());
+    }
+
+    #[test]
+    fn substitutes_known_variables_and_reports_missing_ones() {
+        let env = environment();
+        assert_eq!(
+            substitute("{{base}}/pub/{{ key }}/x", Some(&env)),
+            Ok(String::from("http://localhost:6286/pub/abc/x"))
+        );
+        assert_eq!(
+            substitute("{{base}}/{{nope}}/{{nope}}/{{other}}", Some(&env)),
+            Err(vec![String::from("nope"), String::from("other")])
+        );
+        assert_eq!(substitute("{{key}}", None), Err(vec![String::from("key")]));
+        assert_eq!(
+            substitute("plain {{ text", None),
+            Ok(String::from("plain {{ text"))
+        );
+        assert!(has_placeholders("{{base}}/x"));
+        assert!(!has_placeholders("{ not } {{ open"));
+        assert_eq!(
+            describe_missing("URL", &[String::from("a"), String::from("b")]),
+            "Undefined variables in the URL: {{a}}, {{b}}"
+        );
+    }
+
+    #[test]
+    fn removing_the_active_environment_deactivates_it() {
+        let mut prefs = EnvironmentPrefs::default();
+        prefs.upsert(environment());
+        prefs.upsert(HttpEnvironment::parse("testnet", "base = http://127.0.0.1").unwrap());
+        prefs.active = Some(String::from("testnet"));
+        assert_eq!(prefs.environments.len(), 1);
+        assert_eq!(
+            prefs
+                .active_environment()
+                .and_then(|env| env.lookup("base")),
+            Some("http://127.0.0.1")
+        );
+
+        prefs.remove("testnet");
+        assert_eq!(prefs.active, None);
+        assert!(prefs.environments.is_empty());
+    }
+}
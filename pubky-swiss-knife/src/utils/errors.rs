@@ -0,0 +1,158 @@
+/// Which authentication call failed, since the same status code means
+/// different things for signup and sign-in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AuthAttempt {
+    Signup { with_code: bool },
+    Signin,
+}
+
+/// Common reasons a signup or sign-in is refused, recognised from the error
+/// text the SDK and homeserver return.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AuthFailure {
+    /// The signup code is wrong, expired, or was already used.
+    InvalidSignupCode,
+    /// The homeserver only accepts signups with a code and none was given.
+    SignupCodeRequired,
+    /// The key already has an account on this homeserver.
+    AlreadyRegistered,
+    /// The key has no account on the homeserver it tried to sign in to.
+    NotRegistered,
+    /// The homeserver could not be resolved or did not answer.
+    Unreachable,
+    Other,
+}
+
+impl AuthFailure {
+    pub fn classify(attempt: AuthAttempt, error: &anyhow::Error) -> Self {
+        let text = format!("{error:#}").to_lowercase();
+        let mentions = |needles: &[&str]| needles.iter().any(|needle| text.contains(needle));
+
+        if mentions(&[
+            "connection refused",
+            "timed out",
+            "timeout",
+            "error sending request",
+            "dns error",
+            "failed to resolve",
+            "could not resolve",
+            "no homeserver",
+        ]) {
+            return AuthFailure::Unreachable;
+        }
+        if mentions(&["409", "conflict", "already exists", "already registered"]) {
+            return AuthFailure::AlreadyRegistered;
+        }
+
+        match attempt {
+            AuthAttempt::Signup { with_code } => {
+                if mentions(&["already used", "already been used", "already redeemed"]) {
+                    return AuthFailure::InvalidSignupCode;
+                }
+                let refused = mentions(&["401", "unauthorized", "403", "forbidden"])
+                    || (mentions(&["signup", "token", "code"])
+                        && mentions(&["invalid", "missing", "required"]));
+                match (refused, with_code) {
+                    (true, true) => AuthFailure::InvalidSignupCode,
+                    (true, false) => AuthFailure::SignupCodeRequired,
+                    _ => AuthFailure::Other,
+                }
+            }
+            AuthAttempt::Signin => {
+                if mentions(&["404", "not found", "401", "unauthorized", "no such user"]) {
+                    AuthFailure::NotRegistered
+                } else {
+                    AuthFailure::Other
+                }
+            }
+        }
+    }
+
+    pub fn hint(self) -> Option<&'static str> {
+        match self {
+            AuthFailure::InvalidSignupCode => Some(
+                "the homeserver rejected the signup code. It is wrong, expired, or has already been used; ask the operator for a new one.",
+            ),
+            AuthFailure::SignupCodeRequired => Some(
+                "this homeserver only accepts signups with a signup code. Ask the operator for one and enter it above.",
+            ),
+            AuthFailure::AlreadyRegistered => Some(
+                "this key already has an account on the homeserver. Use Sign in (root) instead.",
+            ),
+            AuthFailure::NotRegistered => Some(
+                "the homeserver does not know this key. Sign up first, or check that you are on the right network.",
+            ),
+            AuthFailure::Unreachable => Some(
+                "the homeserver could not be reached. Check the key, your connection, and the Mainnet/Testnet toggle.",
+            ),
+            AuthFailure::Other => None,
+        }
+    }
+}
+
+/// `"<action> failed: <hint>"`, keeping the raw error as detail.
+pub fn describe_auth_error(action: &str, attempt: AuthAttempt, error: &anyhow::Error) -> String {
+    match AuthFailure::classify(attempt, error).hint() {
+        Some(hint) => format!("{action} failed: {hint} ({error})"),
+        None => format!("{action} failed: {error}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::anyhow;
+
+    fn classify(attempt: AuthAttempt, message: &str) -> AuthFailure {
+        AuthFailure::classify(attempt, &anyhow!(message.to_string()))
+    }
+
+    #[test]
+    fn signup_code_failures_are_told_apart() {
+        let with_code = AuthAttempt::Signup { with_code: true };
+        let without_code = AuthAttempt::Signup { with_code: false };
+
+        assert_eq!(
+            classify(
+                with_code,
+                "Server responded with 401 Unauthorized: Invalid signup token"
+            ),
+            AuthFailure::InvalidSignupCode
+        );
+        assert_eq!(
+            classify(with_code, "signup token already used"),
+            AuthFailure::InvalidSignupCode
+        );
+        assert_eq!(
+            classify(without_code, "401 Unauthorized: signup token required"),
+            AuthFailure::SignupCodeRequired
+        );
+        assert_eq!(
+            classify(without_code, "409 Conflict: user already exists"),
+            AuthFailure::AlreadyRegistered
+        );
+    }
+
+    #[test]
+    fn network_and_signin_failures_are_classified() {
+        assert_eq!(
+            classify(
+                AuthAttempt::Signin,
+                "error sending request for url (https://example/session)"
+            ),
+            AuthFailure::Unreachable
+        );
+        assert_eq!(
+            classify(AuthAttempt::Signin, "404 Not Found"),
+            AuthFailure::NotRegistered
+        );
+        assert_eq!(
+            classify(AuthAttempt::Signin, "something odd"),
+            AuthFailure::Other
+        );
+        assert_eq!(
+            describe_auth_error("Signin", AuthAttempt::Signin, &anyhow!("something odd")),
+            "Signin failed: something odd"
+        );
+    }
+}
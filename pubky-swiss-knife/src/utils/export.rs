@@ -0,0 +1,209 @@
+use std::fs;
+use std::path::PathBuf;
+
+use anyhow::{Context, Result, anyhow};
+use futures::{StreamExt, stream};
+use pubky::PubkySession;
+use pubky_app_specs::{PubkyAppUser, traits::HasPath};
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::utils::storage::{DirectoryPager, is_not_found};
+use crate::utils::tags::TAGS_DIRECTORY;
+
+/// Where pubky.app keeps the session user's posts.
+pub const POSTS_DIRECTORY: &str = "/pub/pubky.app/posts/";
+
+/// Entries requested per listing page while exporting.
+pub const EXPORT_PAGE_SIZE: u16 = 100;
+
+/// Entry bodies fetched at once while exporting.
+const MAX_CONCURRENT_EXPORT_READS: usize = 4;
+
+/// A backup of the session user's pubky.app data.
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+pub struct DataExport {
+    /// `profile.json`, or `null` when none is stored.
+    pub profile: Option<Value>,
+    pub posts: Vec<ExportedEntry>,
+    pub tags: Vec<ExportedEntry>,
+    /// Entries that were listed but could not be read.
+    pub failed: Vec<FailedEntry>,
+}
+
+/// One stored file. Bodies that are not JSON are kept as text.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct ExportedEntry {
+    pub path: String,
+    pub data: Value,
+}
+
+impl ExportedEntry {
+    fn new(path: String, body: &[u8]) -> Self {
+        let data = serde_json::from_slice(body)
+            .unwrap_or_else(|_| Value::String(String::from_utf8_lossy(body).into_owned()));
+        Self { path, data }
+    }
+}
+
+/// An entry left out of the bundle, with the reason.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct FailedEntry {
+    pub path: String,
+    pub error: String,
+}
+
+/// Read the profile, posts, and tags under `/pub/pubky.app/`, one listing
+/// page at a time. `progress` receives the section name and how many of its
+/// entries have been read so far after every page. Entries that fail to read
+/// are recorded in [`DataExport::failed`] instead of stopping the export.
+pub async fn export_pubky_app_data(
+    session: &PubkySession,
+    progress: impl Fn(&str, usize),
+) -> Result<DataExport> {
+    let mut failed = Vec::new();
+    let profile_path = PubkyAppUser::create_path();
+    let profile = match read_entry(session, profile_path.clone()).await {
+        Ok(entry) => entry.map(|entry| entry.data),
+        Err(err) => {
+            failed.push(FailedEntry {
+                path: profile_path,
+                error: format!("{err:#}"),
+            });
+            None
+        }
+    };
+    progress("profile", usize::from(profile.is_some()));
+    let posts = export_directory(session, POSTS_DIRECTORY, &mut failed, |count| {
+        progress("posts", count)
+    })
+    .await?;
+    let tags = export_directory(session, TAGS_DIRECTORY, &mut failed, |count| {
+        progress("tags", count)
+    })
+    .await?;
+    Ok(DataExport {
+        profile,
+        posts,
+        tags,
+        failed,
+    })
+}
+
+async fn export_directory(
+    session: &PubkySession,
+    directory: &str,
+    failed: &mut Vec<FailedEntry>,
+    progress: impl Fn(usize),
+) -> Result<Vec<ExportedEntry>> {
+    let mut exported = Vec::new();
+    let mut read = 0;
+    let mut pager = DirectoryPager::new(session, directory, EXPORT_PAGE_SIZE);
+    while let Some(page) = pager.next_page().await? {
+        let results: Vec<(String, Result<Option<ExportedEntry>>)> = stream::iter(page)
+            .map(|path| async move { (path.clone(), read_entry(session, path).await) })
+            .buffered(MAX_CONCURRENT_EXPORT_READS)
+            .collect()
+            .await;
+        read += results.len();
+        for (path, result) in results {
+            match result {
+                Ok(entry) => exported.extend(entry),
+                Err(err) => failed.push(FailedEntry {
+                    path,
+                    error: format!("{err:#}"),
+                }),
+            }
+        }
+        progress(read);
+    }
+    Ok(exported)
+}
+
+/// Fetch one file, or `None` when it is not there.
+async fn read_entry(session: &PubkySession, path: String) -> Result<Option<ExportedEntry>> {
+    let response = match session.storage().get(path.clone()).await {
+        Ok(response) => response,
+        Err(err) => {
+            let err = anyhow::Error::from(err);
+            if is_not_found(&format!("{err:#}")) {
+                return Ok(None);
+            }
+            return Err(err.context(format!("Failed to read {path}")));
+        }
+    };
+    let status = response.status();
+    if status.as_u16() == 404 {
+        return Ok(None);
+    }
+    if !status.is_success() {
+        return Err(anyhow!("Reading {path} returned status {status}"));
+    }
+    let body = response.bytes().await?;
+    Ok(Some(ExportedEntry::new(path, &body)))
+}
+
+/// Write the bundle as pretty JSON, adding a `.json` extension when none is
+/// given.
+pub fn save_export(export: &DataExport, path: &str) -> Result<PathBuf> {
+    let mut target = PathBuf::from(path.trim());
+    if target.extension().is_none() {
+        target.set_extension("json");
+    }
+    if let Some(parent) = target
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+    {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("failed to create directory {}", parent.display()))?;
+    }
+    let json = serde_json::to_string_pretty(export).context("failed to serialize the export")?;
+    fs::write(&target, json).with_context(|| format!("failed to write {}", target.display()))?;
+    Ok(target)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bundle_keeps_json_and_text_bodies() {
+        let export = DataExport {
+            profile: Some(serde_json::json!({ "name": "alice" })),
+            posts: vec![ExportedEntry::new(
+                format!("{POSTS_DIRECTORY}0033SSE3B1FQ0"),
+                br#"{"content":"hello","kind":"short"}"#,
+            )],
+            tags: vec![ExportedEntry::new(
+                format!("{TAGS_DIRECTORY}X"),
+                b"not json",
+            )],
+            failed: vec![FailedEntry {
+                path: format!("{POSTS_DIRECTORY}0033SSE3B1FQ1"),
+                error: String::from("Reading it returned status 500"),
+            }],
+        };
+        let temp_dir = tempfile::tempdir().expect("temp dir");
+        let target = save_export(
+            &export,
+            &temp_dir.path().join("backup").display().to_string(),
+        )
+        .expect("export should save");
+        assert_eq!(
+            target.extension().and_then(|ext| ext.to_str()),
+            Some("json")
+        );
+
+        let written: Value =
+            serde_json::from_str(&fs::read_to_string(&target).expect("export should exist"))
+                .expect("export is JSON");
+        assert_eq!(written["profile"]["name"], "alice");
+        assert_eq!(written["posts"][0]["data"]["content"], "hello");
+        assert_eq!(written["tags"][0]["data"], "not json");
+        assert_eq!(written["tags"][0]["path"], "/pub/pubky.app/tags/X");
+        assert_eq!(
+            written["failed"][0]["path"],
+            "/pub/pubky.app/posts/0033SSE3B1FQ1"
+        );
+    }
+}
@@ -1,4 +1,4 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 #[derive(Debug, Clone)]
 pub enum FileDialogResult {
@@ -10,28 +10,46 @@ pub enum FileDialogResult {
 pub const MANUAL_ENTRY_HINT: &str =
     "File picker unavailable on this platform. Enter a path manually.";
 
-#[cfg(target_os = "android")]
 pub fn pick_file() -> FileDialogResult {
+    pick_file_in(None)
+}
+
+pub fn save_file() -> FileDialogResult {
+    save_file_in(None)
+}
+
+#[cfg(target_os = "android")]
+pub fn pick_file_in(_start_dir: Option<&Path>) -> FileDialogResult {
     FileDialogResult::Unavailable
 }
 
+/// Open a file picker, starting in `start_dir` when it exists.
 #[cfg(not(target_os = "android"))]
-pub fn pick_file() -> FileDialogResult {
-    rfd::FileDialog::new()
+pub fn pick_file_in(start_dir: Option<&Path>) -> FileDialogResult {
+    with_start_dir(rfd::FileDialog::new(), start_dir)
         .pick_file()
         .map(FileDialogResult::Selected)
         .unwrap_or(FileDialogResult::Cancelled)
 }
 
 #[cfg(target_os = "android")]
-pub fn save_file() -> FileDialogResult {
+pub fn save_file_in(_start_dir: Option<&Path>) -> FileDialogResult {
     FileDialogResult::Unavailable
 }
 
+/// Open a save dialog, starting in `start_dir` when it exists.
 #[cfg(not(target_os = "android"))]
-pub fn save_file() -> FileDialogResult {
-    rfd::FileDialog::new()
+pub fn save_file_in(start_dir: Option<&Path>) -> FileDialogResult {
+    with_start_dir(rfd::FileDialog::new(), start_dir)
         .save_file()
         .map(FileDialogResult::Selected)
         .unwrap_or(FileDialogResult::Cancelled)
 }
+
+#[cfg(not(target_os = "android"))]
+fn with_start_dir(dialog: rfd::FileDialog, start_dir: Option<&Path>) -> rfd::FileDialog {
+    match start_dir {
+        Some(dir) if dir.is_dir() => dialog.set_directory(dir),
+        _ => dialog,
+    }
+}
@@ -1,16 +1,91 @@
-use anyhow::Result;
+use anyhow::{Context, Result, bail};
+use base64::{Engine as _, engine::general_purpose::STANDARD};
+use bytes::Bytes;
+use pubky::PublicKey;
 use reqwest::{
     StatusCode, Version,
     header::{CONTENT_TYPE, HeaderMap},
 };
 use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use url::Url;
+
+use crate::app::NetworkMode;
+use crate::utils::testnet::TESTNET_HOMESERVER_KEY;
+
+/// Upper bound on in-flight requests when repeating a request concurrently.
+pub const MAX_CONCURRENT_REQUESTS: usize = 8;
+
+/// Largest image body rendered inline as a preview.
+pub const MAX_IMAGE_PREVIEW_BYTES: usize = 2 * 1024 * 1024;
+
+/// Requests kept in the Raw Requests history.
+pub const MAX_HISTORY_ENTRIES: usize = 20;
+
+/// Form field used for an attached file when none is given.
+pub const DEFAULT_MULTIPART_FIELD: &str = "file";
 
 pub async fn format_response(response: reqwest::Response) -> Result<String> {
+    Ok(format_response_with_headers(response).await?.0)
+}
+
+/// Like [`format_response`], but also returns the header rows for the
+/// structured header table.
+pub async fn format_response_with_headers(
+    response: reqwest::Response,
+) -> Result<(String, Vec<HeaderRow>)> {
     let status = response.status();
     let version = response.version();
     let headers = response.headers().clone();
     let bytes = response.bytes().await?;
-    Ok(format_response_parts(status, version, &headers, &bytes))
+    Ok((
+        format_response_parts(status, version, &headers, &bytes),
+        header_rows(&headers),
+    ))
+}
+
+/// One response header, as shown in the header table.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HeaderRow {
+    pub name: String,
+    pub value: String,
+}
+
+/// Header rows in response order. Values that are not valid text are skipped,
+/// matching [`format_response_parts`].
+pub fn header_rows(headers: &HeaderMap) -> Vec<HeaderRow> {
+    headers
+        .iter()
+        .filter_map(|(name, value)| {
+            value.to_str().ok().map(|text| HeaderRow {
+                name: name.to_string(),
+                value: text.to_string(),
+            })
+        })
+        .collect()
+}
+
+/// Rows whose name or value contains `query` (case-insensitive), sorted by
+/// name. Rows with the same name keep their response order.
+pub fn filter_header_rows(rows: &[HeaderRow], query: &str, descending: bool) -> Vec<HeaderRow> {
+    let needle = query.trim().to_lowercase();
+    let mut matching: Vec<HeaderRow> = rows
+        .iter()
+        .filter(|row| {
+            needle.is_empty()
+                || row.name.to_lowercase().contains(&needle)
+                || row.value.to_lowercase().contains(&needle)
+        })
+        .cloned()
+        .collect();
+    matching.sort_by(|a, b| {
+        let order = a.name.cmp(&b.name);
+        if descending { order.reverse() } else { order }
+    });
+    matching
 }
 
 pub fn format_response_parts(
@@ -75,3 +150,468 @@ fn binary_preview(bytes: &[u8]) -> String {
         format!("<binary {} bytes: {}>", bytes.len(), sample)
     }
 }
+
+/// Inline `data:` URL for image bodies small enough to preview, or `None` for
+/// anything else.
+pub fn image_preview_data_url(content_type: Option<&str>, body: &[u8]) -> Option<String> {
+    let mime = content_type?.split(';').next()?.trim().to_lowercase();
+    if !mime.starts_with("image/") || body.is_empty() || body.len() > MAX_IMAGE_PREVIEW_BYTES {
+        return None;
+    }
+    Some(format!("data:{mime};base64,{}", STANDARD.encode(body)))
+}
+
+/// The parts of a response rendered by [`format_response_parts`], so each can
+/// be copied on its own.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResponseSections<'a> {
+    /// Status line followed by the header lines.
+    pub head: &'a str,
+    pub body: &'a str,
+}
+
+impl<'a> ResponseSections<'a> {
+    /// Split formatted output at its status line. Any notes printed above the
+    /// status line are left out of both sections.
+    pub fn parse(formatted: &'a str) -> Option<Self> {
+        let start = if formatted.starts_with("HTTP/") {
+            0
+        } else {
+            formatted.find("\nHTTP/")? + 1
+        };
+        let response = &formatted[start..];
+        let (status_line, rest) = response.split_once('\n').unwrap_or((response, ""));
+        let (head, body) = match rest.split_once("\n\n") {
+            Some((headers, body)) => (&response[..status_line.len() + 1 + headers.len()], body),
+            None => (response, ""),
+        };
+        Some(Self {
+            head: head.trim_end(),
+            body,
+        })
+    }
+}
+
+/// A `multipart/form-data` body holding one file part, with the matching
+/// `Content-Type` header value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MultipartBody {
+    pub content_type: String,
+    pub bytes: Bytes,
+}
+
+impl MultipartBody {
+    /// Wrap `data` as the only part, named `field`. The data is copied
+    /// byte for byte, so binary files arrive intact.
+    pub fn single_file(field: &str, file_name: &str, data: &[u8]) -> Result<Self> {
+        let field = field.trim();
+        if field.is_empty() {
+            bail!("Provide a form field name for the attached file");
+        }
+        let boundary = pick_boundary(data);
+        let mut bytes = Vec::with_capacity(data.len() + 256);
+        bytes.extend_from_slice(
+            format!(
+                "--{boundary}\r\nContent-Disposition: form-data; name=\"{}\"; filename=\"{}\"\r\nContent-Type: application/octet-stream\r\n\r\n",
+                escape_disposition(field),
+                escape_disposition(file_name)
+            )
+            .as_bytes(),
+        );
+        bytes.extend_from_slice(data);
+        bytes.extend_from_slice(format!("\r\n--{boundary}--\r\n").as_bytes());
+        Ok(Self {
+            content_type: format!("multipart/form-data; boundary={boundary}"),
+            bytes: Bytes::from(bytes),
+        })
+    }
+}
+
+/// Read the file at `path` as raw bytes and wrap it with
+/// [`MultipartBody::single_file`].
+pub fn multipart_from_file(field: &str, path: &Path) -> Result<MultipartBody> {
+    let data = fs::read(path).with_context(|| format!("failed to read {}", path.display()))?;
+    let file_name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| String::from("upload"));
+    MultipartBody::single_file(field, &file_name, &data)
+}
+
+/// A boundary that does not occur in `data`.
+fn pick_boundary(data: &[u8]) -> String {
+    let seed = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|since| since.as_nanos())
+        .unwrap_or_default();
+    (0u32..)
+        .map(|attempt| format!("pubky-swiss-knife-{seed:x}-{attempt}"))
+        .find(|boundary| {
+            !data
+                .windows(boundary.len())
+                .any(|window| window == boundary.as_bytes())
+        })
+        .expect("some boundary is absent from the data")
+}
+
+/// Percent-encode the characters that would end a quoted
+/// `Content-Disposition` parameter.
+fn escape_disposition(value: &str) -> String {
+    value
+        .replace('"', "%22")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+/// A sent request as it was typed, before environment substitution, with
+/// only the status line of its response.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HistoryEntry {
+    pub method: String,
+    pub url: String,
+    pub headers: String,
+    pub body: String,
+    /// `200 OK`, or `failed` when no response arrived.
+    pub status: String,
+    pub succeeded: bool,
+    pub sent_at: String,
+}
+
+/// Add `entry` as the newest item, dropping the oldest ones beyond
+/// [`MAX_HISTORY_ENTRIES`].
+pub fn push_history(history: &mut Vec<HistoryEntry>, entry: HistoryEntry) {
+    history.insert(0, entry);
+    history.truncate(MAX_HISTORY_ENTRIES);
+}
+
+/// Time of day in UTC, as `HH:MM:SS UTC`.
+pub fn utc_clock(time: SystemTime) -> String {
+    let secs = time
+        .duration_since(UNIX_EPOCH)
+        .map(|since| since.as_secs())
+        .unwrap_or_default()
+        % 86_400;
+    format!(
+        "{:02}:{:02}:{:02} UTC",
+        secs / 3600,
+        secs % 3600 / 60,
+        secs % 60
+    )
+}
+
+/// Aggregated outcome of a batch of repeated requests.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct LoadSummary {
+    latencies: Vec<Duration>,
+    failures: usize,
+    statuses: BTreeMap<u16, usize>,
+}
+
+impl LoadSummary {
+    pub fn record_response(&mut self, status: StatusCode, latency: Duration) {
+        self.latencies.push(latency);
+        *self.statuses.entry(status.as_u16()).or_default() += 1;
+        if !status.is_success() {
+            self.failures += 1;
+        }
+    }
+
+    pub fn record_error(&mut self, latency: Duration) {
+        self.latencies.push(latency);
+        self.failures += 1;
+    }
+
+    pub fn total(&self) -> usize {
+        self.latencies.len()
+    }
+
+    pub fn failures(&self) -> usize {
+        self.failures
+    }
+
+    pub fn render(&self) -> String {
+        let total = self.total();
+        let mut lines = vec![format!(
+            "Requests: {total} ({} succeeded, {} failed)",
+            total - self.failures,
+            self.failures
+        )];
+        if let (Some(min), Some(max)) = (self.latencies.iter().min(), self.latencies.iter().max()) {
+            let sum: Duration = self.latencies.iter().sum();
+            let avg = sum / total as u32;
+            lines.push(format!(
+                "Latency: min {} / avg {} / max {}",
+                format_latency(*min),
+                format_latency(avg),
+                format_latency(*max)
+            ));
+        }
+        if !self.statuses.is_empty() {
+            lines.push(String::from("Status codes:"));
+            for (status, count) in &self.statuses {
+                lines.push(format!("  {status}: {count}"));
+            }
+        }
+        lines.join("\n")
+    }
+}
+
+pub fn format_latency(latency: Duration) -> String {
+    format!("{:.1} ms", latency.as_secs_f64() * 1000.0)
+}
+
+/// The public key a request URL routes through: the host of
+/// `pubky://<key>/...`, `https://_pubky.<key>/...`, or `https://<key>/...`.
+/// `None` for ordinary domains and IP addresses.
+pub fn parse_homeserver_key(url: &str) -> Option<PublicKey> {
+    let parsed = Url::parse(url.trim()).ok()?;
+    let host = parsed.host_str()?;
+    let key = host.strip_prefix("_pubky.").unwrap_or(host);
+    PublicKey::try_from(key).ok()
+}
+
+/// Advisory note when `key` will almost certainly not resolve on `network`.
+/// Keys look the same on both networks, so only the local testnet homeserver
+/// can be told apart without a lookup.
+pub fn network_mismatch(network: NetworkMode, key: &PublicKey) -> Option<&'static str> {
+    let testnet_homeserver = PublicKey::try_from(TESTNET_HOMESERVER_KEY).ok()?;
+    (network == NetworkMode::Mainnet && *key == testnet_homeserver).then_some(
+        "This key is the local testnet homeserver, which Mainnet cannot resolve. Switch the network to Testnet if that is where it runs.",
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn homeserver_keys_are_read_from_pubky_urls() {
+        let key = PublicKey::try_from(TESTNET_HOMESERVER_KEY).expect("valid key");
+        for url in [
+            format!("pubky://{TESTNET_HOMESERVER_KEY}/pub/example.txt"),
+            format!("https://_pubky.{TESTNET_HOMESERVER_KEY}/pub/example.txt"),
+            format!(" https://{TESTNET_HOMESERVER_KEY}/session "),
+        ] {
+            assert_eq!(parse_homeserver_key(&url).as_ref(), Some(&key), "{url}");
+        }
+        assert!(parse_homeserver_key("https://example.com/pub/x").is_none());
+        assert!(parse_homeserver_key("http://localhost:6286/").is_none());
+        assert!(parse_homeserver_key("not a url").is_none());
+
+        assert!(network_mismatch(NetworkMode::Mainnet, &key).is_some());
+        assert!(network_mismatch(NetworkMode::Testnet, &key).is_none());
+        let other = pubky::Keypair::random().public_key();
+        assert!(network_mismatch(NetworkMode::Mainnet, &other).is_none());
+    }
+
+    #[test]
+    fn load_summary_reports_counts_latency_and_statuses() {
+        let mut summary = LoadSummary::default();
+        summary.record_response(StatusCode::OK, Duration::from_millis(10));
+        summary.record_response(StatusCode::OK, Duration::from_millis(30));
+        summary.record_response(StatusCode::NOT_FOUND, Duration::from_millis(20));
+        summary.record_error(Duration::from_millis(40));
+
+        assert_eq!(summary.total(), 4);
+        assert_eq!(summary.failures(), 2);
+        let rendered = summary.render();
+        assert!(rendered.contains("Requests: 4 (2 succeeded, 2 failed)"));
+        assert!(rendered.contains("min 10.0 ms / avg 25.0 ms / max 40.0 ms"));
+        assert!(rendered.contains("  200: 2"));
+        assert!(rendered.contains("  404: 1"));
+    }
+
+    #[test]
+    fn empty_load_summary_renders_counts_only() {
+        let rendered = LoadSummary::default().render();
+        assert_eq!(rendered, "Requests: 0 (0 succeeded, 0 failed)");
+    }
+
+    #[test]
+    fn response_sections_split_head_and_body() {
+        let mut headers = HeaderMap::new();
+        headers.insert(CONTENT_TYPE, "text/plain".parse().unwrap());
+        let formatted = format_response_parts(
+            StatusCode::OK,
+            Version::HTTP_11,
+            &headers,
+            b"hello\n\nworld",
+        );
+        let sections = ResponseSections::parse(&formatted).unwrap();
+        assert_eq!(sections.head, "HTTP/1.1 200 OK\ncontent-type: text/plain");
+        assert_eq!(sections.body, "hello\n\nworld");
+
+        let empty = format_response_parts(
+            StatusCode::NO_CONTENT,
+            Version::HTTP_11,
+            &HeaderMap::new(),
+            b"",
+        );
+        let sections = ResponseSections::parse(&empty).unwrap();
+        assert_eq!(sections.head, "HTTP/1.1 204 No Content");
+        assert_eq!(sections.body, "<empty body>");
+    }
+
+    #[test]
+    fn json_bodies_are_pretty_printed() {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            CONTENT_TYPE,
+            "application/json; charset=utf-8".parse().unwrap(),
+        );
+        let formatted = format_response_parts(
+            StatusCode::OK,
+            Version::HTTP_11,
+            &headers,
+            br#"{"name":"alice","tags":["rust"]}"#,
+        );
+        let body = ResponseSections::parse(&formatted).unwrap().body;
+        assert_eq!(
+            body,
+            "{\n  \"name\": \"alice\",\n  \"tags\": [\n    \"rust\"\n  ]\n}"
+        );
+
+        let invalid = format_response_parts(
+            StatusCode::OK,
+            Version::HTTP_11,
+            &headers,
+            b"{\"name\": oops",
+        );
+        assert_eq!(
+            ResponseSections::parse(&invalid).unwrap().body,
+            "{\"name\": oops"
+        );
+    }
+
+    #[test]
+    fn header_rows_filter_and_sort() {
+        let mut headers = HeaderMap::new();
+        headers.insert("content-type", "application/json".parse().unwrap());
+        headers.append("access-control-allow-origin", "*".parse().unwrap());
+        headers.append("cache-control", "no-store".parse().unwrap());
+        headers.append("cache-control", "private".parse().unwrap());
+        let rows = header_rows(&headers);
+        assert_eq!(rows.len(), 4);
+
+        let names = |rows: Vec<HeaderRow>| rows.into_iter().map(|row| row.name).collect::<Vec<_>>();
+        assert_eq!(
+            names(filter_header_rows(&rows, "", false)),
+            [
+                "access-control-allow-origin",
+                "cache-control",
+                "cache-control",
+                "content-type"
+            ]
+        );
+        assert_eq!(
+            names(filter_header_rows(&rows, "", true))[0],
+            "content-type"
+        );
+        let cache = filter_header_rows(&rows, "CACHE", false);
+        assert_eq!(
+            cache
+                .iter()
+                .map(|row| row.value.as_str())
+                .collect::<Vec<_>>(),
+            ["no-store", "private"]
+        );
+        assert_eq!(filter_header_rows(&rows, "json", false).len(), 1);
+    }
+
+    #[test]
+    fn image_preview_only_for_small_images() {
+        assert_eq!(
+            image_preview_data_url(Some("image/PNG; charset=binary"), b"abc").as_deref(),
+            Some("data:image/png;base64,YWJj")
+        );
+        assert!(image_preview_data_url(Some("text/plain"), b"abc").is_none());
+        assert!(image_preview_data_url(None, b"abc").is_none());
+        assert!(image_preview_data_url(Some("image/png"), b"").is_none());
+        let large = vec![0u8; MAX_IMAGE_PREVIEW_BYTES + 1];
+        assert!(image_preview_data_url(Some("image/png"), &large).is_none());
+    }
+
+    #[test]
+    fn history_keeps_the_newest_entries() {
+        let entry = |index: usize| HistoryEntry {
+            method: String::from("GET"),
+            url: format!("https://example.com/{index}"),
+            headers: String::new(),
+            body: String::new(),
+            status: String::from("200 OK"),
+            succeeded: true,
+            sent_at: utc_clock(UNIX_EPOCH + Duration::from_secs(86_400 + 3_723)),
+        };
+        let mut history = Vec::new();
+        for index in 0..(MAX_HISTORY_ENTRIES + 3) {
+            push_history(&mut history, entry(index));
+        }
+        assert_eq!(history.len(), MAX_HISTORY_ENTRIES);
+        assert_eq!(
+            history[0].url,
+            format!("https://example.com/{}", MAX_HISTORY_ENTRIES + 2)
+        );
+        assert_eq!(
+            history[MAX_HISTORY_ENTRIES - 1].url,
+            "https://example.com/3"
+        );
+        assert_eq!(history[0].sent_at, "01:02:03 UTC");
+    }
+
+    #[test]
+    fn multipart_body_keeps_binary_data_intact() {
+        let data = [0u8, 159, 146, 150, 255, b'\r', b'\n', 0];
+        let body = MultipartBody::single_file("file", "a\"b.bin", &data).unwrap();
+        let boundary = body
+            .content_type
+            .strip_prefix("multipart/form-data; boundary=")
+            .expect("multipart content type");
+
+        let head = format!(
+            "--{boundary}\r\nContent-Disposition: form-data; name=\"file\"; filename=\"a%22b.bin\"\r\nContent-Type: application/octet-stream\r\n\r\n"
+        );
+        let tail = format!("\r\n--{boundary}--\r\n");
+        assert!(body.bytes.starts_with(head.as_bytes()));
+        assert!(body.bytes.ends_with(tail.as_bytes()));
+        assert_eq!(
+            &body.bytes[head.len()..body.bytes.len() - tail.len()],
+            &data
+        );
+
+        assert!(MultipartBody::single_file("  ", "a.bin", &data).is_err());
+    }
+
+    #[test]
+    fn multipart_reads_files_as_raw_bytes() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("image.png");
+        let data: Vec<u8> = (0..=255).collect();
+        fs::write(&path, &data).unwrap();
+
+        let body = multipart_from_file("upload", &path).unwrap();
+        assert!(
+            body.bytes
+                .windows(data.len())
+                .any(|window| window == data.as_slice())
+        );
+        let file_name = b"filename=\"image.png\"";
+        assert!(
+            body.bytes
+                .windows(file_name.len())
+                .any(|window| window == file_name)
+        );
+        assert!(multipart_from_file("upload", &dir.path().join("missing")).is_err());
+    }
+
+    #[test]
+    fn response_sections_skip_leading_notes() {
+        let sections = ResponseSections::parse(
+            "Returned bytes 0-1\n\nHTTP/1.1 206 Partial Content\na: b\n\nhi",
+        )
+        .unwrap();
+        assert_eq!(sections.head, "HTTP/1.1 206 Partial Content\na: b");
+        assert_eq!(sections.body, "hi");
+        assert!(ResponseSections::parse("not a response").is_none());
+    }
+}
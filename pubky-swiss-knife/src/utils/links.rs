@@ -16,9 +16,32 @@ pub fn open_pubkyauth_link(url: &str) -> Result<()> {
     open_on_platform(trimmed)
 }
 
+/// Whether `text` looks like a `pubkyauth:` request URL.
+pub fn is_pubkyauth_url(text: &str) -> bool {
+    text.trim().to_ascii_lowercase().starts_with("pubkyauth:")
+}
+
+/// Whether `url` can be handed to the system browser or mail client.
+pub fn is_web_link(url: &str) -> bool {
+    let lower = url.trim().to_ascii_lowercase();
+    ["https://", "http://", "mailto:"]
+        .iter()
+        .any(|scheme| lower.starts_with(scheme) && lower.len() > scheme.len())
+}
+
+/// Open an http(s) or mailto link, such as a profile link, outside the app.
+pub fn open_external_link(url: &str) -> Result<()> {
+    let trimmed = url.trim();
+    if !is_web_link(trimmed) {
+        bail!("Only http(s) and mailto links can be opened");
+    }
+
+    open_on_platform(trimmed)
+}
+
 #[cfg(not(target_os = "android"))]
 fn open_on_platform(url: &str) -> Result<()> {
-    open::that(url).context("failed to hand off pubkyauth link")?;
+    open::that(url).context("failed to hand off link")?;
     Ok(())
 }
 
@@ -42,8 +65,32 @@ fn open_on_platform(url: &str) -> Result<()> {
     });
 
     if let Some(err) = activity_error {
-        Err(err).context("failed to hand off pubkyauth link")
+        Err(err).context("failed to hand off link")
     } else {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn recognises_pubkyauth_urls() {
+        assert!(is_pubkyauth_url(" pubkyauth:///?caps=/pub/:rw&secret=abc "));
+        assert!(is_pubkyauth_url("PUBKYAUTH://?relay=x"));
+        assert!(!is_pubkyauth_url("https://example.com/?pubkyauth:"));
+        assert!(!is_pubkyauth_url(""));
+    }
+
+    #[test]
+    fn only_web_links_are_opened_externally() {
+        assert!(is_web_link("https://example.com"));
+        assert!(is_web_link(" HTTP://example.com/a "));
+        assert!(is_web_link("mailto:alice@example.com"));
+        assert!(!is_web_link("https://"));
+        assert!(!is_web_link("pubky://abc/pub/pubky.app/profile.json"));
+        assert!(!is_web_link("javascript:alert(1)"));
+        assert!(open_external_link("file:///etc/passwd").is_err());
+    }
+}
@@ -1,26 +1,105 @@
+use anyhow::{Context, Result};
 use dioxus::prelude::{Signal, WritableExt};
+use std::fs;
+use std::path::Path;
+use std::time::SystemTime;
 
-/// Maximum number of log entries kept in memory before older ones are trimmed.
-const MAX_LOG_ENTRIES: usize = 200;
+use crate::utils::http::utc_clock;
+
+/// Maximum number of log entries kept in memory; the oldest are dropped first.
+pub const MAX_LOG_ENTRIES: usize = 500;
 
 #[derive(Clone, Copy, PartialEq, Eq)]
 pub enum LogLevel {
     Info,
     Success,
+    Warning,
     Error,
 }
 
+impl LogLevel {
+    pub const ALL: [LogLevel; 4] = [
+        LogLevel::Info,
+        LogLevel::Success,
+        LogLevel::Warning,
+        LogLevel::Error,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            LogLevel::Info => "info",
+            LogLevel::Success => "success",
+            LogLevel::Warning => "warning",
+            LogLevel::Error => "error",
+        }
+    }
+}
+
+/// Levels the activity drawer shows. Everything is shown by default and the
+/// choice is not saved between launches.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LogFilter {
+    info: bool,
+    success: bool,
+    warning: bool,
+    error: bool,
+}
+
+impl Default for LogFilter {
+    fn default() -> Self {
+        Self {
+            info: true,
+            success: true,
+            warning: true,
+            error: true,
+        }
+    }
+}
+
+impl LogFilter {
+    pub fn shows(self, level: LogLevel) -> bool {
+        match level {
+            LogLevel::Info => self.info,
+            LogLevel::Success => self.success,
+            LogLevel::Warning => self.warning,
+            LogLevel::Error => self.error,
+        }
+    }
+
+    pub fn allows(self, entry: &LogEntry) -> bool {
+        self.shows(entry.level)
+    }
+
+    /// The same filter with `level` shown if it was hidden, or hidden if shown.
+    pub fn toggled(mut self, level: LogLevel) -> Self {
+        let slot = match level {
+            LogLevel::Info => &mut self.info,
+            LogLevel::Success => &mut self.success,
+            LogLevel::Warning => &mut self.warning,
+            LogLevel::Error => &mut self.error,
+        };
+        *slot = !*slot;
+        self
+    }
+}
+
 #[derive(Clone)]
 pub struct LogEntry {
     level: LogLevel,
     message: String,
+    logged_at: SystemTime,
 }
 
 impl LogEntry {
     pub fn new(level: LogLevel, message: impl Into<String>) -> Self {
+        Self::at(level, message, SystemTime::now())
+    }
+
+    fn at(level: LogLevel, message: impl Into<String>, logged_at: SystemTime) -> Self {
         Self {
             level,
             message: message.into(),
+            logged_at,
         }
     }
 
@@ -28,18 +107,60 @@ impl LogEntry {
         match self.level {
             LogLevel::Info => "log-info",
             LogLevel::Success => "log-success",
+            LogLevel::Warning => "log-warning",
             LogLevel::Error => "log-error",
         }
     }
 
+    pub fn level_label(&self) -> &'static str {
+        self.level.label()
+    }
+
     pub fn message(&self) -> &str {
         &self.message
     }
+
+    /// When the entry was logged, as `HH:MM:SS UTC`.
+    pub fn timestamp(&self) -> String {
+        utc_clock(self.logged_at)
+    }
+
+    /// `[HH:MM:SS UTC] [LEVEL] message`, with any further message lines
+    /// indented so every entry starts on its own bracketed line.
+    pub fn export_line(&self) -> String {
+        format!(
+            "[{}] [{}] {}",
+            self.timestamp(),
+            self.level_label().to_uppercase(),
+            self.message.trim_end().replace('\n', "\n    ")
+        )
+    }
+}
+
+/// The whole log as plain text, oldest entry first, one entry per line.
+pub fn format_log(entries: &[LogEntry]) -> String {
+    entries
+        .iter()
+        .map(|entry| entry.export_line() + "\n")
+        .collect()
+}
+
+/// Write [`format_log`] output to `path`, creating missing directories.
+pub fn save_log(entries: &[LogEntry], path: &Path) -> Result<()> {
+    if let Some(parent) = path
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+    {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("failed to create directory {}", parent.display()))?;
+    }
+    fs::write(path, format_log(entries))
+        .with_context(|| format!("failed to write {}", path.display()))
 }
 
 /// Thin wrapper around the shared activity log signal with convenience helpers for
 /// recording messages.
-#[derive(Clone)]
+#[derive(Clone, PartialEq)]
 pub struct ActivityLog {
     entries: Signal<Vec<LogEntry>>,
 }
@@ -57,6 +178,11 @@ impl ActivityLog {
         self.log(LogLevel::Success, message);
     }
 
+    /// Something the user should act on that did not fail outright.
+    pub fn warn(&self, message: impl Into<String>) {
+        self.log(LogLevel::Warning, message);
+    }
+
     pub fn error(&self, message: impl Into<String>) {
         self.log(LogLevel::Error, message);
     }
@@ -67,22 +193,84 @@ impl ActivityLog {
 }
 
 pub fn push_log(mut logs: Signal<Vec<LogEntry>>, level: LogLevel, message: impl Into<String>) {
-    let mut entries = logs.write();
-    entries.push(LogEntry::new(level, message));
+    append_capped(&mut logs.write(), LogEntry::new(level, message));
+}
+
+/// Add `entry` as the newest item, dropping the oldest ones beyond
+/// [`MAX_LOG_ENTRIES`].
+fn append_capped(entries: &mut Vec<LogEntry>, entry: LogEntry) {
+    entries.push(entry);
     if entries.len() > MAX_LOG_ENTRIES {
         let overflow = entries.len() - MAX_LOG_ENTRIES;
         entries.drain(0..overflow);
-        if overflow > 0 {
-            entries.insert(
-                0,
-                LogEntry::new(
-                    LogLevel::Info,
-                    format!("Trimmed {overflow} older log entries"),
-                ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{Duration, UNIX_EPOCH};
+
+    #[test]
+    fn log_keeps_the_newest_entries_in_order() {
+        let mut entries = Vec::new();
+        for index in 0..(MAX_LOG_ENTRIES + 25) {
+            append_capped(
+                &mut entries,
+                LogEntry::new(LogLevel::Info, index.to_string()),
             );
-            if entries.len() > MAX_LOG_ENTRIES {
-                entries.truncate(MAX_LOG_ENTRIES);
-            }
         }
+        let messages: Vec<&str> = entries.iter().map(LogEntry::message).collect();
+        let expected: Vec<String> = (25..MAX_LOG_ENTRIES + 25)
+            .map(|index| index.to_string())
+            .collect();
+        assert_eq!(messages, expected);
+    }
+
+    #[test]
+    fn filter_hides_toggled_levels() {
+        let filter = LogFilter::default();
+        assert!(LogLevel::ALL.iter().all(|level| filter.shows(*level)));
+
+        let errors_only = filter.toggled(LogLevel::Info).toggled(LogLevel::Success);
+        assert!(errors_only.allows(&LogEntry::new(LogLevel::Error, "failed")));
+        assert!(errors_only.allows(&LogEntry::new(LogLevel::Warning, "expiring")));
+        assert!(!errors_only.allows(&LogEntry::new(LogLevel::Info, "working")));
+        assert!(
+            !errors_only
+                .toggled(LogLevel::Warning)
+                .allows(&LogEntry::new(LogLevel::Warning, "expiring"))
+        );
+        assert_eq!(
+            errors_only
+                .toggled(LogLevel::Info)
+                .toggled(LogLevel::Success),
+            filter
+        );
+    }
+
+    #[test]
+    fn exported_log_is_timestamped_and_level_prefixed() {
+        let start = UNIX_EPOCH + Duration::from_secs(3_723);
+        let entries = vec![
+            LogEntry::at(LogLevel::Info, "Signing in...", start),
+            LogEntry::at(
+                LogLevel::Error,
+                "Request failed\nstatus 500",
+                start + Duration::from_secs(2),
+            ),
+        ];
+        assert_eq!(
+            format_log(&entries),
+            "[01:02:03 UTC] [INFO] Signing in...\n[01:02:05 UTC] [ERROR] Request failed\n    status 500\n"
+        );
+
+        let temp_dir = tempfile::tempdir().expect("temp dir");
+        let path = temp_dir.path().join("logs").join("activity.log");
+        save_log(&entries, &path).expect("log should save");
+        assert_eq!(
+            fs::read_to_string(&path).expect("log should exist"),
+            format_log(&entries)
+        );
     }
 }
@@ -0,0 +1,100 @@
+use pulldown_cmark::{CowStr, Event, Options, Parser, Tag, html};
+
+/// URL schemes a rendered link or image may point to. Anything else (notably
+/// `javascript:`) is replaced with an inert `#` target.
+const ALLOWED_SCHEMES: [&str; 4] = ["http", "https", "pubky", "mailto"];
+
+/// Post kinds whose content is written as markdown and can be previewed.
+pub fn supports_markdown_preview(kind: &str) -> bool {
+    matches!(kind.trim().to_ascii_lowercase().as_str(), "short" | "long")
+}
+
+/// Render post content to HTML for the composer preview.
+///
+/// Raw HTML in the source is escaped rather than passed through, and link or
+/// image destinations with unexpected schemes are neutralised, so the output is
+/// safe to inject into the WebView.
+pub fn render_markdown(source: &str) -> String {
+    let options =
+        Options::ENABLE_STRIKETHROUGH | Options::ENABLE_TABLES | Options::ENABLE_TASKLISTS;
+    let events = Parser::new_ext(source, options).map(|event| match event {
+        Event::Html(raw) | Event::InlineHtml(raw) => Event::Text(raw),
+        Event::Start(Tag::Link {
+            link_type,
+            dest_url,
+            title,
+            id,
+        }) => Event::Start(Tag::Link {
+            link_type,
+            dest_url: safe_destination(dest_url),
+            title,
+            id,
+        }),
+        Event::Start(Tag::Image {
+            link_type,
+            dest_url,
+            title,
+            id,
+        }) => Event::Start(Tag::Image {
+            link_type,
+            dest_url: safe_destination(dest_url),
+            title,
+            id,
+        }),
+        other => other,
+    });
+
+    let mut rendered = String::with_capacity(source.len() * 3 / 2);
+    html::push_html(&mut rendered, events);
+    rendered
+}
+
+fn safe_destination(dest: CowStr<'_>) -> CowStr<'_> {
+    let allowed = match dest.trim().split_once(':') {
+        Some((scheme, _)) if !scheme.contains(['/', '?', '#']) => {
+            ALLOWED_SCHEMES.contains(&scheme.to_ascii_lowercase().as_str())
+        }
+        _ => true,
+    };
+
+    if allowed { dest } else { CowStr::Borrowed("#") }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_common_markdown() {
+        let html = render_markdown("# Title\n\nSome **bold** and ~~old~~ text.\n\n- one\n- two");
+        assert!(html.contains("<h1>Title</h1>"));
+        assert!(html.contains("<strong>bold</strong>"));
+        assert!(html.contains("<del>old</del>"));
+        assert!(html.contains("<li>one</li>"));
+    }
+
+    #[test]
+    fn escapes_raw_html() {
+        let html = render_markdown("<script>alert(1)</script>\n\nhi <b>there</b>");
+        assert!(!html.contains("<script>"));
+        assert!(html.contains("&lt;script&gt;"));
+        assert!(!html.contains("<b>"));
+    }
+
+    #[test]
+    fn neutralises_unsafe_links() {
+        let html =
+            render_markdown("[bad](javascript:alert(1)) [good](https://pubky.app) [rel](/pub/a)");
+        assert!(!html.contains("javascript:"));
+        assert!(html.contains(r##"<a href="#">bad</a>"##));
+        assert!(html.contains(r#"href="https://pubky.app""#));
+        assert!(html.contains(r#"href="/pub/a""#));
+    }
+
+    #[test]
+    fn previews_only_text_post_kinds() {
+        assert!(supports_markdown_preview("short"));
+        assert!(supports_markdown_preview(" Long "));
+        assert!(!supports_markdown_preview("image"));
+    }
+}
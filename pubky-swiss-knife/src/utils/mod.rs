@@ -1,8 +1,30 @@
+pub mod accounts;
+pub mod capabilities;
+pub mod clipboard;
+pub mod confirm;
+pub mod diagnostics;
+pub mod documents;
+pub mod encoding;
+pub mod environments;
+pub mod errors;
+pub mod export;
 pub mod file_dialog;
 pub mod http;
 pub mod links;
 pub mod logging;
+pub mod markdown;
 pub mod mobile;
+pub mod packet;
+pub mod pkdns;
+pub mod posts;
+pub mod prefs;
+pub mod profile;
 pub mod pubky;
 pub mod qr;
 pub mod recovery;
+pub mod relay;
+pub mod session;
+pub mod storage;
+pub mod tags;
+pub mod task;
+pub mod testnet;
@@ -0,0 +1,115 @@
+use anyhow::{Context, Result, anyhow};
+use bytes::Bytes;
+use pkarr::SignedPacket;
+use pubky::{Pubky, PublicKey};
+use std::fmt::Write as _;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::utils::recovery::normalize_pkarr_path;
+
+/// Resolve the most recent signed packet published for `public_key`.
+pub async fn resolve_signed_packet(pubky: &Pubky, public_key: &PublicKey) -> Option<SignedPacket> {
+    pubky.client().pkarr().resolve_most_recent(public_key).await
+}
+
+/// Parse the raw `<public key><signature><timestamp><dns packet>` bytes of a signed packet.
+pub fn decode_signed_packet(bytes: &[u8]) -> Result<SignedPacket> {
+    SignedPacket::from_bytes(&Bytes::copy_from_slice(bytes))
+        .map_err(|err| anyhow!("invalid signed packet: {err}"))
+}
+
+pub fn save_signed_packet(packet: &SignedPacket, path: &str) -> Result<PathBuf> {
+    let normalized = normalize_pkarr_path(path)?;
+    if let Some(parent) = normalized.parent()
+        && !parent.as_os_str().is_empty()
+    {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("failed to create directory {}", parent.display()))?;
+    }
+    fs::write(&normalized, packet.as_bytes())
+        .with_context(|| format!("failed to write {}", normalized.display()))?;
+    Ok(normalized)
+}
+
+pub fn load_signed_packet(path: impl AsRef<Path>) -> Result<SignedPacket> {
+    let bytes = fs::read(path.as_ref())
+        .with_context(|| format!("failed to read {}", path.as_ref().display()))?;
+    decode_signed_packet(&bytes)
+}
+
+/// Render a human readable dump of a signed packet and its resource records,
+/// one `name  TYPE  TTL  rdata` line each.
+pub fn format_packet_dump(packet: &SignedPacket) -> String {
+    let mut out = String::new();
+    let _ = writeln!(out, "Public key: {}", packet.public_key());
+    let _ = writeln!(out, "Timestamp: {} µs", packet.timestamp().as_u64());
+    let _ = writeln!(out, "Size: {} bytes", packet.as_bytes().len());
+    let _ = writeln!(out, "Records:");
+    let mut any = false;
+    for record in packet.all_resource_records() {
+        any = true;
+        let _ = writeln!(
+            out,
+            "  {}  {:?}  TTL {}  {:?}",
+            record.name,
+            record.rdata.type_code(),
+            record.ttl,
+            record.rdata
+        );
+    }
+    if !any {
+        let _ = writeln!(out, "  (none)");
+    }
+    out.trim_end().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pkarr::dns::Name;
+    use pubky::Keypair;
+    use std::net::Ipv4Addr;
+    use tempfile::TempDir;
+
+    fn sample_packet() -> Result<SignedPacket> {
+        let keypair = Keypair::from_secret_key(&[9u8; 32]);
+        let packet = SignedPacket::builder()
+            .a(Name::new("_swiss")?, Ipv4Addr::new(127, 0, 0, 1), 300)
+            .sign(&keypair)?;
+        Ok(packet)
+    }
+
+    #[test]
+    fn save_and_load_signed_packet_roundtrip() -> Result<()> {
+        let packet = sample_packet()?;
+        let dir = TempDir::new()?;
+        let target = dir.path().join("packets/sample");
+        let saved = save_signed_packet(&packet, &target.to_string_lossy())?;
+        assert_eq!(
+            saved.extension().and_then(|ext| ext.to_str()),
+            Some("pkarr")
+        );
+
+        let restored = load_signed_packet(&saved)?;
+        assert_eq!(restored.as_bytes(), packet.as_bytes());
+        assert_eq!(restored.public_key(), packet.public_key());
+        Ok(())
+    }
+
+    #[test]
+    fn format_packet_dump_lists_records() -> Result<()> {
+        let packet = sample_packet()?;
+        let dump = format_packet_dump(&packet);
+        assert!(dump.contains(&packet.public_key().to_string()));
+        assert!(dump.contains("_swiss"));
+        assert!(dump.contains("  A  TTL 300"));
+        Ok(())
+    }
+
+    #[test]
+    fn decode_signed_packet_rejects_garbage() {
+        let err = decode_signed_packet(b"not a packet").unwrap_err();
+        assert!(err.to_string().contains("invalid signed packet"));
+    }
+}
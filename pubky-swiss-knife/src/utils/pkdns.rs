@@ -0,0 +1,230 @@
+use anyhow::{Context, Result};
+use futures::{StreamExt, stream};
+use pubky::{Pubky, PublicKey};
+use std::collections::HashSet;
+use std::fs;
+use std::path::PathBuf;
+
+/// Upper bound on PKARR lookups in flight during a batch resolve.
+pub const MAX_CONCURRENT_LOOKUPS: usize = 8;
+
+/// Default interval of the scheduled `_pubky` republish.
+pub const DEFAULT_REPUBLISH_MINUTES: u64 = 60;
+
+/// Longest scheduled republish interval accepted: one day.
+pub const MAX_REPUBLISH_MINUTES: u64 = 24 * 60;
+
+/// Parse the republish interval field, in whole minutes.
+pub fn parse_republish_minutes(input: &str) -> Result<u64, String> {
+    let minutes: u64 = input
+        .trim()
+        .parse()
+        .map_err(|_| format!("'{}' is not a whole number of minutes", input.trim()))?;
+    if (1..=MAX_REPUBLISH_MINUTES).contains(&minutes) {
+        Ok(minutes)
+    } else {
+        Err(format!(
+            "Republish interval must be between 1 and {MAX_REPUBLISH_MINUTES} minutes"
+        ))
+    }
+}
+
+/// Outcome of resolving one public key in a batch.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum BatchLookupOutcome {
+    Homeserver(String),
+    NoRecord,
+    Error(String),
+}
+
+impl BatchLookupOutcome {
+    pub fn status(&self) -> &'static str {
+        match self {
+            BatchLookupOutcome::Homeserver(_) => "found",
+            BatchLookupOutcome::NoRecord => "none",
+            BatchLookupOutcome::Error(_) => "error",
+        }
+    }
+
+    /// Short text for the results table.
+    pub fn summary(&self) -> String {
+        match self {
+            BatchLookupOutcome::Homeserver(host) => host.clone(),
+            BatchLookupOutcome::NoRecord => String::from("none"),
+            BatchLookupOutcome::Error(message) => format!("error: {message}"),
+        }
+    }
+
+    pub fn detail(&self) -> &str {
+        match self {
+            BatchLookupOutcome::Homeserver(host) => host,
+            BatchLookupOutcome::NoRecord => "",
+            BatchLookupOutcome::Error(message) => message,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BatchLookupRow {
+    pub public_key: String,
+    pub outcome: BatchLookupOutcome,
+}
+
+/// Resolve the homeserver `public_key` advertises in its `_pubky` record.
+pub async fn resolve_homeserver(pubky: &Pubky, public_key: &PublicKey) -> Option<String> {
+    pubky
+        .pkdns()
+        .get_homeserver_of(public_key)
+        .await
+        .map(|host| host.to_string())
+}
+
+/// Split newline-separated input into trimmed, de-duplicated public keys.
+pub fn parse_key_list(input: &str) -> Vec<String> {
+    let mut seen = HashSet::new();
+    input
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .filter(|line| seen.insert(line.to_string()))
+        .map(str::to_string)
+        .collect()
+}
+
+/// Resolve every key with bounded concurrency, keeping the input order.
+pub async fn resolve_homeservers(pubky: &Pubky, keys: Vec<String>) -> Vec<BatchLookupRow> {
+    stream::iter(keys)
+        .map(|key| async move {
+            let outcome = match PublicKey::try_from(key.as_str()) {
+                Ok(public_key) => match resolve_homeserver(pubky, &public_key).await {
+                    Some(host) => BatchLookupOutcome::Homeserver(host),
+                    None => BatchLookupOutcome::NoRecord,
+                },
+                Err(err) => BatchLookupOutcome::Error(format!("invalid public key: {err}")),
+            };
+            BatchLookupRow {
+                public_key: key,
+                outcome,
+            }
+        })
+        .buffered(MAX_CONCURRENT_LOOKUPS)
+        .collect()
+        .await
+}
+
+/// Render results as `pubkey -> homeserver` lines, with `none` for keys that
+/// advertise no homeserver and the error inline for invalid ones.
+pub fn format_lookup_table(rows: &[BatchLookupRow]) -> String {
+    rows.iter()
+        .map(|row| format!("{} -> {}", row.public_key, row.outcome.summary()))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Render batch results as CSV with a header row.
+pub fn format_batch_csv(rows: &[BatchLookupRow]) -> String {
+    let mut csv = String::from("public_key,status,homeserver_or_error\n");
+    for row in rows {
+        csv.push_str(&format!(
+            "{},{},{}\n",
+            csv_field(&row.public_key),
+            row.outcome.status(),
+            csv_field(row.outcome.detail())
+        ));
+    }
+    csv
+}
+
+/// Write the CSV export, adding a `.csv` extension when none is given.
+pub fn save_batch_csv(rows: &[BatchLookupRow], path: &str) -> Result<PathBuf> {
+    let mut target = PathBuf::from(path.trim());
+    if target.extension().is_none() {
+        target.set_extension("csv");
+    }
+    if let Some(parent) = target
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+    {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("failed to create directory {}", parent.display()))?;
+    }
+    fs::write(&target, format_batch_csv(rows))
+        .with_context(|| format!("failed to write {}", target.display()))?;
+    Ok(target)
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn sample_rows() -> Vec<BatchLookupRow> {
+        vec![
+            BatchLookupRow {
+                public_key: "alice".into(),
+                outcome: BatchLookupOutcome::Homeserver("hs1".into()),
+            },
+            BatchLookupRow {
+                public_key: "bob".into(),
+                outcome: BatchLookupOutcome::NoRecord,
+            },
+            BatchLookupRow {
+                public_key: "carol".into(),
+                outcome: BatchLookupOutcome::Error("invalid public key: bad, \"z32\"".into()),
+            },
+        ]
+    }
+
+    #[test]
+    fn parse_key_list_trims_and_dedupes() {
+        let keys = parse_key_list("  alice \n\nbob\nalice\n\t carol\n");
+        assert_eq!(keys, vec!["alice", "bob", "carol"]);
+    }
+
+    #[test]
+    fn lookup_table_reports_every_line() {
+        assert_eq!(
+            format_lookup_table(&sample_rows()),
+            "alice -> hs1\nbob -> none\ncarol -> error: invalid public key: bad, \"z32\""
+        );
+    }
+
+    #[test]
+    fn csv_export_escapes_fields() {
+        let csv = format_batch_csv(&sample_rows());
+        let lines: Vec<_> = csv.lines().collect();
+        assert_eq!(lines[0], "public_key,status,homeserver_or_error");
+        assert_eq!(lines[1], "alice,found,hs1");
+        assert_eq!(lines[2], "bob,none,");
+        assert_eq!(
+            lines[3],
+            r#"carol,error,"invalid public key: bad, ""z32""""#
+        );
+    }
+
+    #[test]
+    fn save_batch_csv_adds_extension() -> Result<()> {
+        let dir = TempDir::new()?;
+        let saved = save_batch_csv(&sample_rows(), dir.path().join("audit").to_str().unwrap())?;
+        assert_eq!(saved.extension().and_then(|ext| ext.to_str()), Some("csv"));
+        assert!(fs::read_to_string(saved)?.starts_with("public_key,"));
+        Ok(())
+    }
+
+    #[test]
+    fn republish_interval_is_bounded() {
+        assert_eq!(parse_republish_minutes(" 60 "), Ok(60));
+        assert_eq!(parse_republish_minutes("1440"), Ok(MAX_REPUBLISH_MINUTES));
+        assert!(parse_republish_minutes("0").is_err());
+        assert!(parse_republish_minutes("1441").is_err());
+        assert!(parse_republish_minutes("1.5").is_err());
+    }
+}
@@ -0,0 +1,138 @@
+use pubky_app_specs::PubkyAppPost;
+
+/// Longest `long` post pubky.app accepts, in characters.
+pub const MAX_LONG_POST_CHARS: usize = 50_000;
+
+/// Longest post of any other kind, in characters.
+pub const MAX_SHORT_POST_CHARS: usize = 2_000;
+
+/// Character limit for a post of `kind`, matching `PubkyAppPost::validate`.
+pub fn content_limit(kind: &str) -> usize {
+    if kind.trim().eq_ignore_ascii_case("long") {
+        MAX_LONG_POST_CHARS
+    } else {
+        MAX_SHORT_POST_CHARS
+    }
+}
+
+/// Length of the composer content against the limit for the selected kind.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ContentLength {
+    pub count: usize,
+    pub limit: usize,
+}
+
+impl ContentLength {
+    pub fn measure(content: &str, kind: &str) -> Self {
+        Self {
+            count: content.chars().count(),
+            limit: content_limit(kind),
+        }
+    }
+
+    pub fn overage(&self) -> usize {
+        self.count.saturating_sub(self.limit)
+    }
+
+    pub fn is_over(&self) -> bool {
+        self.count > self.limit
+    }
+
+    /// `"120 / 2000"`, or `"2012 / 2000 (12 over)"` past the limit.
+    pub fn label(&self) -> String {
+        if self.is_over() {
+            format!("{} / {} ({} over)", self.count, self.limit, self.overage())
+        } else {
+            format!("{} / {}", self.count, self.limit)
+        }
+    }
+}
+
+/// A stored post spread over the composer fields, in the text form each
+/// field edits.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ComposerFields {
+    pub content: String,
+    pub kind: String,
+    pub parent: String,
+    pub embed_kind: String,
+    pub embed_uri: String,
+    /// One attachment URI per line.
+    pub attachments: String,
+}
+
+/// Parse a fetched body as a `PubkyAppPost` and lay it out for the composer.
+pub fn composer_fields(body: &[u8]) -> Result<ComposerFields, String> {
+    let post: PubkyAppPost =
+        serde_json::from_slice(body).map_err(|err| format!("Not a pubky.app post: {err}"))?;
+    let (embed_kind, embed_uri) = post
+        .embed
+        .map(|embed| (embed.kind.to_string(), embed.uri))
+        .unwrap_or_default();
+    Ok(ComposerFields {
+        content: post.content,
+        kind: post.kind.to_string(),
+        parent: post.parent.unwrap_or_default(),
+        embed_kind,
+        embed_uri,
+        attachments: post.attachments.unwrap_or_default().join("\n"),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn limits_follow_the_post_kind() {
+        assert_eq!(content_limit("long"), MAX_LONG_POST_CHARS);
+        assert_eq!(content_limit(" Long "), MAX_LONG_POST_CHARS);
+        assert_eq!(content_limit("short"), MAX_SHORT_POST_CHARS);
+        assert_eq!(content_limit("image"), MAX_SHORT_POST_CHARS);
+    }
+
+    #[test]
+    fn stored_posts_fill_the_composer() {
+        let body = br#"{
+            "content": "Hello **pubky**",
+            "kind": "long",
+            "parent": "pubky://alice/pub/pubky.app/posts/0033SSE3B1FQ0",
+            "embed": { "kind": "link", "uri": "https://example.com" },
+            "attachments": ["pubky://alice/pub/pubky.app/files/1", "pubky://alice/pub/pubky.app/files/2"]
+        }"#;
+        let fields = composer_fields(body).expect("valid post");
+        assert_eq!(fields.content, "Hello **pubky**");
+        assert_eq!(fields.kind, "long");
+        assert_eq!(
+            fields.parent,
+            "pubky://alice/pub/pubky.app/posts/0033SSE3B1FQ0"
+        );
+        assert_eq!(fields.embed_kind, "link");
+        assert_eq!(fields.embed_uri, "https://example.com");
+        assert_eq!(
+            fields.attachments,
+            "pubky://alice/pub/pubky.app/files/1\npubky://alice/pub/pubky.app/files/2"
+        );
+
+        let minimal = composer_fields(br#"{"content":"hi","kind":"short"}"#).expect("valid post");
+        assert_eq!(minimal.embed_kind, "");
+        assert_eq!(minimal.attachments, "");
+
+        assert!(composer_fields(br#"{"name":"alice"}"#).is_err());
+        assert!(composer_fields(b"not json").is_err());
+    }
+
+    #[test]
+    fn counts_characters_and_reports_overage() {
+        let within = ContentLength::measure("héllo ✓", "short");
+        assert_eq!(within.count, 7);
+        assert!(!within.is_over());
+        assert_eq!(within.label(), "7 / 2000");
+
+        let over = ContentLength::measure(&"a".repeat(MAX_SHORT_POST_CHARS + 12), "short");
+        assert!(over.is_over());
+        assert_eq!(over.overage(), 12);
+        assert_eq!(over.label(), "2012 / 2000 (12 over)");
+        assert!(!ContentLength::measure(&"a".repeat(MAX_SHORT_POST_CHARS + 12), "long").is_over());
+    }
+}
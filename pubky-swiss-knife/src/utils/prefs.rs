@@ -0,0 +1,606 @@
+use anyhow::{Context, Result, anyhow};
+use dioxus::prelude::{ReadableExt, Signal, WritableExt};
+use pubky::Capabilities;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::app::{NetworkMode, Tab};
+use crate::utils::environments::EnvironmentPrefs;
+use crate::utils::logging::ActivityLog;
+
+/// Number of recovery files remembered for quick re-loading.
+pub const MAX_RECENT_RECOVERY_FILES: usize = 5;
+
+const PREFS_FILE_NAME: &str = "prefs.json";
+
+/// Pause after the last edit before remembered tab inputs are written, so
+/// typing does not rewrite `prefs.json` on every keystroke.
+pub const INPUTS_SAVE_DELAY: Duration = Duration::from_secs(1);
+
+/// Built-in capability string used by the token and auth tabs.
+pub const DEFAULT_CAPABILITIES: &str = "/:rw";
+
+/// Built-in storage path used by the storage tab.
+pub const DEFAULT_STORAGE_PATH: &str = "/pub/";
+
+/// Non-sensitive preferences persisted between launches. Secrets and
+/// passphrases must never be added here.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AppPrefs {
+    pub recovery: RecoveryPrefs,
+    pub defaults: DefaultsPrefs,
+    pub safety: SafetyPrefs,
+    pub inputs: InputPrefs,
+    pub environments: EnvironmentPrefs,
+    pub session: SessionPrefs,
+    /// [`Tab::key`] of the tab selected last.
+    pub last_tab: Option<String>,
+    /// Retry a failed Pubky client build with backoff before showing the error.
+    pub auto_retry_facade: bool,
+}
+
+impl AppPrefs {
+    /// Whether deletes, overwrites, and record publishes on `network` must be
+    /// confirmed first. Testnet is never gated.
+    pub fn confirms_destructive(&self, network: NetworkMode) -> bool {
+        self.safety.confirm_mainnet_destructive && network == NetworkMode::Mainnet
+    }
+
+    /// Whether a storage PUT on `network` first checks for an entry it would
+    /// replace.
+    pub fn checks_overwrite(&self, network: NetworkMode) -> bool {
+        match self.safety.overwrite_check {
+            OverwriteCheck::Off => false,
+            OverwriteCheck::Mainnet => network == NetworkMode::Mainnet,
+            OverwriteCheck::Always => true,
+        }
+    }
+
+    /// The tab to open at launch: the last one selected, unless it needs a
+    /// session and none is loaded, or it no longer exists.
+    pub fn launch_tab(&self, has_session: bool) -> Tab {
+        self.last_tab
+            .as_deref()
+            .and_then(Tab::from_key)
+            .filter(|tab| has_session || !tab.requires_session())
+            .unwrap_or(Tab::Keys)
+    }
+
+    /// Whether raw secret keys may be revealed or copied out of the app.
+    pub fn allows_secret_export(&self) -> bool {
+        self.safety.allow_secret_export
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RecoveryPrefs {
+    pub last_dir: Option<PathBuf>,
+    pub recent_files: Vec<PathBuf>,
+}
+
+impl RecoveryPrefs {
+    /// Record `path` as the most recently used recovery file.
+    pub fn remember(&mut self, path: &Path) {
+        if let Some(parent) = path.parent()
+            && !parent.as_os_str().is_empty()
+        {
+            self.last_dir = Some(parent.to_path_buf());
+        }
+        self.recent_files.retain(|existing| existing != path);
+        self.recent_files.insert(0, path.to_path_buf());
+        self.recent_files.truncate(MAX_RECENT_RECOVERY_FILES);
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SafetyPrefs {
+    pub confirm_mainnet_destructive: bool,
+    /// Off by default; gates every action that shows raw secret keys.
+    pub allow_secret_export: bool,
+    /// Set once the warning shown on first enabling secret export is dismissed.
+    pub secret_export_warning_seen: bool,
+    pub overwrite_check: OverwriteCheck,
+}
+
+impl Default for SafetyPrefs {
+    fn default() -> Self {
+        Self {
+            confirm_mainnet_destructive: true,
+            allow_secret_export: false,
+            secret_export_warning_seen: false,
+            overwrite_check: OverwriteCheck::default(),
+        }
+    }
+}
+
+/// When a storage PUT looks for an existing entry and asks before replacing it.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OverwriteCheck {
+    Off,
+    #[default]
+    Mainnet,
+    Always,
+}
+
+impl OverwriteCheck {
+    pub const ALL: [OverwriteCheck; 3] = [
+        OverwriteCheck::Off,
+        OverwriteCheck::Mainnet,
+        OverwriteCheck::Always,
+    ];
+
+    pub fn key(self) -> &'static str {
+        match self {
+            OverwriteCheck::Off => "off",
+            OverwriteCheck::Mainnet => "mainnet",
+            OverwriteCheck::Always => "always",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            OverwriteCheck::Off => "Never",
+            OverwriteCheck::Mainnet => "On Mainnet only",
+            OverwriteCheck::Always => "On every network",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|check| check.key() == key)
+    }
+}
+
+/// Homeserver and signup code from the last successful mainnet signup or
+/// sign-in, restored into the Sessions tab at launch. Testnet keeps its own
+/// prefill.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SessionPrefs {
+    pub homeserver: String,
+    pub signup_code: String,
+}
+
+impl SessionPrefs {
+    /// Remember the fields, keeping the stored homeserver when `homeserver`
+    /// is blank (a root sign-in needs none).
+    pub fn remember(&mut self, homeserver: &str, signup_code: &str) {
+        let homeserver = homeserver.trim();
+        if !homeserver.is_empty() {
+            self.homeserver = homeserver.to_string();
+        }
+        self.signup_code = signup_code.trim().to_string();
+    }
+}
+
+/// Opt-in memory of the last tab inputs, restored at launch.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct InputPrefs {
+    pub enabled: bool,
+    pub saved: Option<SavedInputs>,
+}
+
+impl InputPrefs {
+    /// Inputs to restore at launch, if remembering is on.
+    pub fn restored(&self) -> Option<&SavedInputs> {
+        self.saved.as_ref().filter(|_| self.enabled)
+    }
+}
+
+/// Request fields worth keeping between launches. Keys, passphrases, signup
+/// codes, and request bodies are deliberately absent, and headers that carry
+/// credentials are dropped before saving.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SavedInputs {
+    pub http_method: String,
+    pub http_url: String,
+    pub http_headers: String,
+    pub storage_path: String,
+    pub token_capabilities: String,
+    pub auth_capabilities: String,
+    pub pkdns_lookup: String,
+    pub pkdns_host_override: String,
+    pub pkdns_batch: String,
+}
+
+impl Default for SavedInputs {
+    fn default() -> Self {
+        Self::from_defaults(&NetworkDefaults::default())
+    }
+}
+
+impl SavedInputs {
+    /// The values the tabs start with when nothing was saved.
+    pub fn from_defaults(defaults: &NetworkDefaults) -> Self {
+        Self {
+            http_method: String::from("GET"),
+            http_url: String::from("https://"),
+            http_headers: String::new(),
+            storage_path: defaults.storage_path.clone(),
+            token_capabilities: defaults.capabilities.clone(),
+            auth_capabilities: defaults.capabilities.clone(),
+            pkdns_lookup: String::new(),
+            pkdns_host_override: String::new(),
+            pkdns_batch: String::new(),
+        }
+    }
+}
+
+/// Drop `Name: Value` header lines that usually carry credentials.
+pub fn strip_sensitive_headers(headers: &str) -> String {
+    headers
+        .lines()
+        .filter(|line| {
+            let name = line
+                .split_once(':')
+                .map_or(*line, |(name, _)| name)
+                .trim()
+                .to_ascii_lowercase();
+            !matches!(
+                name.as_str(),
+                "authorization" | "proxy-authorization" | "cookie"
+            ) && !["token", "secret", "password", "api-key", "session"]
+                .iter()
+                .any(|needle| name.contains(needle))
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Starting values for tab inputs, kept separately for each network.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DefaultsPrefs {
+    pub mainnet: NetworkDefaults,
+    pub testnet: NetworkDefaults,
+}
+
+impl DefaultsPrefs {
+    pub fn for_network(&self, mode: NetworkMode) -> &NetworkDefaults {
+        match mode {
+            NetworkMode::Mainnet => &self.mainnet,
+            NetworkMode::Testnet => &self.testnet,
+        }
+    }
+
+    pub fn for_network_mut(&mut self, mode: NetworkMode) -> &mut NetworkDefaults {
+        match mode {
+            NetworkMode::Mainnet => &mut self.mainnet,
+            NetworkMode::Testnet => &mut self.testnet,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NetworkDefaults {
+    pub capabilities: String,
+    pub storage_path: String,
+}
+
+impl Default for NetworkDefaults {
+    fn default() -> Self {
+        Self {
+            capabilities: DEFAULT_CAPABILITIES.to_string(),
+            storage_path: DEFAULT_STORAGE_PATH.to_string(),
+        }
+    }
+}
+
+impl NetworkDefaults {
+    /// Validate user-entered defaults, normalising the storage path to a
+    /// `/pub/` directory with a trailing slash.
+    pub fn validated(capabilities: &str, storage_path: &str) -> Result<Self> {
+        let capabilities = capabilities.trim();
+        Capabilities::try_from(capabilities)
+            .map_err(|err| anyhow!("invalid capabilities '{capabilities}': {err}"))?;
+
+        let mut storage_path = storage_path.trim().to_string();
+        if !storage_path.starts_with('/') {
+            storage_path.insert(0, '/');
+        }
+        if !storage_path.ends_with('/') {
+            storage_path.push('/');
+        }
+        if !storage_path.starts_with(DEFAULT_STORAGE_PATH) {
+            return Err(anyhow!(
+                "storage path must live under {DEFAULT_STORAGE_PATH}"
+            ));
+        }
+
+        Ok(Self {
+            capabilities: capabilities.to_string(),
+            storage_path,
+        })
+    }
+}
+
+/// Location of the preferences file inside the platform config directory.
+pub fn prefs_path() -> Option<PathBuf> {
+    directories::ProjectDirs::from("io", "Pubky", "SwissKnife")
+        .map(|dirs| dirs.config_dir().join(PREFS_FILE_NAME))
+}
+
+pub fn load_prefs_from(path: &Path) -> Result<AppPrefs> {
+    if !path.is_file() {
+        return Ok(AppPrefs::default());
+    }
+    let bytes = fs::read(path).with_context(|| format!("failed to read {}", path.display()))?;
+    serde_json::from_slice(&bytes).with_context(|| format!("failed to parse {}", path.display()))
+}
+
+pub fn save_prefs_to(path: &Path, prefs: &AppPrefs) -> Result<()> {
+    if let Some(parent) = path.parent()
+        && !parent.as_os_str().is_empty()
+    {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("failed to create directory {}", parent.display()))?;
+    }
+    let rendered = serde_json::to_vec_pretty(prefs)?;
+    fs::write(path, rendered).with_context(|| format!("failed to write {}", path.display()))
+}
+
+/// Load the stored preferences, falling back to defaults when missing or unreadable.
+pub fn load_prefs() -> AppPrefs {
+    prefs_path()
+        .and_then(|path| load_prefs_from(&path).ok())
+        .unwrap_or_default()
+}
+
+pub fn save_prefs(prefs: &AppPrefs) -> Result<()> {
+    match prefs_path() {
+        Some(path) => save_prefs_to(&path, prefs),
+        None => Ok(()),
+    }
+}
+
+/// Shared handle to the in-memory preferences that writes changes through to disk.
+#[derive(Clone, Copy)]
+pub struct PrefsHandle {
+    prefs: Signal<AppPrefs>,
+}
+
+impl PrefsHandle {
+    pub fn new(prefs: Signal<AppPrefs>) -> Self {
+        Self { prefs }
+    }
+
+    pub fn snapshot(&self) -> AppPrefs {
+        self.prefs.read().clone()
+    }
+
+    pub fn update(&self, logs: &ActivityLog, apply: impl FnOnce(&mut AppPrefs)) {
+        let mut prefs = self.prefs;
+        let updated = {
+            let mut guard = prefs.write();
+            apply(&mut guard);
+            guard.clone()
+        };
+        if let Err(err) = save_prefs(&updated) {
+            logs.error(format!("Failed to save preferences: {err}"));
+        }
+    }
+}
+
+/// The tab inputs covered by [`InputPrefs`], so they can be saved and reset
+/// together.
+#[derive(Clone, Copy)]
+pub struct InputFields {
+    pub http_method: Signal<String>,
+    pub http_url: Signal<String>,
+    pub http_headers: Signal<String>,
+    pub storage_path: Signal<String>,
+    pub token_capabilities: Signal<String>,
+    pub auth_capabilities: Signal<String>,
+    pub pkdns_lookup: Signal<String>,
+    pub pkdns_host_override: Signal<String>,
+    pub pkdns_batch: Signal<String>,
+}
+
+impl InputFields {
+    /// Current values, ready to save; subscribes to every field.
+    pub fn current(&self) -> SavedInputs {
+        SavedInputs {
+            http_method: self.http_method.read().clone(),
+            http_url: self.http_url.read().clone(),
+            http_headers: strip_sensitive_headers(&self.http_headers.read()),
+            storage_path: self.storage_path.read().clone(),
+            token_capabilities: self.token_capabilities.read().clone(),
+            auth_capabilities: self.auth_capabilities.read().clone(),
+            pkdns_lookup: self.pkdns_lookup.read().clone(),
+            pkdns_host_override: self.pkdns_host_override.read().clone(),
+            pkdns_batch: self.pkdns_batch.read().clone(),
+        }
+    }
+
+    pub fn fill(&self, values: &SavedInputs) {
+        let Self {
+            mut http_method,
+            mut http_url,
+            mut http_headers,
+            mut storage_path,
+            mut token_capabilities,
+            mut auth_capabilities,
+            mut pkdns_lookup,
+            mut pkdns_host_override,
+            mut pkdns_batch,
+        } = *self;
+        http_method.set(values.http_method.clone());
+        http_url.set(values.http_url.clone());
+        http_headers.set(values.http_headers.clone());
+        storage_path.set(values.storage_path.clone());
+        token_capabilities.set(values.token_capabilities.clone());
+        auth_capabilities.set(values.auth_capabilities.clone());
+        pkdns_lookup.set(values.pkdns_lookup.clone());
+        pkdns_host_override.set(values.pkdns_host_override.clone());
+        pkdns_batch.set(values.pkdns_batch.clone());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn missing_prefs_file_yields_defaults() -> Result<()> {
+        let dir = TempDir::new()?;
+        let prefs = load_prefs_from(&dir.path().join(PREFS_FILE_NAME))?;
+        assert_eq!(prefs, AppPrefs::default());
+        Ok(())
+    }
+
+    #[test]
+    fn prefs_roundtrip_through_disk() -> Result<()> {
+        let dir = TempDir::new()?;
+        let path = dir.path().join("nested").join(PREFS_FILE_NAME);
+        let mut prefs = AppPrefs::default();
+        prefs.recovery.remember(Path::new("/keys/alice.pkarr"));
+
+        save_prefs_to(&path, &prefs)?;
+        assert_eq!(load_prefs_from(&path)?, prefs);
+        Ok(())
+    }
+
+    #[test]
+    fn older_prefs_files_get_builtin_defaults() -> Result<()> {
+        let prefs: AppPrefs = serde_json::from_str(r#"{"recovery":{"recent_files":[]}}"#)?;
+        assert_eq!(
+            prefs.defaults.for_network(NetworkMode::Testnet),
+            &NetworkDefaults::default()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn last_tab_is_restored_unless_it_needs_a_session() {
+        let mut prefs = AppPrefs::default();
+        assert_eq!(prefs.launch_tab(false), Tab::Keys);
+
+        prefs.last_tab = Some(Tab::Storage.key().to_string());
+        assert_eq!(prefs.launch_tab(false), Tab::Storage);
+
+        prefs.last_tab = Some(Tab::Social.key().to_string());
+        assert_eq!(prefs.launch_tab(false), Tab::Keys);
+        assert_eq!(prefs.launch_tab(true), Tab::Social);
+
+        prefs.last_tab = Some(String::from("removed-tab"));
+        assert_eq!(prefs.launch_tab(true), Tab::Keys);
+    }
+
+    #[test]
+    fn session_fields_keep_the_last_homeserver() {
+        let mut session = SessionPrefs::default();
+        session.remember(" homeserver-key ", " CODE-1 ");
+        assert_eq!(session.homeserver, "homeserver-key");
+        assert_eq!(session.signup_code, "CODE-1");
+
+        session.remember("", "");
+        assert_eq!(session.homeserver, "homeserver-key");
+        assert!(session.signup_code.is_empty());
+    }
+
+    #[test]
+    fn destructive_confirmation_only_applies_to_mainnet() {
+        let mut prefs = AppPrefs::default();
+        assert!(prefs.confirms_destructive(NetworkMode::Mainnet));
+        assert!(!prefs.confirms_destructive(NetworkMode::Testnet));
+
+        prefs.safety.confirm_mainnet_destructive = false;
+        assert!(!prefs.confirms_destructive(NetworkMode::Mainnet));
+    }
+
+    #[test]
+    fn overwrite_check_defaults_to_mainnet_only() -> Result<()> {
+        let mut prefs = AppPrefs::default();
+        assert!(prefs.checks_overwrite(NetworkMode::Mainnet));
+        assert!(!prefs.checks_overwrite(NetworkMode::Testnet));
+
+        prefs.safety.overwrite_check = OverwriteCheck::Always;
+        assert!(prefs.checks_overwrite(NetworkMode::Testnet));
+        prefs.safety.overwrite_check = OverwriteCheck::Off;
+        assert!(!prefs.checks_overwrite(NetworkMode::Mainnet));
+
+        let older: AppPrefs =
+            serde_json::from_str(r#"{"safety":{"confirm_mainnet_destructive":true}}"#)?;
+        assert_eq!(older.safety.overwrite_check, OverwriteCheck::Mainnet);
+        let stored: AppPrefs = serde_json::from_str(r#"{"safety":{"overwrite_check":"always"}}"#)?;
+        assert_eq!(stored.safety.overwrite_check, OverwriteCheck::Always);
+        Ok(())
+    }
+
+    #[test]
+    fn secret_export_is_opt_in() -> Result<()> {
+        assert!(!AppPrefs::default().allows_secret_export());
+
+        let prefs: AppPrefs =
+            serde_json::from_str(r#"{"safety":{"confirm_mainnet_destructive":false}}"#)?;
+        assert!(!prefs.allows_secret_export());
+        assert!(!prefs.safety.secret_export_warning_seen);
+        Ok(())
+    }
+
+    #[test]
+    fn saved_inputs_are_opt_in_and_drop_credentials() -> Result<()> {
+        let mut inputs = InputPrefs {
+            enabled: false,
+            saved: Some(SavedInputs::default()),
+        };
+        assert_eq!(inputs.restored(), None);
+        inputs.enabled = true;
+        assert_eq!(
+            inputs.restored().map(|saved| saved.http_method.as_str()),
+            Some("GET")
+        );
+
+        let headers = "Accept: application/json\nAuthorization: Bearer abc\ncookie: s=1\nX-Api-Key: k\nX-Session-Id: 9\nX-Trace: on";
+        assert_eq!(
+            strip_sensitive_headers(headers),
+            "Accept: application/json\nX-Trace: on"
+        );
+
+        let prefs: AppPrefs = serde_json::from_str(r#"{"inputs":{"enabled":true}}"#)?;
+        assert!(prefs.inputs.saved.is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn validates_and_normalises_network_defaults() -> Result<()> {
+        let defaults = NetworkDefaults::validated(" /pub/myapp/:rw ", "pub/myapp")?;
+        assert_eq!(defaults.capabilities, "/pub/myapp/:rw");
+        assert_eq!(defaults.storage_path, "/pub/myapp/");
+
+        assert!(NetworkDefaults::validated("not caps", "/pub/").is_err());
+        assert!(NetworkDefaults::validated(DEFAULT_CAPABILITIES, "/private/").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn remember_tracks_last_dir_and_caps_recent_files() {
+        let mut recovery = RecoveryPrefs::default();
+        for index in 0..(MAX_RECENT_RECOVERY_FILES + 2) {
+            recovery.remember(Path::new(&format!("/keys/key-{index}.pkarr")));
+        }
+        recovery.remember(Path::new("/keys/key-3.pkarr"));
+
+        assert_eq!(recovery.last_dir.as_deref(), Some(Path::new("/keys")));
+        assert_eq!(recovery.recent_files.len(), MAX_RECENT_RECOVERY_FILES);
+        assert_eq!(
+            recovery.recent_files.first().map(PathBuf::as_path),
+            Some(Path::new("/keys/key-3.pkarr"))
+        );
+        let occurrences = recovery
+            .recent_files
+            .iter()
+            .filter(|path| path.as_path() == Path::new("/keys/key-3.pkarr"))
+            .count();
+        assert_eq!(occurrences, 1);
+    }
+}
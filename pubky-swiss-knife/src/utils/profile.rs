@@ -0,0 +1,237 @@
+use pubky_app_specs::PubkyAppUser;
+use url::Url;
+
+use crate::utils::links::is_web_link;
+
+/// How the avatar of a fetched profile is shown.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ProfileAvatar {
+    /// An image URL the webview can load directly.
+    Image(String),
+    /// The name's initial, plus the image reference when it is set but
+    /// cannot be displayed (for example a `pubky://` file).
+    Placeholder {
+        initial: String,
+        source: Option<String>,
+    },
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ProfileLink {
+    pub title: String,
+    pub url: String,
+    /// Only http(s) and mailto links are opened outside the app.
+    pub openable: bool,
+}
+
+/// A fetched `profile.json`, prepared for the Social tab profile card.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ProfileView {
+    pub name: String,
+    pub status: Option<String>,
+    pub bio: Option<String>,
+    pub avatar: ProfileAvatar,
+    pub links: Vec<ProfileLink>,
+}
+
+impl ProfileView {
+    pub fn from_user(user: &PubkyAppUser) -> Self {
+        let image = non_empty(user.image.as_deref());
+        let avatar = match image.clone() {
+            Some(src) if is_loadable_image(&src) => ProfileAvatar::Image(src),
+            source => ProfileAvatar::Placeholder {
+                initial: initial(&user.name),
+                source,
+            },
+        };
+        let links = user
+            .links
+            .iter()
+            .flatten()
+            .map(|link| ProfileLink {
+                title: link.title.trim().to_string(),
+                url: link.url.trim().to_string(),
+                openable: is_web_link(&link.url),
+            })
+            .collect();
+
+        Self {
+            name: user.name.trim().to_string(),
+            status: non_empty(user.status.as_deref()),
+            bio: non_empty(user.bio.as_deref()),
+            avatar,
+            links,
+        }
+    }
+
+    /// Placeholder shown once the avatar image fails to load.
+    pub fn fallback_avatar(&self) -> ProfileAvatar {
+        let source = match &self.avatar {
+            ProfileAvatar::Image(src) => Some(src.clone()),
+            ProfileAvatar::Placeholder { source, .. } => source.clone(),
+        };
+        ProfileAvatar::Placeholder {
+            initial: initial(&self.name),
+            source,
+        }
+    }
+}
+
+/// One non-empty line of the Links editor, checked against the
+/// `Title | URL` grammar.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LinkLineCheck {
+    /// 1-based line number in the editor.
+    pub line: usize,
+    pub result: Result<ProfileLink, String>,
+}
+
+/// Check every non-empty line of the Links editor, so mistakes can be shown
+/// while typing. Saving uses the same checks.
+pub fn check_link_lines(input: &str) -> Vec<LinkLineCheck> {
+    input
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| LinkLineCheck {
+            line: index + 1,
+            result: check_link_line(index + 1, line),
+        })
+        .collect()
+}
+
+fn check_link_line(number: usize, line: &str) -> Result<ProfileLink, String> {
+    let mut parts = line.splitn(2, '|').map(str::trim);
+    let title = parts
+        .next()
+        .filter(|part| !part.is_empty())
+        .ok_or_else(|| format!("Link {number} is missing a title"))?;
+    let url = parts
+        .next()
+        .filter(|part| !part.is_empty())
+        .ok_or_else(|| format!("Link {number} is missing a URL"))?;
+    Url::parse(url).map_err(|err| format!("Link {number}: '{url}' is not a valid URL ({err})"))?;
+    Ok(ProfileLink {
+        title: title.to_string(),
+        url: url.to_string(),
+        openable: is_web_link(url),
+    })
+}
+
+fn non_empty(value: Option<&str>) -> Option<String> {
+    value
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(str::to_string)
+}
+
+fn is_loadable_image(src: &str) -> bool {
+    let lower = src.to_ascii_lowercase();
+    lower.starts_with("https://")
+        || lower.starts_with("http://")
+        || lower.starts_with("data:image/")
+}
+
+fn initial(name: &str) -> String {
+    name.trim()
+        .chars()
+        .next()
+        .map(|first| first.to_uppercase().collect())
+        .unwrap_or_else(|| String::from("?"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pubky_app_specs::PubkyAppUserLink;
+
+    fn user(image: Option<&str>) -> PubkyAppUser {
+        PubkyAppUser::new(
+            String::from(" alice "),
+            Some(String::from("Builds things")),
+            image.map(str::to_string),
+            Some(vec![
+                PubkyAppUserLink {
+                    title: String::from("Site"),
+                    url: String::from("https://alice.example"),
+                },
+                PubkyAppUserLink {
+                    title: String::from("Feed"),
+                    url: String::from("pubky://alice/pub/pubky.app/"),
+                },
+            ]),
+            Some(String::from("  ")),
+        )
+    }
+
+    #[test]
+    fn web_avatars_load_and_others_fall_back_to_initials() {
+        let view = ProfileView::from_user(&user(Some("https://alice.example/me.png")));
+        assert_eq!(view.name, "alice");
+        assert_eq!(view.status, None);
+        assert_eq!(
+            view.avatar,
+            ProfileAvatar::Image(String::from("https://alice.example/me.png"))
+        );
+        assert_eq!(
+            view.fallback_avatar(),
+            ProfileAvatar::Placeholder {
+                initial: String::from("A"),
+                source: Some(String::from("https://alice.example/me.png")),
+            }
+        );
+
+        let stored = ProfileView::from_user(&user(Some("pubky://alice/pub/pubky.app/files/1")));
+        assert!(matches!(
+            stored.avatar,
+            ProfileAvatar::Placeholder {
+                source: Some(_),
+                ..
+            }
+        ));
+        let missing = ProfileView::from_user(&user(None));
+        assert!(matches!(
+            missing.avatar,
+            ProfileAvatar::Placeholder { source: None, .. }
+        ));
+    }
+
+    #[test]
+    fn link_lines_report_each_mistake() {
+        let checks = check_link_lines(
+            "Site | https://alice.example\n\n | https://x.example\nBlog\nFeed | not a url\n",
+        );
+        let lines: Vec<usize> = checks.iter().map(|check| check.line).collect();
+        assert_eq!(lines, vec![1, 3, 4, 5]);
+        assert_eq!(
+            checks[0].result,
+            Ok(ProfileLink {
+                title: String::from("Site"),
+                url: String::from("https://alice.example"),
+                openable: true,
+            })
+        );
+        assert_eq!(
+            checks[1].result,
+            Err(String::from("Link 3 is missing a title"))
+        );
+        assert_eq!(
+            checks[2].result,
+            Err(String::from("Link 4 is missing a URL"))
+        );
+        assert!(
+            checks[3]
+                .result
+                .as_ref()
+                .unwrap_err()
+                .starts_with("Link 5: 'not a url' is not a valid URL")
+        );
+    }
+
+    #[test]
+    fn only_web_links_are_openable() {
+        let view = ProfileView::from_user(&user(None));
+        let openable: Vec<bool> = view.links.iter().map(|link| link.openable).collect();
+        assert_eq!(openable, vec![true, false]);
+    }
+}
@@ -1,12 +1,27 @@
 use std::sync::Arc;
+use std::time::Duration;
 
 use anyhow::{Result, anyhow};
 use dioxus::prelude::{ReadableExt, Signal, WritableExt};
-use pubky::Pubky;
+use pubky::{Pubky, PubkyHttpClient};
 
 use crate::app::NetworkMode;
 use crate::utils::logging::ActivityLog;
 
+/// Automatic rebuilds after a failed facade build, when enabled in Settings.
+pub const MAX_FACADE_RETRIES: u32 = 3;
+
+/// Wait before the first automatic rebuild; each later one waits twice as long.
+pub const FACADE_RETRY_BASE_DELAY: Duration = Duration::from_secs(2);
+
+/// Delay before automatic retry number `attempt` (counting from 1), or `None`
+/// once the retries are used up.
+pub fn facade_retry_delay(attempt: u32) -> Option<Duration> {
+    (1..=MAX_FACADE_RETRIES)
+        .contains(&attempt)
+        .then(|| FACADE_RETRY_BASE_DELAY * 2u32.pow(attempt - 1))
+}
+
 #[derive(Clone)]
 pub struct PubkyFacadeState {
     pub network: NetworkMode,
@@ -57,11 +72,29 @@ impl PubkyFacadeState {
 #[derive(Clone)]
 pub struct PubkyFacadeHandle {
     state: Signal<PubkyFacadeState>,
+    /// Bumped by every build, so a superseded one (and its pending retries)
+    /// can tell it should stop.
+    build: Signal<u64>,
 }
 
 impl PubkyFacadeHandle {
-    pub fn new(state: Signal<PubkyFacadeState>) -> Self {
-        Self { state }
+    pub fn new(state: Signal<PubkyFacadeState>, build: Signal<u64>) -> Self {
+        Self { state, build }
+    }
+
+    /// Mark `network` as loading and start a new build, superseding any
+    /// earlier one.
+    pub fn begin_build(&self, network: NetworkMode) -> u64 {
+        let mut build = self.build;
+        let next = *build.read() + 1;
+        build.set(next);
+        self.set(PubkyFacadeState::loading(network));
+        next
+    }
+
+    /// Whether `build` is still the latest one started.
+    pub fn is_current_build(&self, build: u64) -> bool {
+        *self.build.read() == build
     }
 
     pub fn snapshot(&self) -> PubkyFacadeState {
@@ -120,13 +153,66 @@ impl std::fmt::Display for PubkyFacadeReadiness {
 
 impl std::error::Error for PubkyFacadeReadiness {}
 
+/// Which network a facade talks to. [`NetworkMode`] covers what the app
+/// offers; tests can point the testnet at another host.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FacadeConfig {
+    Mainnet,
+    /// A static testnet whose DHT bootstrap node and pkarr relay listen on
+    /// `host` at the pubky-testnet ports.
+    Testnet {
+        host: String,
+    },
+}
+
+impl From<NetworkMode> for FacadeConfig {
+    fn from(mode: NetworkMode) -> Self {
+        match mode {
+            NetworkMode::Mainnet => FacadeConfig::Mainnet,
+            NetworkMode::Testnet => FacadeConfig::Testnet {
+                host: String::from("localhost"),
+            },
+        }
+    }
+}
+
 pub async fn build_pubky_facade(mode: NetworkMode) -> Result<Arc<Pubky>> {
-    let facade = tokio::task::spawn_blocking(move || match mode {
-        NetworkMode::Mainnet => Pubky::new(),
-        NetworkMode::Testnet => Pubky::testnet(),
+    build_pubky_facade_with(mode.into()).await
+}
+
+/// Build a facade without any UI state, so integration tests can drive real
+/// storage calls against a local testnet.
+pub async fn build_pubky_facade_with(config: FacadeConfig) -> Result<Arc<Pubky>> {
+    let facade = tokio::task::spawn_blocking(move || -> Result<Pubky> {
+        match config {
+            FacadeConfig::Mainnet => Ok(Pubky::new()?),
+            FacadeConfig::Testnet { host } => {
+                let client = PubkyHttpClient::builder()
+                    .testnet_with_host(&host)
+                    .build()?;
+                Ok(Pubky::with_client(client))
+            }
+        }
     })
     .await
     .map_err(|err| anyhow!("Failed to join Pubky build task: {err}"))??;
 
     Ok(Arc::new(facade))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn retry_delays_double_until_the_retries_run_out() {
+        assert_eq!(facade_retry_delay(0), None);
+        assert_eq!(facade_retry_delay(1), Some(FACADE_RETRY_BASE_DELAY));
+        assert_eq!(facade_retry_delay(2), Some(FACADE_RETRY_BASE_DELAY * 2));
+        assert_eq!(
+            facade_retry_delay(MAX_FACADE_RETRIES),
+            Some(FACADE_RETRY_BASE_DELAY * 2u32.pow(MAX_FACADE_RETRIES - 1))
+        );
+        assert_eq!(facade_retry_delay(MAX_FACADE_RETRIES + 1), None);
+    }
+}
@@ -1,6 +1,105 @@
-use anyhow::{Context, Result};
+use anyhow::{Context, Result, bail};
 use base64::{Engine as _, engine::general_purpose::STANDARD};
 use qrcode::{QrCode, render::svg};
+use std::fs;
+use std::path::PathBuf;
+
+/// Longest value the "Show QR" buttons will encode. Denser codes exist, but
+/// phone cameras struggle to scan them off a screen.
+pub const MAX_QR_INPUT_BYTES: usize = 1024;
+
+/// Like [`generate_qr_data_url`], but refuses values longer than
+/// [`MAX_QR_INPUT_BYTES`] with a message suitable for the UI.
+pub fn generate_capped_qr_data_url(content: &str) -> Result<String> {
+    if content.len() > MAX_QR_INPUT_BYTES {
+        bail!(
+            "This value is {} bytes, too large for a scannable QR code (limit {MAX_QR_INPUT_BYTES} bytes). Copy it instead.",
+            content.len()
+        );
+    }
+    generate_qr_data_url(content)
+}
+
+/// Pixel size of one QR module for the sizes offered in the Auth tab.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum QrSize {
+    Small,
+    #[default]
+    Medium,
+    Large,
+}
+
+impl QrSize {
+    pub const ALL: [QrSize; 3] = [QrSize::Small, QrSize::Medium, QrSize::Large];
+
+    pub fn module_scale(self) -> u32 {
+        match self {
+            QrSize::Small => 4,
+            QrSize::Medium => 8,
+            QrSize::Large => 16,
+        }
+    }
+
+    pub fn key(self) -> &'static str {
+        match self {
+            QrSize::Small => "small",
+            QrSize::Medium => "medium",
+            QrSize::Large => "large",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            QrSize::Small => "Small",
+            QrSize::Medium => "Medium",
+            QrSize::Large => "Large",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|size| size.key() == key)
+    }
+}
+
+/// SVG markup for `content` with every module `size.module_scale()` pixels
+/// wide, quiet zone included.
+pub fn generate_qr_svg(content: &str, size: QrSize) -> Result<String> {
+    let code = QrCode::new(content.as_bytes()).context("failed to encode QR code")?;
+    let scale = size.module_scale();
+    Ok(code
+        .render::<svg::Color>()
+        .module_dimensions(scale, scale)
+        .dark_color(svg::Color("#0f172a"))
+        .light_color(svg::Color("#f8fafc"))
+        .build())
+}
+
+pub fn generate_sized_qr_data_url(content: &str, size: QrSize) -> Result<String> {
+    let svg = generate_qr_svg(content, size)?;
+    Ok(format!(
+        "data:image/svg+xml;base64,{}",
+        STANDARD.encode(svg.as_bytes())
+    ))
+}
+
+/// Write the QR code as an SVG file, adding a `.svg` extension when none is
+/// given. Vector output stays sharp at any display density.
+pub fn save_qr_svg(content: &str, size: QrSize, path: &str) -> Result<PathBuf> {
+    let mut target = PathBuf::from(path.trim());
+    if target.extension().is_none() {
+        target.set_extension("svg");
+    }
+    if let Some(parent) = target
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+    {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("failed to create directory {}", parent.display()))?;
+    }
+    fs::write(&target, generate_qr_svg(content, size)?)
+        .with_context(|| format!("failed to write {}", target.display()))?;
+    Ok(target)
+}
 
 pub fn generate_qr_data_url(content: &str) -> Result<String> {
     let code = QrCode::new(content.as_bytes()).context("failed to encode QR code")?;
@@ -18,6 +117,7 @@ pub fn generate_qr_data_url(content: &str) -> Result<String> {
 mod tests {
     use super::*;
     use anyhow::Result;
+    use tempfile::TempDir;
 
     #[test]
     fn generate_qr_data_url_encodes_svg() -> Result<()> {
@@ -31,4 +131,47 @@ mod tests {
         assert!(svg.contains("#f8fafc"), "light color should be embedded");
         Ok(())
     }
+
+    #[test]
+    fn larger_sizes_produce_larger_images() -> Result<()> {
+        fn svg_width(svg: &str) -> u32 {
+            let rest = svg.split_once("width=\"").expect("svg width").1;
+            rest.split('"')
+                .next()
+                .unwrap()
+                .parse()
+                .expect("numeric width")
+        }
+
+        let small = generate_qr_svg("pubkyauth://example", QrSize::Small)?;
+        let large = generate_qr_svg("pubkyauth://example", QrSize::Large)?;
+        assert_eq!(svg_width(&large), svg_width(&small) * 4);
+        assert_eq!(QrSize::from_key(QrSize::Large.key()), Some(QrSize::Large));
+
+        let data_url = generate_sized_qr_data_url("pubkyauth://example", QrSize::Medium)?;
+        assert!(data_url.starts_with("data:image/svg+xml;base64,"));
+        Ok(())
+    }
+
+    #[test]
+    fn qr_svg_is_saved_with_extension() -> Result<()> {
+        let dir = TempDir::new()?;
+        let target = dir.path().join("codes/auth");
+        let saved = save_qr_svg(
+            "pubkyauth://example",
+            QrSize::Medium,
+            &target.to_string_lossy(),
+        )?;
+        assert_eq!(saved.extension().and_then(|ext| ext.to_str()), Some("svg"));
+        assert!(fs::read_to_string(&saved)?.contains("<svg"));
+        Ok(())
+    }
+
+    #[test]
+    fn capped_qr_rejects_oversized_values() {
+        assert!(generate_capped_qr_data_url("pubky://example/pub/app/file.txt").is_ok());
+        let oversized = "a".repeat(MAX_QR_INPUT_BYTES + 1);
+        let err = generate_capped_qr_data_url(&oversized).unwrap_err();
+        assert!(err.to_string().contains("too large"));
+    }
 }
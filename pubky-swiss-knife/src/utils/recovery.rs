@@ -1,49 +1,220 @@
 use anyhow::{Context, Result, anyhow};
 use base64::{Engine as _, engine::general_purpose::STANDARD};
-use pubky::{Keypair, recovery_file};
+use bip39::{Language, Mnemonic};
+use pubky::{Keypair, PublicKey, recovery_file};
+use std::fmt;
 use std::fs;
+use std::io::{self, ErrorKind};
 use std::path::{Path, PathBuf};
 
-pub fn decode_secret_key(value: &str) -> Result<Keypair> {
-    let bytes = STANDARD
-        .decode(value.trim())
-        .context("secret key must be valid base64")?;
+/// Why a secret key or recovery file could not be read or written.
+#[derive(Debug)]
+pub enum RecoveryError {
+    /// The file decoded as a recovery file but the passphrase did not open it.
+    WrongPassphrase,
+    NotFound(PathBuf),
+    /// The input is not in the expected shape: a bad path, a malformed secret,
+    /// or a file that is not a Pubky recovery file.
+    InvalidFormat(String),
+    Io {
+        context: String,
+        source: io::Error,
+    },
+}
+
+impl RecoveryError {
+    fn io(context: impl Into<String>, source: io::Error) -> Self {
+        Self::Io {
+            context: context.into(),
+            source,
+        }
+    }
+
+    /// What the user can do about it, when there is something specific.
+    pub fn hint(&self) -> Option<&'static str> {
+        match self {
+            RecoveryError::WrongPassphrase => Some("Re-enter the passphrase and try again."),
+            RecoveryError::NotFound(_) => {
+                Some("Check the path, or leave it empty to pick the file.")
+            }
+            RecoveryError::InvalidFormat(_) | RecoveryError::Io { .. } => None,
+        }
+    }
+}
+
+impl fmt::Display for RecoveryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RecoveryError::WrongPassphrase => {
+                f.write_str("wrong passphrase, or the encrypted key is damaged")
+            }
+            RecoveryError::NotFound(path) => {
+                write!(f, "recovery file {} not found", path.display())
+            }
+            RecoveryError::InvalidFormat(message) => f.write_str(message),
+            RecoveryError::Io { context, source } => write!(f, "{context}: {source}"),
+        }
+    }
+}
+
+impl std::error::Error for RecoveryError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            RecoveryError::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+/// Decode a 32-byte secret written as 64 hex characters or as base64.
+pub fn decode_secret_key(value: &str) -> Result<Keypair, RecoveryError> {
+    let trimmed = value.trim();
+    let bytes = match decode_hex_secret(trimmed) {
+        Some(bytes) => bytes,
+        None => STANDARD.decode(trimmed).map_err(|_| {
+            RecoveryError::InvalidFormat(String::from(
+                "secret key must be 64 hex characters or valid base64",
+            ))
+        })?,
+    };
     let secret: [u8; 32] = bytes
         .try_into()
-        .map_err(|_| anyhow!("secret key must be 32 bytes"))?;
+        .map_err(|_| RecoveryError::InvalidFormat(String::from("secret key must be 32 bytes")))?;
+    Ok(Keypair::from_secret_key(&secret))
+}
+
+/// The signer secret as lowercase hex, as the pkarr CLI and most SDKs expect.
+pub fn secret_key_hex(keypair: &Keypair) -> String {
+    keypair
+        .secret_key()
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+fn decode_hex_secret(value: &str) -> Option<Vec<u8>> {
+    if value.len() != 64 || !value.chars().all(|ch| ch.is_ascii_hexdigit()) {
+        return None;
+    }
+    (0..value.len())
+        .step_by(2)
+        .map(|index| u8::from_str_radix(&value[index..index + 2], 16).ok())
+        .collect()
+}
+
+/// Words in a mnemonic whose entropy is exactly a 32-byte secret.
+pub const MNEMONIC_WORDS: usize = 24;
+
+/// Rebuild a keypair from a 24-word BIP39 phrase whose entropy is the secret.
+/// Case and extra whitespace are ignored; the word count and checksum are
+/// checked.
+pub fn keypair_from_mnemonic(phrase: &str) -> Result<Keypair> {
+    let words: Vec<String> = phrase
+        .split_whitespace()
+        .map(|word| word.to_lowercase())
+        .collect();
+    if words.len() != MNEMONIC_WORDS {
+        return Err(anyhow!(
+            "mnemonic must have {MNEMONIC_WORDS} words, got {}",
+            words.len()
+        ));
+    }
+    let mnemonic = Mnemonic::parse_in_normalized(Language::English, &words.join(" ")).map_err(
+        |err| match err {
+            bip39::Error::UnknownWord(index) => anyhow!(
+                "word {} ('{}') is not in the BIP39 English word list",
+                index + 1,
+                words[index]
+            ),
+            bip39::Error::InvalidChecksum => {
+                anyhow!("mnemonic checksum does not match; check the words and their order")
+            }
+            other => anyhow!("invalid mnemonic: {other}"),
+        },
+    )?;
+    let secret: [u8; 32] = mnemonic
+        .to_entropy()
+        .try_into()
+        .map_err(|_| anyhow!("mnemonic must encode 32 bytes"))?;
     Ok(Keypair::from_secret_key(&secret))
 }
 
-pub fn load_keypair_from_recovery(path: impl AsRef<Path>, passphrase: &str) -> Result<Keypair> {
-    let bytes = fs::read(path.as_ref())
-        .with_context(|| format!("failed to read {}", path.as_ref().display()))?;
-    let kp = recovery_file::decrypt_recovery_file(&bytes, passphrase)?;
-    Ok(kp)
+/// The 24-word BIP39 phrase for the keypair's secret.
+pub fn mnemonic_from_keypair(keypair: &Keypair) -> String {
+    Mnemonic::from_entropy_in(Language::English, &keypair.secret_key())
+        .expect("32 bytes is a valid BIP39 entropy length")
+        .to_string()
+}
+
+/// First line of every recovery file written by `pubky-common`.
+const RECOVERY_SPEC_LINE: &[u8] = b"pubky.org/recovery\n";
+
+pub fn load_keypair_from_recovery(
+    path: impl AsRef<Path>,
+    passphrase: &str,
+) -> Result<Keypair, RecoveryError> {
+    let path = path.as_ref();
+    let bytes = fs::read(path).map_err(|err| match err.kind() {
+        ErrorKind::NotFound => RecoveryError::NotFound(path.to_path_buf()),
+        _ => RecoveryError::io(format!("failed to read {}", path.display()), err),
+    })?;
+    if !bytes.starts_with(RECOVERY_SPEC_LINE) || bytes.len() == RECOVERY_SPEC_LINE.len() {
+        return Err(RecoveryError::InvalidFormat(format!(
+            "{} is not a Pubky recovery file or is corrupt",
+            path.display()
+        )));
+    }
+    recovery_file::decrypt_recovery_file(&bytes, passphrase)
+        .map_err(|_| RecoveryError::WrongPassphrase)
+}
+
+/// Decrypt a recovery file only to report its public key, so a backup can be
+/// checked without replacing the active signer.
+pub fn verify_recovery_file(
+    path: impl AsRef<Path>,
+    passphrase: &str,
+) -> Result<PublicKey, RecoveryError> {
+    Ok(load_keypair_from_recovery(path, passphrase)?.public_key())
 }
 
 pub fn save_keypair_to_recovery_file(
     keypair: &Keypair,
     path: &str,
     passphrase: &str,
-) -> Result<PathBuf> {
-    let normalized = normalize_pkarr_path(path)?;
+) -> Result<PathBuf, RecoveryError> {
+    let normalized = recovery_path(path)?;
     if let Some(parent) = normalized.parent()
         && !parent.as_os_str().is_empty()
     {
-        fs::create_dir_all(parent)
-            .with_context(|| format!("failed to create directory {}", parent.display()))?;
+        fs::create_dir_all(parent).map_err(|err| {
+            RecoveryError::io(
+                format!("failed to create directory {}", parent.display()),
+                err,
+            )
+        })?;
     }
     let bytes = recovery_file::create_recovery_file(keypair, passphrase);
-    fs::write(&normalized, bytes)
-        .with_context(|| format!("failed to write {}", normalized.display()))?;
+    fs::write(&normalized, bytes).map_err(|err| {
+        RecoveryError::io(format!("failed to write {}", normalized.display()), err)
+    })?;
     Ok(normalized)
 }
 
+/// [`normalize_pkarr_path`], with a bad path reported as
+/// [`RecoveryError::InvalidFormat`].
+pub fn recovery_path(input: &str) -> Result<PathBuf, RecoveryError> {
+    normalize_pkarr_path(input).map_err(|err| RecoveryError::InvalidFormat(format!("{err:#}")))
+}
+
+/// Turn a typed path into a recovery file path: expand `$VAR` and `${VAR}`,
+/// then a leading `~`, and add the `.pkarr` extension when it is missing.
 pub fn normalize_pkarr_path(input: &str) -> Result<PathBuf> {
     let trimmed = input.trim();
     if trimmed.is_empty() {
         return Err(anyhow!("path cannot be empty"));
     }
+    let with_vars = expand_env_vars(trimmed)?;
+    let trimmed = with_vars.as_str();
 
     let mut expanded = if let Some(stripped) = trimmed.strip_prefix('~') {
         let home = resolve_home_dir().context("unable to resolve home directory")?;
@@ -70,6 +241,56 @@ pub fn normalize_pkarr_path(input: &str) -> Result<PathBuf> {
     Ok(expanded)
 }
 
+/// The first dropped path with a `.pkarr` extension, in drop order.
+pub fn first_recovery_file(paths: impl IntoIterator<Item = PathBuf>) -> Option<PathBuf> {
+    paths.into_iter().find(|path| {
+        path.extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("pkarr"))
+    })
+}
+
+/// Replace `$NAME` and `${NAME}` with the variable's value. As in a shell, a
+/// `$NAME` whose variable is not set stays literal text, so paths such as
+/// `C:\$Recycle.Bin` or `backup$old.pkarr` keep working; the explicit
+/// `${NAME}` form is an error when unset, so a typo there does not silently
+/// point somewhere else.
+fn expand_env_vars(input: &str) -> Result<String> {
+    let mut output = String::with_capacity(input.len());
+    let mut rest = input;
+    while let Some(start) = rest.find('$') {
+        output.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        let (name, consumed, braced) = if let Some(braced) = after.strip_prefix('{') {
+            let end = braced
+                .find('}')
+                .ok_or_else(|| anyhow!("unclosed ${{ in path"))?;
+            (&braced[..end], end + 2, true)
+        } else {
+            let end = after
+                .find(|ch: char| !(ch.is_ascii_alphanumeric() || ch == '_'))
+                .unwrap_or(after.len());
+            (&after[..end], end, false)
+        };
+        if name.is_empty() {
+            output.push('$');
+            rest = after;
+            continue;
+        }
+        match std::env::var_os(name) {
+            Some(value) => output.push_str(&value.to_string_lossy()),
+            None if braced => return Err(anyhow!("environment variable {name} is not set")),
+            None => {
+                output.push('$');
+                output.push_str(name);
+            }
+        }
+        rest = &after[consumed..];
+    }
+    output.push_str(rest);
+    Ok(output)
+}
+
 fn resolve_home_dir() -> Option<PathBuf> {
     std::env::var_os("HOME")
         .map(PathBuf::from)
@@ -134,6 +355,48 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn decode_secret_key_accepts_hex() -> Result<()> {
+        let keypair = Keypair::random();
+        let hex = secret_key_hex(&keypair);
+        assert_eq!(hex.len(), 64);
+        assert_eq!(hex, hex.to_lowercase());
+
+        let decoded = decode_secret_key(&format!("  {}\n", hex.to_uppercase()))?;
+        assert_eq!(decoded.public_key(), keypair.public_key());
+        Ok(())
+    }
+
+    #[test]
+    fn mnemonic_roundtrip_matches_known_phrase() -> Result<()> {
+        let phrase = format!("{}art", "abandon ".repeat(23));
+        let keypair = Keypair::from_secret_key(&[0u8; 32]);
+        assert_eq!(mnemonic_from_keypair(&keypair), phrase);
+
+        let restored = keypair_from_mnemonic(&format!("  {}\n", phrase.to_uppercase()))?;
+        assert_eq!(restored.public_key(), keypair.public_key());
+
+        let random = Keypair::random();
+        let restored = keypair_from_mnemonic(&mnemonic_from_keypair(&random))?;
+        assert_eq!(restored.secret_key(), random.secret_key());
+        Ok(())
+    }
+
+    #[test]
+    fn mnemonic_rejects_bad_count_words_and_checksum() {
+        let twelve = format!("{}about", "abandon ".repeat(11));
+        let err = keypair_from_mnemonic(&twelve).unwrap_err();
+        assert!(err.to_string().contains("24 words, got 12"));
+
+        let unknown = format!("{}pubky", "abandon ".repeat(23));
+        let err = keypair_from_mnemonic(&unknown).unwrap_err();
+        assert!(err.to_string().contains("word 24 ('pubky')"));
+
+        let bad_checksum = "abandon ".repeat(24);
+        let err = keypair_from_mnemonic(&bad_checksum).unwrap_err();
+        assert!(err.to_string().contains("checksum"));
+    }
+
     #[test]
     fn decode_secret_key_rejects_invalid_base64() {
         let err = decode_secret_key("not-base64").unwrap_err();
@@ -166,6 +429,60 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn normalize_pkarr_path_keeps_absolute_paths() -> Result<()> {
+        assert_eq!(
+            normalize_pkarr_path("/tmp/keys/backup.pkarr")?,
+            PathBuf::from("/tmp/keys/backup.pkarr")
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn normalize_pkarr_path_expands_environment_variables() -> Result<()> {
+        let dir = TempDir::new()?;
+        let _guard = EnvGuard::set_path("SWISS_KNIFE_TEST_KEYS", dir.path());
+
+        assert_eq!(
+            normalize_pkarr_path("$SWISS_KNIFE_TEST_KEYS/backup")?,
+            dir.path().join("backup.pkarr")
+        );
+        assert_eq!(
+            normalize_pkarr_path("${SWISS_KNIFE_TEST_KEYS}/${SWISS_KNIFE_TEST_UNSET}/backup.pkarr")
+                .unwrap_err()
+                .to_string(),
+            "environment variable SWISS_KNIFE_TEST_UNSET is not set"
+        );
+        assert_eq!(
+            normalize_pkarr_path(
+                "${SWISS_KNIFE_TEST_KEYS}/$SWISS_KNIFE_TEST_UNSET/backup$old.pkarr"
+            )?,
+            dir.path()
+                .join("$SWISS_KNIFE_TEST_UNSET")
+                .join("backup$old.pkarr")
+        );
+        assert_eq!(
+            normalize_pkarr_path("${SWISS_KNIFE_TEST_KEYS}/cost$/backup.pkarr")?,
+            dir.path().join("cost$").join("backup.pkarr")
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn dropped_files_pick_the_first_recovery_file() {
+        let dropped = vec![
+            PathBuf::from("/tmp/notes.txt"),
+            PathBuf::from("/tmp/keys/alice.PKARR"),
+            PathBuf::from("/tmp/keys/bob.pkarr"),
+        ];
+        assert_eq!(
+            first_recovery_file(dropped.clone()),
+            Some(PathBuf::from("/tmp/keys/alice.PKARR"))
+        );
+        assert_eq!(first_recovery_file(dropped.into_iter().take(1)), None);
+        assert_eq!(first_recovery_file(Vec::new()), None);
+    }
+
     #[test]
     fn normalize_pkarr_path_rejects_empty_input() {
         let err = normalize_pkarr_path("").unwrap_err();
@@ -189,4 +506,67 @@ mod tests {
         assert_eq!(restored.secret_key(), keypair.secret_key());
         Ok(())
     }
+
+    #[test]
+    fn recovery_errors_have_a_variant_per_failure() -> Result<()> {
+        let keypair = Keypair::from_secret_key(&[3u8; 32]);
+        let dir = TempDir::new()?;
+        let saved = save_keypair_to_recovery_file(
+            &keypair,
+            &dir.path().join("key").to_string_lossy(),
+            "pw",
+        )?;
+
+        let wrong = load_keypair_from_recovery(&saved, "nope").unwrap_err();
+        assert!(matches!(wrong, RecoveryError::WrongPassphrase));
+        assert!(wrong.hint().is_some());
+
+        let missing = dir.path().join("missing.pkarr");
+        let err = load_keypair_from_recovery(&missing, "pw").unwrap_err();
+        assert!(matches!(&err, RecoveryError::NotFound(path) if *path == missing));
+
+        let err = load_keypair_from_recovery(dir.path(), "pw").unwrap_err();
+        assert!(matches!(err, RecoveryError::Io { .. }));
+        assert!(std::error::Error::source(&err).is_some());
+
+        assert!(matches!(
+            decode_secret_key("abcd"),
+            Err(RecoveryError::InvalidFormat(_))
+        ));
+        assert!(matches!(
+            save_keypair_to_recovery_file(&keypair, "  ", "pw"),
+            Err(RecoveryError::InvalidFormat(_))
+        ));
+
+        let blocker = dir.path().join("file");
+        fs::write(&blocker, b"not a directory")?;
+        let err =
+            save_keypair_to_recovery_file(&keypair, &blocker.join("key").to_string_lossy(), "pw")
+                .unwrap_err();
+        assert!(matches!(err, RecoveryError::Io { .. }));
+        assert!(err.to_string().starts_with("failed to create directory"));
+        Ok(())
+    }
+
+    #[test]
+    fn verify_recovery_file_distinguishes_failures() -> Result<()> {
+        let keypair = Keypair::from_secret_key(&[9u8; 32]);
+        let dir = TempDir::new()?;
+        let target = dir.path().join("backup");
+        let saved = save_keypair_to_recovery_file(&keypair, &target.to_string_lossy(), "right")?;
+
+        assert_eq!(verify_recovery_file(&saved, "right")?, keypair.public_key());
+
+        let wrong = verify_recovery_file(&saved, "wrong").unwrap_err();
+        assert!(wrong.to_string().contains("wrong passphrase"));
+
+        let missing = verify_recovery_file(dir.path().join("nope.pkarr"), "right").unwrap_err();
+        assert!(missing.to_string().contains("not found"));
+
+        let corrupt = dir.path().join("corrupt.pkarr");
+        fs::write(&corrupt, b"definitely not a bundle")?;
+        let corrupt = verify_recovery_file(&corrupt, "right").unwrap_err();
+        assert!(corrupt.to_string().contains("corrupt"));
+        Ok(())
+    }
 }
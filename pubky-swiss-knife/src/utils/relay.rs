@@ -0,0 +1,121 @@
+use anyhow::{Context, Result, anyhow};
+use std::time::Duration;
+use url::Url;
+
+/// Path segment the Pubky HTTP relays expose their auth channels under.
+const RELAY_LINK_SEGMENT: &str = "link";
+
+/// How long "Await approval" waits for the remote signer by default.
+pub const DEFAULT_APPROVAL_TIMEOUT_SECS: u64 = 120;
+
+/// Parse the approval timeout field, in whole seconds (at least one).
+pub fn parse_approval_timeout(input: &str) -> Result<Duration> {
+    let secs: u64 = input
+        .trim()
+        .parse()
+        .map_err(|_| anyhow!("approval timeout must be a whole number of seconds"))?;
+    if secs == 0 {
+        return Err(anyhow!("approval timeout must be at least one second"));
+    }
+    Ok(Duration::from_secs(secs))
+}
+
+/// Normalize a user-provided relay override into the URL the auth flow expects.
+///
+/// Missing schemes default to `https://`, only `http`/`https` are accepted, and the
+/// `/link/` channel path is appended (with a trailing slash) when it is absent.
+pub fn normalize_relay_url(input: &str) -> Result<Url> {
+    let trimmed = input.trim();
+    if trimmed.is_empty() {
+        return Err(anyhow!("relay URL cannot be empty"));
+    }
+
+    let candidate = if trimmed.contains("://") {
+        trimmed.to_string()
+    } else {
+        format!("https://{trimmed}")
+    };
+    let mut url = Url::parse(&candidate).context("relay URL must be valid")?;
+
+    match url.scheme() {
+        "http" | "https" => {}
+        other => return Err(anyhow!("relay URL must use http or https, not {other}")),
+    }
+    if url.host_str().is_none_or(str::is_empty) {
+        return Err(anyhow!("relay URL must include a host"));
+    }
+    if url.query().is_some() || url.fragment().is_some() {
+        return Err(anyhow!(
+            "relay URL must not contain a query string or fragment"
+        ));
+    }
+
+    let mut segments: Vec<String> = url
+        .path_segments()
+        .map(|parts| {
+            parts
+                .filter(|part| !part.is_empty())
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default();
+    if segments.last().map(String::as_str) != Some(RELAY_LINK_SEGMENT) {
+        segments.push(RELAY_LINK_SEGMENT.to_string());
+    }
+    url.set_path(&format!("/{}/", segments.join("/")));
+
+    Ok(url)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn appends_link_path_and_trailing_slash() -> Result<()> {
+        assert_eq!(
+            normalize_relay_url("https://httprelay.pubky.app")?.as_str(),
+            "https://httprelay.pubky.app/link/"
+        );
+        assert_eq!(
+            normalize_relay_url("https://relay.example/link")?.as_str(),
+            "https://relay.example/link/"
+        );
+        assert_eq!(
+            normalize_relay_url("http://localhost:15412/link/")?.as_str(),
+            "http://localhost:15412/link/"
+        );
+        assert_eq!(
+            normalize_relay_url("https://relay.example/base/")?.as_str(),
+            "https://relay.example/base/link/"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn defaults_to_https_when_scheme_is_missing() -> Result<()> {
+        assert_eq!(
+            normalize_relay_url("  relay.example  ")?.as_str(),
+            "https://relay.example/link/"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn approval_timeout_is_positive_seconds() -> Result<()> {
+        assert_eq!(parse_approval_timeout(" 90 ")?, Duration::from_secs(90));
+        assert!(parse_approval_timeout("0").is_err());
+        assert!(parse_approval_timeout("2m").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn rejects_invalid_relays() {
+        assert!(normalize_relay_url("").is_err());
+        let scheme = normalize_relay_url("pubkyauth://relay.example").unwrap_err();
+        assert!(scheme.to_string().contains("http or https"));
+        let query = normalize_relay_url("https://relay.example/link/?x=1").unwrap_err();
+        assert!(query.to_string().contains("query"));
+        assert!(normalize_relay_url("https://").is_err());
+    }
+}
@@ -0,0 +1,311 @@
+use anyhow::{Result, anyhow};
+use pubky::PublicKey;
+
+/// Whether the current session can actually be used, beyond merely being present.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SessionHealth {
+    /// No session has been created or loaded.
+    #[default]
+    Missing,
+    /// A session exists and a revalidation probe is in flight.
+    Checking,
+    /// The homeserver confirmed the session on the last probe.
+    Valid,
+    /// The homeserver no longer recognises the session token.
+    Expired,
+    /// The probe failed (e.g. network trouble), so validity is unknown.
+    Unverified,
+}
+
+impl SessionHealth {
+    /// Interpret the outcome of `PubkySession::revalidate`.
+    pub fn from_probe<T, E>(result: &Result<Option<T>, E>) -> Self {
+        match result {
+            Ok(Some(_)) => SessionHealth::Valid,
+            Ok(None) => SessionHealth::Expired,
+            Err(_) => SessionHealth::Unverified,
+        }
+    }
+
+    /// Explanation shown where session-dependent tools are locked, or `None` when
+    /// they can be used. Presence is checked separately so a freshly created
+    /// session is usable before its first probe completes.
+    pub fn locked_reason(self, has_session: bool) -> Option<&'static str> {
+        if !has_session {
+            return Some("Load or create a session in the Sessions tab to use this tool.");
+        }
+        match self {
+            SessionHealth::Expired => Some(
+                "The current session has expired or was revoked. Sign in again from the Sessions tab to refresh it.",
+            ),
+            _ => None,
+        }
+    }
+}
+
+/// Seconds left below which the expiry countdown is highlighted.
+pub const EXPIRY_WARNING_SECS: u64 = 60;
+
+/// How long the loaded session has left, for the Sessions tab countdown.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SessionExpiry {
+    /// The homeserver did not report an expiry.
+    Unknown,
+    Remaining(u64),
+    Expired,
+}
+
+impl SessionExpiry {
+    /// Compare an expiry and the current time, both in Unix seconds.
+    pub fn at(expires_at: Option<u64>, now: u64) -> Self {
+        match expires_at {
+            None => SessionExpiry::Unknown,
+            Some(expires_at) if expires_at <= now => SessionExpiry::Expired,
+            Some(expires_at) => SessionExpiry::Remaining(expires_at - now),
+        }
+    }
+
+    pub fn is_urgent(self) -> bool {
+        match self {
+            SessionExpiry::Remaining(secs) => secs <= EXPIRY_WARNING_SECS,
+            SessionExpiry::Expired => true,
+            SessionExpiry::Unknown => false,
+        }
+    }
+
+    pub fn label(self) -> String {
+        match self {
+            SessionExpiry::Unknown => String::from("Expires in: unknown"),
+            SessionExpiry::Expired => String::from("Expired"),
+            SessionExpiry::Remaining(secs) => {
+                let (hours, minutes, seconds) = (secs / 3600, secs / 60 % 60, secs % 60);
+                if hours > 0 {
+                    format!("Expires in {hours}h {minutes:02}m {seconds:02}s")
+                } else {
+                    format!("Expires in {minutes}m {seconds:02}s")
+                }
+            }
+        }
+    }
+}
+
+/// Expiry in Unix seconds from the pretty-printed session details, when the
+/// homeserver includes a field such as `expires_at`. Millisecond and
+/// microsecond values are scaled down. The SDK's `SessionInfo` has no typed
+/// expiry today, so this only finds one in details that carry it.
+pub fn expiry_from_details(details: &str) -> Option<u64> {
+    let mut lines = details.lines();
+    let value = loop {
+        let (key, value) = lines.next()?.split_once(':').unwrap_or_default();
+        if key.to_ascii_lowercase().contains("expir") {
+            break value.trim();
+        }
+    };
+    // `{:#?}` puts the value of `Some(..)` on its own line.
+    let value = if value == "Some(" {
+        lines.next()?
+    } else {
+        value
+    };
+    let digits: String = value
+        .chars()
+        .skip_while(|ch| !ch.is_ascii_digit())
+        .take_while(|ch| ch.is_ascii_digit())
+        .collect();
+    let value: u64 = digits.parse().ok()?;
+    Some(match value {
+        v if v >= 1_000_000_000_000_000 => v / 1_000_000,
+        v if v >= 1_000_000_000_000 => v / 1_000,
+        v => v,
+    })
+}
+
+/// Stages of a signup, shown as a checklist in the Sessions tab.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SignupStep {
+    Resolving,
+    Connecting,
+    SigningUp,
+}
+
+impl SignupStep {
+    pub const ALL: [Self; 3] = [Self::Resolving, Self::Connecting, Self::SigningUp];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            SignupStep::Resolving => "Resolving homeserver",
+            SignupStep::Connecting => "Connecting",
+            SignupStep::SigningUp => "Signing up",
+        }
+    }
+}
+
+/// Where the most recent signup attempt got to.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum SignupProgress {
+    #[default]
+    Idle,
+    Running(SignupStep),
+    Succeeded,
+    Failed {
+        step: SignupStep,
+        message: String,
+    },
+}
+
+impl SignupProgress {
+    /// CSS state of `step` in the checklist: `pending`, `active`, `done`, or `failed`.
+    pub fn step_state(&self, step: SignupStep) -> &'static str {
+        let position = |step: SignupStep| SignupStep::ALL.iter().position(|s| *s == step);
+        let (current, failed) = match self {
+            SignupProgress::Idle => return "pending",
+            SignupProgress::Succeeded => return "done",
+            SignupProgress::Running(current) => (*current, false),
+            SignupProgress::Failed { step, .. } => (*step, true),
+        };
+        match position(step).cmp(&position(current)) {
+            std::cmp::Ordering::Less => "done",
+            std::cmp::Ordering::Equal if failed => "failed",
+            std::cmp::Ordering::Equal => "active",
+            std::cmp::Ordering::Greater => "pending",
+        }
+    }
+}
+
+/// Prefix of the one-line invites created by the portable homeserver's
+/// admin tools.
+pub const INVITE_SCHEME: &str = "pubky-invite:";
+
+/// Homeserver and signup code carried by an invite.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Invite {
+    pub homeserver: String,
+    pub signup_code: String,
+}
+
+/// Parse `pubky-invite:<homeserver key>?code=<token>`. The code is optional
+/// so open-signup homeservers can share the same format.
+pub fn parse_invite(raw: &str) -> Result<Invite> {
+    let rest = raw
+        .trim()
+        .strip_prefix(INVITE_SCHEME)
+        .ok_or_else(|| anyhow!("invites start with {INVITE_SCHEME}"))?;
+    let (homeserver, query) = rest.split_once('?').unwrap_or((rest, ""));
+    let homeserver = homeserver.trim_start_matches("//").trim();
+    PublicKey::try_from(homeserver)
+        .map_err(|err| anyhow!("invalid homeserver key in invite: {err}"))?;
+    let signup_code = query
+        .split('&')
+        .find_map(|pair| pair.strip_prefix("code="))
+        .unwrap_or_default()
+        .trim()
+        .to_string();
+    Ok(Invite {
+        homeserver: homeserver.to_string(),
+        signup_code,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pubky::Keypair;
+
+    #[test]
+    fn probe_results_map_to_health() {
+        assert_eq!(
+            SessionHealth::from_probe::<_, ()>(&Ok(Some(()))),
+            SessionHealth::Valid
+        );
+        assert_eq!(
+            SessionHealth::from_probe::<(), ()>(&Ok(None)),
+            SessionHealth::Expired
+        );
+        assert_eq!(
+            SessionHealth::from_probe::<(), _>(&Err("offline")),
+            SessionHealth::Unverified
+        );
+    }
+
+    #[test]
+    fn only_missing_and_expired_sessions_lock_tools() {
+        assert!(SessionHealth::Missing.locked_reason(false).is_some());
+        assert!(SessionHealth::Valid.locked_reason(false).is_some());
+        assert!(SessionHealth::Expired.locked_reason(true).is_some());
+        assert!(SessionHealth::Missing.locked_reason(true).is_none());
+        assert!(SessionHealth::Checking.locked_reason(true).is_none());
+        assert!(SessionHealth::Valid.locked_reason(true).is_none());
+        assert!(SessionHealth::Unverified.locked_reason(true).is_none());
+    }
+
+    #[test]
+    fn expiry_counts_down_and_turns_urgent() {
+        assert_eq!(SessionExpiry::at(None, 100), SessionExpiry::Unknown);
+        assert_eq!(SessionExpiry::at(Some(100), 100), SessionExpiry::Expired);
+
+        let later = SessionExpiry::at(Some(3_825), 100);
+        assert_eq!(later.label(), "Expires in 1h 02m 05s");
+        assert!(!later.is_urgent());
+
+        let soon = SessionExpiry::at(Some(145), 100);
+        assert_eq!(soon.label(), "Expires in 0m 45s");
+        assert!(soon.is_urgent());
+        assert!(!SessionExpiry::Unknown.is_urgent());
+    }
+
+    #[test]
+    fn expiry_is_read_from_session_details() {
+        let details = "SessionInfo {\n    public_key: abc,\n    expires_at: Some(\n        1700000000000,\n    ),\n}";
+        assert_eq!(expiry_from_details(details), Some(1_700_000_000));
+        assert_eq!(
+            expiry_from_details("SessionInfo {\n    expires_at: 1700000000000000,\n}"),
+            Some(1_700_000_000)
+        );
+        assert_eq!(
+            expiry_from_details("expiry: Some(1700000000)"),
+            Some(1_700_000_000)
+        );
+        assert_eq!(
+            expiry_from_details("SessionInfo {\n    created_at: 1,\n}"),
+            None
+        );
+        assert_eq!(
+            expiry_from_details("SessionInfo {\n    expires_at: None,\n    version: 2,\n}"),
+            None
+        );
+    }
+
+    #[test]
+    fn signup_checklist_tracks_progress() {
+        let running = SignupProgress::Running(SignupStep::Connecting);
+        assert_eq!(running.step_state(SignupStep::Resolving), "done");
+        assert_eq!(running.step_state(SignupStep::Connecting), "active");
+        assert_eq!(running.step_state(SignupStep::SigningUp), "pending");
+
+        let failed = SignupProgress::Failed {
+            step: SignupStep::SigningUp,
+            message: "rejected".into(),
+        };
+        assert_eq!(failed.step_state(SignupStep::Connecting), "done");
+        assert_eq!(failed.step_state(SignupStep::SigningUp), "failed");
+        assert_eq!(
+            SignupProgress::Succeeded.step_state(SignupStep::SigningUp),
+            "done"
+        );
+    }
+
+    #[test]
+    fn invites_fill_homeserver_and_code() -> Result<()> {
+        let homeserver = Keypair::random().public_key().to_string();
+        let invite = parse_invite(&format!(" {INVITE_SCHEME}{homeserver}?code=AB12-CD34 "))?;
+        assert_eq!(invite.homeserver, homeserver);
+        assert_eq!(invite.signup_code, "AB12-CD34");
+
+        let open = parse_invite(&format!("{INVITE_SCHEME}{homeserver}"))?;
+        assert!(open.signup_code.is_empty());
+
+        assert!(parse_invite(&homeserver).is_err());
+        assert!(parse_invite(&format!("{INVITE_SCHEME}not-a-key?code=x")).is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,530 @@
+use anyhow::{Context, Result, anyhow};
+use bytes::Bytes;
+use pubky::{Pubky, PubkySession, PublicKey};
+use reqwest::Method;
+use reqwest::header::{CONTENT_TYPE, COOKIE};
+use std::fs;
+use std::path::Path;
+use url::Url;
+
+/// A clickable breadcrumb for a storage path: the rendered label plus the
+/// absolute prefix it navigates to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Breadcrumb {
+    pub label: String,
+    pub prefix: String,
+}
+
+/// Split an absolute storage path into breadcrumbs, e.g. `/pub/photos/2024/`
+/// becomes `/pub/`, `photos/`, `2024/`.
+pub fn path_breadcrumbs(path: &str) -> Vec<Breadcrumb> {
+    let trimmed = path.trim();
+    let mut crumbs = Vec::new();
+    let mut prefix = String::from("/");
+    let segments: Vec<&str> = trimmed.split('/').filter(|s| !s.is_empty()).collect();
+    let ends_with_slash = trimmed.ends_with('/');
+
+    for (index, segment) in segments.iter().enumerate() {
+        let is_last = index + 1 == segments.len();
+        let is_dir = !is_last || ends_with_slash;
+        prefix.push_str(segment);
+        if is_dir {
+            prefix.push('/');
+        }
+        let mut label = if index == 0 {
+            format!("/{segment}")
+        } else {
+            segment.to_string()
+        };
+        if is_dir {
+            label.push('/');
+        }
+        crumbs.push(Breadcrumb {
+            label,
+            prefix: prefix.clone(),
+        });
+    }
+
+    crumbs
+}
+
+/// Return the directory one level above `path`. Files resolve to their
+/// containing directory and the root stays at `/`.
+pub fn parent_directory(path: &str) -> String {
+    let trimmed = path.trim().trim_end_matches('/');
+    match trimmed.rfind('/') {
+        Some(index) => trimmed[..=index].to_string(),
+        None => String::from("/"),
+    }
+}
+
+/// Return `path` when it already names a directory, otherwise its parent.
+pub fn directory_of(path: &str) -> String {
+    let trimmed = path.trim();
+    if trimmed.is_empty() {
+        String::from("/")
+    } else if trimmed.ends_with('/') {
+        trimmed.to_string()
+    } else {
+        parent_directory(trimmed)
+    }
+}
+
+/// Strip the `pubky://<public key>` (or `pubky<public key>`) prefix from a
+/// listing entry so it can be reused as an absolute session path.
+pub fn entry_path(entry: &str) -> String {
+    let trimmed = entry.trim();
+    let rest = trimmed
+        .strip_prefix("pubky://")
+        .or_else(|| trimmed.strip_prefix("pubky"))
+        .unwrap_or(trimmed);
+    if rest.starts_with('/') {
+        return rest.to_string();
+    }
+    match rest.find('/') {
+        Some(index) => rest[index..].to_string(),
+        None => String::from("/"),
+    }
+}
+
+/// Walks a session directory listing one page at a time, passing the last
+/// entry's path as the cursor for the next page.
+pub struct DirectoryPager<'a> {
+    session: &'a PubkySession,
+    directory: String,
+    page_size: u16,
+    cursor: Option<String>,
+    done: bool,
+}
+
+impl<'a> DirectoryPager<'a> {
+    pub fn new(session: &'a PubkySession, directory: &str, page_size: u16) -> Self {
+        Self {
+            session,
+            directory: directory.to_string(),
+            page_size,
+            cursor: None,
+            done: false,
+        }
+    }
+
+    /// Paths of the next page of entries, or `None` once the listing is done.
+    pub async fn next_page(&mut self) -> Result<Option<Vec<String>>> {
+        if self.done {
+            return Ok(None);
+        }
+        let mut request = self
+            .session
+            .storage()
+            .list(self.directory.clone())?
+            .limit(self.page_size);
+        if let Some(cursor) = self.cursor.as_deref() {
+            request = request.cursor(cursor);
+        }
+        let page: Vec<String> = request
+            .send()
+            .await?
+            .into_iter()
+            .map(|entry| entry_path(&entry.to_string()))
+            .collect();
+        self.cursor = next_page_cursor(&page, self.page_size);
+        self.done = self.cursor.is_none();
+        Ok(Some(page))
+    }
+}
+
+/// Cursor for the page after `page`: the path of its last entry, or `None`
+/// when a short page shows the listing is exhausted.
+fn next_page_cursor(page: &[String], page_size: u16) -> Option<String> {
+    if page.len() < usize::from(page_size) {
+        return None;
+    }
+    page.last().cloned()
+}
+
+/// Build the address for a public fetch from either a full resource
+/// (`pubky://<key>/path` or `pubky<key>/path`) or a user key plus a path.
+pub fn public_resource_target(owner: &str, resource: &str) -> Result<String> {
+    let owner = owner.trim();
+    let resource = resource.trim();
+    if owner.is_empty() {
+        if resource.is_empty() {
+            return Err(anyhow!("Provide a resource to fetch"));
+        }
+        if !resource.starts_with("pubky") {
+            return Err(anyhow!(
+                "Enter a pubky:// link, or fill in the user key and use a /pub/ path"
+            ));
+        }
+        return Ok(resource.to_string());
+    }
+
+    let key = owner
+        .strip_prefix("pubky://")
+        .or_else(|| owner.strip_prefix("pubky"))
+        .unwrap_or(owner)
+        .trim_end_matches('/');
+    PublicKey::try_from(key).map_err(|err| anyhow!("Invalid user key: {err}"))?;
+    let path = if resource.starts_with("pubky") {
+        entry_path(resource)
+    } else if resource.starts_with('/') {
+        resource.to_string()
+    } else {
+        format!("/{resource}")
+    };
+    if !path.starts_with("/pub/") {
+        return Err(anyhow!("Public paths start with /pub/"));
+    }
+    Ok(format!("pubky://{key}{path}"))
+}
+
+/// Whether an entry is stored at `path`, checked with a HEAD request before a
+/// PUT that would replace it.
+pub async fn entry_exists(pubky: &Pubky, session: &PubkySession, path: &str) -> Result<bool> {
+    let url = Url::parse(&format!(
+        "pubky://{}{}",
+        session.info().public_key(),
+        path.trim()
+    ))?;
+    let status = pubky
+        .client()
+        .request(Method::HEAD, url)
+        .send()
+        .await?
+        .status();
+    if status.is_success() {
+        Ok(true)
+    } else if status.as_u16() == 404 {
+        Ok(false)
+    } else {
+        Err(anyhow!("existence check returned status {status}"))
+    }
+}
+
+/// PUT `file` at `path` with the content type guessed from its name. Session
+/// storage writes take no headers, so this sends the request itself with the
+/// session cookie attached.
+pub async fn put_file(
+    pubky: &Pubky,
+    session: &PubkySession,
+    path: &str,
+    file: &UploadFile,
+) -> Result<reqwest::Response> {
+    let url = Url::parse(&format!(
+        "pubky://{}{}",
+        session.info().public_key(),
+        path.trim()
+    ))?;
+    let response = pubky
+        .client()
+        .request(Method::PUT, url)
+        .header(COOKIE, session_cookie(&session.export_secret())?)
+        .header(CONTENT_TYPE, file.content_type)
+        .body(file.bytes.clone())
+        .send()
+        .await?;
+    Ok(response.error_for_status()?)
+}
+
+/// `Cookie` header value for an exported session secret
+/// (`<public key>:<cookie secret>`); the homeserver names the cookie after the
+/// user's public key.
+fn session_cookie(exported: &str) -> Result<String> {
+    match exported.split_once(':') {
+        Some((public_key, secret)) if !public_key.is_empty() && !secret.is_empty() => {
+            Ok(format!("{public_key}={secret}"))
+        }
+        _ => Err(anyhow!(
+            "session secret is not in <public key>:<cookie> form"
+        )),
+    }
+}
+
+/// Recognise the "no such entry" error the SDK raises for a 404.
+pub fn is_not_found(message: &str) -> bool {
+    let lower = message.to_lowercase();
+    lower.contains("404") || lower.contains("not found")
+}
+
+/// Byte range for a partial GET. `end` is inclusive; `None` reads to the end of
+/// the entry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ByteRange {
+    pub start: u64,
+    pub end: Option<u64>,
+}
+
+impl ByteRange {
+    /// Parse the start/end inputs. An empty start means offset zero.
+    pub fn parse(start: &str, end: &str) -> Result<Self> {
+        let start = match start.trim() {
+            "" => 0,
+            raw => raw
+                .parse()
+                .map_err(|_| anyhow!("Range start must be a byte offset"))?,
+        };
+        let end = match end.trim() {
+            "" => None,
+            raw => Some(
+                raw.parse()
+                    .map_err(|_| anyhow!("Range end must be a byte offset"))?,
+            ),
+        };
+        if let Some(end) = end
+            && end < start
+        {
+            return Err(anyhow!("Range end must not be before the start"));
+        }
+        Ok(Self { start, end })
+    }
+
+    /// Value for the `Range` request header.
+    pub fn header_value(&self) -> String {
+        match self.end {
+            Some(end) => format!("bytes={}-{end}", self.start),
+            None => format!("bytes={}-", self.start),
+        }
+    }
+}
+
+/// One-line summary of what a ranged GET returned, based on the status and the
+/// `Content-Range` header.
+pub fn describe_range_response(
+    status: u16,
+    content_range: Option<&str>,
+    body_len: usize,
+) -> String {
+    let parsed = content_range.and_then(|value| value.trim().strip_prefix("bytes "));
+    match (status, parsed) {
+        (206, Some(spec)) => match spec.split_once('/') {
+            Some((range, "*")) => {
+                format!("Returned bytes {range} ({body_len} bytes, total size unknown)")
+            }
+            Some((range, total)) => format!("Returned bytes {range} of {total} ({body_len} bytes)"),
+            None => format!("Returned bytes {spec} ({body_len} bytes)"),
+        },
+        (206, None) => format!("Returned a partial body of {body_len} bytes"),
+        (416, Some(spec)) => match spec.strip_prefix("*/") {
+            Some(total) => format!("Range not satisfiable; the entry is {total} bytes"),
+            None => String::from("Range not satisfiable"),
+        },
+        (416, None) => String::from("Range not satisfiable"),
+        (200, _) => {
+            format!("Server ignored the range and returned the full entry ({body_len} bytes)")
+        }
+        _ => format!("Range request returned status {status}"),
+    }
+}
+
+/// A local file chosen for upload, kept as raw bytes so binary content is
+/// stored unchanged.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UploadFile {
+    pub name: String,
+    pub bytes: Bytes,
+    pub content_type: &'static str,
+}
+
+impl UploadFile {
+    pub fn load(path: &Path) -> Result<Self> {
+        let bytes = fs::read(path).with_context(|| format!("failed to read {}", path.display()))?;
+        let name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_else(|| path.display().to_string());
+        Ok(Self {
+            content_type: guess_content_type(&name),
+            name,
+            bytes: Bytes::from(bytes),
+        })
+    }
+
+    /// `photo.png (2048 bytes, image/png)`.
+    pub fn summary(&self) -> String {
+        format!(
+            "{} ({} bytes, {})",
+            self.name,
+            self.bytes.len(),
+            self.content_type
+        )
+    }
+}
+
+/// MIME type for a file name, guessed from its extension. Unknown extensions
+/// are `application/octet-stream`.
+pub fn guess_content_type(name: &str) -> &'static str {
+    let extension = name
+        .rsplit_once('.')
+        .map(|(_, ext)| ext.to_ascii_lowercase())
+        .unwrap_or_default();
+    match extension.as_str() {
+        "json" => "application/json",
+        "txt" => "text/plain",
+        "md" => "text/markdown",
+        "html" | "htm" => "text/html",
+        "css" => "text/css",
+        "js" => "text/javascript",
+        "csv" => "text/csv",
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "webp" => "image/webp",
+        "svg" => "image/svg+xml",
+        "pdf" => "application/pdf",
+        "mp3" => "audio/mpeg",
+        "wav" => "audio/wav",
+        "mp4" => "video/mp4",
+        "webm" => "video/webm",
+        "zip" => "application/zip",
+        _ => "application/octet-stream",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn breadcrumbs_split_directories_and_files() {
+        let crumbs = path_breadcrumbs("/pub/photos/2024/");
+        let labels: Vec<&str> = crumbs.iter().map(|c| c.label.as_str()).collect();
+        let prefixes: Vec<&str> = crumbs.iter().map(|c| c.prefix.as_str()).collect();
+        assert_eq!(labels, ["/pub/", "photos/", "2024/"]);
+        assert_eq!(prefixes, ["/pub/", "/pub/photos/", "/pub/photos/2024/"]);
+
+        let file = path_breadcrumbs("/pub/app/file.txt");
+        assert_eq!(file.last().map(|c| c.label.as_str()), Some("file.txt"));
+        assert_eq!(
+            file.last().map(|c| c.prefix.as_str()),
+            Some("/pub/app/file.txt")
+        );
+        assert!(path_breadcrumbs("/").is_empty());
+    }
+
+    #[test]
+    fn parent_directory_walks_up_one_level() {
+        assert_eq!(parent_directory("/pub/photos/2024/"), "/pub/photos/");
+        assert_eq!(parent_directory("/pub/app/file.txt"), "/pub/app/");
+        assert_eq!(parent_directory("/pub/"), "/");
+        assert_eq!(parent_directory("/"), "/");
+        assert_eq!(directory_of("/pub/app/file.txt"), "/pub/app/");
+        assert_eq!(directory_of("/pub/app/"), "/pub/app/");
+    }
+
+    #[test]
+    fn entry_path_strips_pubky_prefix() {
+        assert_eq!(
+            entry_path(
+                "pubky://o4dksfbqk85ogzdb5osziw6befigbuxmuxkuxq8434q89uj56uyy/pub/app/a.txt"
+            ),
+            "/pub/app/a.txt"
+        );
+        assert_eq!(
+            entry_path("pubkyo4dksfbqk85ogzdb5osziw6befigbuxmuxkuxq8434q89uj56uyy/pub/"),
+            "/pub/"
+        );
+        assert_eq!(entry_path("/pub/app/"), "/pub/app/");
+    }
+
+    #[test]
+    fn public_resource_target_accepts_links_and_key_path_pairs() {
+        let display = pubky::Keypair::random().public_key().to_string();
+        let key = display.strip_prefix("pubky").unwrap_or(&display);
+        let link = format!("pubky://{key}/pub/app/a.txt");
+        assert_eq!(public_resource_target("", &link).unwrap(), link);
+        assert_eq!(public_resource_target(key, "pub/app/a.txt").unwrap(), link);
+        assert_eq!(
+            public_resource_target(&format!("pubky{key}"), "/pub/app/a.txt").unwrap(),
+            link
+        );
+        assert!(public_resource_target("", "/pub/app/a.txt").is_err());
+        assert!(public_resource_target(key, "/private/a.txt").is_err());
+        assert!(public_resource_target("not-a-key", "/pub/app/a.txt").is_err());
+        assert!(public_resource_target("", " ").is_err());
+    }
+
+    #[test]
+    fn missing_entries_are_recognised() {
+        assert!(is_not_found("Server responded with 404 Not Found"));
+        assert!(is_not_found("entry not found"));
+        assert!(!is_not_found("error sending request: connection refused"));
+    }
+
+    #[test]
+    fn byte_range_parses_inputs() {
+        let range = ByteRange::parse("100", "199").unwrap();
+        assert_eq!(range.header_value(), "bytes=100-199");
+        assert_eq!(ByteRange::parse("", "").unwrap().header_value(), "bytes=0-");
+        assert_eq!(
+            ByteRange::parse(" 512 ", "").unwrap().header_value(),
+            "bytes=512-"
+        );
+        assert!(ByteRange::parse("10", "5").is_err());
+        assert!(ByteRange::parse("-1", "").is_err());
+        assert!(ByteRange::parse("0", "end").is_err());
+    }
+
+    #[test]
+    fn upload_files_keep_raw_bytes_and_guess_types() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("Avatar.PNG");
+        let data = [0x89, b'P', b'N', b'G', 0x00, 0xff];
+        fs::write(&path, data).unwrap();
+
+        let file = UploadFile::load(&path).unwrap();
+        assert_eq!(file.bytes.as_ref(), &data);
+        assert_eq!(file.summary(), "Avatar.PNG (6 bytes, image/png)");
+
+        assert_eq!(guess_content_type("post.json"), "application/json");
+        assert_eq!(
+            guess_content_type("archive.tar.gz"),
+            "application/octet-stream"
+        );
+        assert_eq!(guess_content_type("README"), "application/octet-stream");
+        assert!(UploadFile::load(&dir.path().join("missing")).is_err());
+    }
+
+    #[test]
+    fn page_cursors_are_entry_paths() {
+        let page = vec![
+            entry_path("pubkyo4dksf/pub/pubky.app/posts/A"),
+            entry_path("pubky://o4dksf/pub/pubky.app/posts/B"),
+        ];
+        assert_eq!(
+            next_page_cursor(&page, 2).as_deref(),
+            Some("/pub/pubky.app/posts/B")
+        );
+        assert_eq!(next_page_cursor(&page, 3), None);
+        assert_eq!(next_page_cursor(&[], 2), None);
+    }
+
+    #[test]
+    fn session_cookies_are_named_after_the_public_key() {
+        assert_eq!(
+            session_cookie("o4dksf:c2VjcmV0").unwrap(),
+            "o4dksf=c2VjcmV0"
+        );
+        assert!(session_cookie("o4dksf").is_err());
+        assert!(session_cookie(":secret").is_err());
+    }
+
+    #[test]
+    fn describes_range_responses() {
+        assert_eq!(
+            describe_range_response(206, Some("bytes 0-99/4096"), 100),
+            "Returned bytes 0-99 of 4096 (100 bytes)"
+        );
+        assert_eq!(
+            describe_range_response(206, Some("bytes 0-99/*"), 100),
+            "Returned bytes 0-99 (100 bytes, total size unknown)"
+        );
+        assert_eq!(
+            describe_range_response(416, Some("bytes */4096"), 0),
+            "Range not satisfiable; the entry is 4096 bytes"
+        );
+        assert_eq!(
+            describe_range_response(200, None, 4096),
+            "Server ignored the range and returned the full entry (4096 bytes)"
+        );
+    }
+}
@@ -0,0 +1,281 @@
+use std::collections::HashSet;
+
+use anyhow::Result;
+use futures::{StreamExt, stream};
+use pubky::PubkySession;
+use pubky_app_specs::{
+    PubkyAppTag,
+    traits::{HasIdPath, HashId, Validatable},
+};
+use serde_json::to_string_pretty;
+
+use crate::utils::storage::{DirectoryPager, is_not_found};
+
+/// Upper bound on tag writes in flight during a bulk run.
+pub const MAX_CONCURRENT_TAG_WRITES: usize = 4;
+
+/// Where pubky.app keeps the session user's tags.
+pub const TAGS_DIRECTORY: &str = "/pub/pubky.app/tags/";
+
+/// Tags requested per listing page when listing the session user's tags.
+pub const TAG_LIST_PAGE_SIZE: u16 = 100;
+
+/// Upper bound on tag reads in flight while listing.
+pub const MAX_CONCURRENT_TAG_READS: usize = 4;
+
+/// A validated tag, ready to be written to the session's storage.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PreparedTag {
+    pub path: String,
+    pub body: String,
+}
+
+/// Build, validate, and serialize a `PubkyAppTag`, the same way for the single
+/// and the bulk flow.
+pub fn prepare_tag(uri: &str, label: &str) -> Result<PreparedTag, String> {
+    if uri.trim().is_empty() {
+        return Err(String::from("Provide a URI to tag"));
+    }
+    if label.trim().is_empty() {
+        return Err(String::from("Provide a tag label"));
+    }
+    let tag = PubkyAppTag::new(uri.to_string(), label.to_string());
+    let tag_id = tag.create_id();
+    tag.validate(Some(&tag_id))
+        .map_err(|err| format!("Invalid tag: {err}"))?;
+    let body = to_string_pretty(&tag).map_err(|err| format!("Failed to serialize tag: {err}"))?;
+    Ok(PreparedTag {
+        path: PubkyAppTag::create_path(&tag_id),
+        body,
+    })
+}
+
+/// Outcome of writing one tag in a bulk run.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum BulkTagOutcome {
+    Created(String),
+    Invalid(String),
+    Failed(String),
+}
+
+impl BulkTagOutcome {
+    pub fn status(&self) -> &'static str {
+        match self {
+            BulkTagOutcome::Created(_) => "created",
+            BulkTagOutcome::Invalid(_) => "invalid",
+            BulkTagOutcome::Failed(_) => "error",
+        }
+    }
+
+    /// Short text for the results table.
+    pub fn summary(&self) -> String {
+        match self {
+            BulkTagOutcome::Created(path) => path.clone(),
+            BulkTagOutcome::Invalid(message) => message.clone(),
+            BulkTagOutcome::Failed(message) => format!("error: {message}"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BulkTagRow {
+    pub uri: String,
+    pub label: String,
+    pub outcome: BulkTagOutcome,
+}
+
+/// One target URI per line, trimmed and de-duplicated.
+pub fn parse_uri_list(input: &str) -> Vec<String> {
+    dedupe(input.lines())
+}
+
+/// Labels separated by commas or newlines, trimmed and de-duplicated.
+pub fn parse_label_list(input: &str) -> Vec<String> {
+    dedupe(input.split([',', '\n']))
+}
+
+fn dedupe<'a>(items: impl Iterator<Item = &'a str>) -> Vec<String> {
+    let mut seen = HashSet::new();
+    items
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+        .filter(|item| seen.insert(item.to_string()))
+        .map(str::to_string)
+        .collect()
+}
+
+/// Every URI × label pair, in input order (all labels of the first URI first).
+pub fn bulk_pairs(uris: &[String], labels: &[String]) -> Vec<(String, String)> {
+    uris.iter()
+        .flat_map(|uri| labels.iter().map(move |label| (uri.clone(), label.clone())))
+        .collect()
+}
+
+/// Write a tag for every pair with bounded concurrency, keeping the input
+/// order. Pairs that fail validation are reported without a request.
+pub async fn apply_bulk_tags(
+    session: &PubkySession,
+    pairs: Vec<(String, String)>,
+) -> Vec<BulkTagRow> {
+    stream::iter(pairs)
+        .map(|(uri, label)| async move {
+            let outcome = match prepare_tag(&uri, &label) {
+                Ok(prepared) => match write_tag(session, &prepared).await {
+                    Ok(()) => BulkTagOutcome::Created(prepared.path),
+                    Err(err) => BulkTagOutcome::Failed(err.to_string()),
+                },
+                Err(message) => BulkTagOutcome::Invalid(message),
+            };
+            BulkTagRow {
+                uri,
+                label,
+                outcome,
+            }
+        })
+        .buffered(MAX_CONCURRENT_TAG_WRITES)
+        .collect()
+        .await
+}
+
+/// Result of removing the tag for a URI and label.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RemoveTagOutcome {
+    Removed(String),
+    NotFound(String),
+}
+
+/// Delete the tag `prepare_tag` would create for `uri` and `label`. A missing
+/// tag is reported as [`RemoveTagOutcome::NotFound`] rather than an error.
+pub async fn remove_tag(
+    session: &PubkySession,
+    uri: &str,
+    label: &str,
+) -> Result<RemoveTagOutcome, String> {
+    let path = prepare_tag(uri, label)?.path;
+    match session.storage().delete(path.clone()).await {
+        Ok(response) if response.status().as_u16() == 404 => Ok(RemoveTagOutcome::NotFound(path)),
+        Ok(response) if !response.status().is_success() => Err(format!(
+            "Deleting {path} returned status {}",
+            response.status()
+        )),
+        Ok(_) => Ok(RemoveTagOutcome::Removed(path)),
+        Err(err) => {
+            let message = format!("{:#}", anyhow::Error::from(err));
+            if is_not_found(&message) {
+                Ok(RemoveTagOutcome::NotFound(path))
+            } else {
+                Err(message)
+            }
+        }
+    }
+}
+
+/// One of the session user's tags, or the reason it could not be read.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ListedTag {
+    pub path: String,
+    pub tag: Result<(String, String), String>,
+}
+
+impl ListedTag {
+    /// Read a stored tag body as its label and target URI.
+    pub fn parse(path: String, body: &[u8]) -> Self {
+        let tag = serde_json::from_slice::<PubkyAppTag>(body)
+            .map(|tag| (tag.label, tag.uri))
+            .map_err(|err| format!("not a tag: {err}"));
+        Self { path, tag }
+    }
+}
+
+/// Every one of the session user's tags under [`TAGS_DIRECTORY`], listed page
+/// by page and each fetched and parsed with bounded concurrency.
+pub async fn list_session_tags(session: &PubkySession) -> Result<Vec<ListedTag>> {
+    let mut tags = Vec::new();
+    let mut pager = DirectoryPager::new(session, TAGS_DIRECTORY, TAG_LIST_PAGE_SIZE);
+    while let Some(page) = pager.next_page().await? {
+        tags.extend(read_listed_tags(session, page).await);
+    }
+    Ok(tags)
+}
+
+async fn read_listed_tags(session: &PubkySession, paths: Vec<String>) -> Vec<ListedTag> {
+    stream::iter(paths)
+        .map(|path| async move {
+            let body = async {
+                let response = session.storage().get(path.clone()).await?;
+                Ok::<_, anyhow::Error>(response.bytes().await?)
+            };
+            match body.await {
+                Ok(bytes) => ListedTag::parse(path, &bytes),
+                Err(err) => ListedTag {
+                    path,
+                    tag: Err(format!("error: {err}")),
+                },
+            }
+        })
+        .buffered(MAX_CONCURRENT_TAG_READS)
+        .collect()
+        .await
+}
+
+async fn write_tag(session: &PubkySession, prepared: &PreparedTag) -> Result<()> {
+    session
+        .storage()
+        .put(prepared.path.clone(), prepared.body.clone())
+        .await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lists_are_trimmed_and_deduped() {
+        assert_eq!(
+            parse_uri_list(" pubky://a/pub/x \n\npubky://b/pub/y\npubky://a/pub/x\n"),
+            vec!["pubky://a/pub/x", "pubky://b/pub/y"]
+        );
+        assert_eq!(
+            parse_label_list("rust, p2p\nrust,,\n  pubky "),
+            vec!["rust", "p2p", "pubky"]
+        );
+    }
+
+    #[test]
+    fn pairs_cover_every_uri_and_label() {
+        let uris = vec![String::from("u1"), String::from("u2")];
+        let labels = vec![String::from("a"), String::from("b")];
+        let pairs: Vec<String> = bulk_pairs(&uris, &labels)
+            .into_iter()
+            .map(|(uri, label)| format!("{uri}:{label}"))
+            .collect();
+        assert_eq!(pairs, vec!["u1:a", "u1:b", "u2:a", "u2:b"]);
+    }
+
+    #[test]
+    fn listed_tags_read_label_and_target() {
+        let display = pubky::Keypair::random().public_key().to_string();
+        let key = display.strip_prefix("pubky").unwrap_or(&display);
+        let target = format!("pubky://{key}/pub/pubky.app/posts/0033SSE3B1FQ0");
+        let prepared = prepare_tag(&target, "rust").expect("valid tag");
+        let listed = ListedTag::parse(prepared.path.clone(), prepared.body.as_bytes());
+        assert_eq!(listed.path, prepared.path);
+        assert_eq!(listed.tag, Ok((String::from("rust"), target)));
+
+        let broken = ListedTag::parse(String::from("/pub/pubky.app/tags/X"), b"{}");
+        assert!(broken.tag.is_err());
+    }
+
+    #[test]
+    fn empty_inputs_are_rejected_before_validation() {
+        assert_eq!(
+            prepare_tag(" ", "rust"),
+            Err(String::from("Provide a URI to tag"))
+        );
+        assert_eq!(
+            prepare_tag("pubky://a/pub/x", ""),
+            Err(String::from("Provide a tag label"))
+        );
+    }
+}
@@ -0,0 +1,52 @@
+use std::future::Future;
+
+use dioxus::prelude::{ReadableExt, Signal, Task, WritableExt, spawn};
+
+/// Slot for one cancellable background request. While a task is running its
+/// button turns into "Cancel"; cancelling drops the future, which closes any
+/// connection or lookup it still holds.
+#[derive(Clone, Copy)]
+pub struct InFlight {
+    task: Signal<Option<Task>>,
+}
+
+impl InFlight {
+    pub fn new(task: Signal<Option<Task>>) -> Self {
+        Self { task }
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.task.read().is_some()
+    }
+
+    /// Like [`InFlight::is_running`] without subscribing, for effects that
+    /// restart the task themselves.
+    pub fn peek_running(&self) -> bool {
+        self.task.peek().is_some()
+    }
+
+    /// Spawn `future` in this slot, cancelling whatever ran there before.
+    pub fn start(&self, future: impl Future<Output = ()> + 'static) {
+        let mut slot = self.task;
+        let task = spawn(async move {
+            future.await;
+            slot.set(None);
+        });
+        if let Some(previous) = slot.write().replace(task) {
+            previous.cancel();
+        }
+    }
+
+    /// Abort the running task. Returns `false` when nothing was in flight.
+    pub fn cancel(&self) -> bool {
+        let mut slot = self.task;
+        let running = slot.write().take();
+        match running {
+            Some(task) => {
+                task.cancel();
+                true
+            }
+            None => false,
+        }
+    }
+}
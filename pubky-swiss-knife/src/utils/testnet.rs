@@ -0,0 +1,100 @@
+use std::net::{Ipv4Addr, SocketAddr, TcpStream};
+use std::time::Duration;
+
+/// Where to get a local testnet: the Portable Homeserver's Testnet mode.
+pub const PORTABLE_HOMESERVER_URL: &str =
+    "https://github.com/SHAcollision/pubky-vibes/tree/main/portable-homeserver";
+
+/// Public key of the homeserver `Pubky::testnet()` starts, fixed across runs.
+pub const TESTNET_HOMESERVER_KEY: &str = "8pinxxgqs41n4aididenw5apqp1urfmzdztr8jt4abrkdn435ewo";
+
+/// Local services `Pubky::testnet()` talks to, on the fixed ports the static
+/// testnet binds.
+pub const TESTNET_SERVICES: [(&str, u16); 3] = [
+    ("pkarr relay", 15411),
+    ("HTTP relay", 15412),
+    ("homeserver", 6286),
+];
+
+const PROBE_TIMEOUT: Duration = Duration::from_millis(800);
+
+/// Result of checking whether a local testnet is up.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum TestnetProbe {
+    #[default]
+    Idle,
+    Checking,
+    Reachable,
+    /// Names of the services that did not accept a connection.
+    Unreachable(Vec<&'static str>),
+}
+
+impl TestnetProbe {
+    pub fn is_checking(&self) -> bool {
+        matches!(self, TestnetProbe::Checking)
+    }
+
+    pub fn summary(&self) -> Option<String> {
+        match self {
+            TestnetProbe::Unreachable(missing) => Some(format!(
+                "Nothing answered on localhost for the {}.",
+                missing.join(", ")
+            )),
+            _ => None,
+        }
+    }
+}
+
+/// Services from [`TESTNET_SERVICES`] whose port is not open.
+fn unreachable_services(port_open: impl Fn(u16) -> bool) -> Vec<&'static str> {
+    TESTNET_SERVICES
+        .iter()
+        .filter(|(_, port)| !port_open(*port))
+        .map(|(name, _)| *name)
+        .collect()
+}
+
+fn localhost_port_open(port: u16) -> bool {
+    TcpStream::connect_timeout(
+        &SocketAddr::from((Ipv4Addr::LOCALHOST, port)),
+        PROBE_TIMEOUT,
+    )
+    .is_ok()
+}
+
+/// Try each testnet service on localhost.
+pub async fn probe_local_testnet() -> TestnetProbe {
+    let missing = tokio::task::spawn_blocking(|| unreachable_services(localhost_port_open))
+        .await
+        .unwrap_or_else(|_| TESTNET_SERVICES.iter().map(|(name, _)| *name).collect());
+    if missing.is_empty() {
+        TestnetProbe::Reachable
+    } else {
+        TestnetProbe::Unreachable(missing)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lists_services_whose_ports_are_closed() {
+        assert!(unreachable_services(|_| true).is_empty());
+        assert_eq!(
+            unreachable_services(|port| port == 6286),
+            vec!["pkarr relay", "HTTP relay"]
+        );
+    }
+
+    #[test]
+    fn only_unreachable_probes_have_a_summary() {
+        assert_eq!(TestnetProbe::Reachable.summary(), None);
+        assert_eq!(
+            TestnetProbe::Unreachable(vec!["pkarr relay", "homeserver"]).summary(),
+            Some(String::from(
+                "Nothing answered on localhost for the pkarr relay, homeserver."
+            ))
+        );
+    }
+}
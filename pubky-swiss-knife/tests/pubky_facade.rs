@@ -0,0 +1,36 @@
+use anyhow::Result;
+use pubky::{Keypair, PublicKey};
+use pubky_swiss_knife::utils::pubky::{FacadeConfig, build_pubky_facade_with};
+use pubky_swiss_knife::utils::testnet::TESTNET_HOMESERVER_KEY;
+use pubky_testnet::StaticTestnet;
+
+#[tokio::test]
+async fn testnet_facade_reads_public_storage() -> Result<()> {
+    let _testnet = StaticTestnet::start().await?;
+    let facade = build_pubky_facade_with(FacadeConfig::Testnet {
+        host: String::from("localhost"),
+    })
+    .await?;
+
+    let homeserver = PublicKey::try_from(TESTNET_HOMESERVER_KEY)?;
+    let keypair = Keypair::random();
+    let session = facade
+        .signer(keypair.clone())
+        .signup(&homeserver, None)
+        .await?;
+    session
+        .storage()
+        .put("/pub/swiss-knife/hello.txt", "hello")
+        .await?;
+
+    let response = facade
+        .public_storage()
+        .get(format!(
+            "{}/pub/swiss-knife/hello.txt",
+            keypair.public_key()
+        ))
+        .await?;
+    assert!(response.status().is_success());
+    assert_eq!(response.text().await?, "hello");
+    Ok(())
+}